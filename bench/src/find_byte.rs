@@ -0,0 +1,52 @@
+use getargs::Argument;
+use std::hint::black_box;
+use test::Bencher;
+
+const SHORT: &[u8] = b"--out=x";
+const LONG: &[u8] = b"--a-rather-long-option-name-for-benchmarking=value";
+const NONE: &[u8] = b"--a-rather-long-option-name-with-no-value-at-all-in-it";
+
+/// The byte-by-byte search that `find_byte` replaced.
+#[inline(always)]
+fn naive(arg: &[u8]) -> (&[u8], Option<&[u8]>) {
+    match arg.iter().position(|b| *b == b'=') {
+        Some(i) => (&arg[..i], Some(&arg[i + 1..])),
+        None => (arg, None),
+    }
+}
+
+#[bench]
+#[inline(never)]
+fn naive_short(bencher: &mut Bencher) {
+    bencher.iter(|| naive(black_box(SHORT)));
+}
+
+#[bench]
+#[inline(never)]
+fn swar_short(bencher: &mut Bencher) {
+    bencher.iter(|| black_box(SHORT).split_on(b'='));
+}
+
+#[bench]
+#[inline(never)]
+fn naive_long(bencher: &mut Bencher) {
+    bencher.iter(|| naive(black_box(LONG)));
+}
+
+#[bench]
+#[inline(never)]
+fn swar_long(bencher: &mut Bencher) {
+    bencher.iter(|| black_box(LONG).split_on(b'='));
+}
+
+#[bench]
+#[inline(never)]
+fn naive_none(bencher: &mut Bencher) {
+    bencher.iter(|| naive(black_box(NONE)));
+}
+
+#[bench]
+#[inline(never)]
+fn swar_none(bencher: &mut Bencher) {
+    bencher.iter(|| black_box(NONE).split_on(b'='));
+}
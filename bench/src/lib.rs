@@ -16,6 +16,14 @@ mod versus;
 #[cfg(test)]
 mod evolution;
 
+/// Compares the word-at-a-time byte search that `getargs` uses for
+/// `--option=value` and `split_on` on `&[u8]` arguments to a plain
+/// byte-by-byte search.
+///
+/// `cargo bench -- find_byte`
+#[cfg(test)]
+mod find_byte;
+
 pub const ARGS: [&str; 12] = [
     "-1",         // short_present1
     "-3",         // short_present3
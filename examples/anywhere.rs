@@ -53,8 +53,8 @@ value, just like print.rs.
                 eprintln!("option {:?}: {:?}", arg, opts.value());
             }
 
-            Arg::Short(_) | Arg::Long(_) => eprintln!("option: {:?}", arg),
             Arg::Positional(arg) => eprintln!("positional: {:?}", arg),
+            _ => eprintln!("option: {:?}", arg),
         }
     }
 }
@@ -0,0 +1,105 @@
+//! An iterator over the `argc` and `argv` that C-style entry points
+//! receive, for parsing them with [`getargs`](https://docs.rs/getargs).
+//!
+//! This crate is `#![no_std]` and does not allocate, so it can be used
+//! in `#[no_main]` binaries and libraries that are called from C. It
+//! lives outside of `getargs` so that `getargs` can stay free of
+//! `unsafe` code.
+
+#![no_std]
+
+use core::ffi::{c_char, c_int, CStr};
+use core::iter::{FusedIterator, Map};
+use core::marker::PhantomData;
+
+/// An iterator over the arguments that a C-style entry point receives
+/// as `argc` and `argv`, for `#[no_main]` binaries and libraries called
+/// from C, where `std::env::args` is not available.
+///
+/// Arguments are borrowed as [`CStr`]s, and [`Argv::bytes`] turns them
+/// into `&[u8]`s that can be passed straight to `Options::new`. The
+/// first argument is usually the program name.
+///
+/// # Example
+///
+/// ```
+/// # use core::ffi::{c_char, c_int};
+/// # use getargs::{Opt, Options};
+/// # use getargs_argv::Argv;
+/// #
+/// #[no_mangle]
+/// pub unsafe extern "C" fn tool_main(argc: c_int, argv: *const *const c_char) -> c_int {
+///     let mut opts = Options::new(Argv::new(argc, argv).bytes().skip(1));
+///
+///     while let Ok(Some(opt)) = opts.next_opt() {
+///         if opt == Opt::Short(b'v') {
+///             return 1;
+///         }
+///     }
+///
+///     0
+/// }
+///
+/// let argv = [c"tool".as_ptr(), c"-v".as_ptr()];
+/// assert_eq!(unsafe { tool_main(2, argv.as_ptr()) }, 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Argv<'a> {
+    argv: *const *const c_char,
+    len: usize,
+    marker: PhantomData<&'a CStr>,
+}
+
+impl<'a> Argv<'a> {
+    /// Creates an iterator over the `argc` arguments in `argv`. A null
+    /// pointer in `argv` ends the arguments early, and `argv` itself
+    /// can be null if `argc` is not positive.
+    ///
+    /// # Safety
+    ///
+    /// `argv` must point to `argc` pointers, each of which must be null
+    /// or a valid NUL-terminated string. The strings must not change or
+    /// be freed for `'a`.
+    pub unsafe fn new(argc: c_int, argv: *const *const c_char) -> Self {
+        Self {
+            argv,
+            len: argc.max(0) as usize,
+            marker: PhantomData,
+        }
+    }
+
+    /// Converts this into an iterator over the bytes of each argument,
+    /// without their NUL terminators.
+    pub fn bytes(self) -> Map<Self, fn(&'a CStr) -> &'a [u8]> {
+        self.map(CStr::to_bytes)
+    }
+}
+
+impl<'a> Iterator for Argv<'a> {
+    type Item = &'a CStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // SAFETY: `Argv::new` requires `argv` to point to `len` more
+        // pointers, which are null or valid strings for `'a`.
+        let arg = unsafe { *self.argv };
+
+        if arg.is_null() {
+            self.len = 0;
+            return None;
+        }
+
+        self.len -= 1;
+        self.argv = unsafe { self.argv.add(1) };
+        Some(unsafe { CStr::from_ptr(arg) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.len))
+    }
+}
+
+impl FusedIterator for Argv<'_> {}
@@ -0,0 +1,33 @@
+use core::ffi::{c_char, CStr};
+use core::ptr;
+
+use getargs::{Opt, Options};
+use getargs_argv::Argv;
+
+fn args(args: &[&CStr]) -> Vec<*const c_char> {
+    args.iter().map(|arg| arg.as_ptr()).collect()
+}
+
+#[test]
+fn argv() {
+    let argv = args(&[c"prog", c"-v", c""]);
+    let collected: Vec<&CStr> = unsafe { Argv::new(3, argv.as_ptr()) }.collect();
+    assert_eq!(collected, [c"prog", c"-v", c""]);
+
+    let bytes: Vec<&[u8]> = unsafe { Argv::new(2, argv.as_ptr()) }.bytes().collect();
+    assert_eq!(bytes, [&b"prog"[..], b"-v"]);
+
+    let terminated = [c"a".as_ptr(), ptr::null(), c"b".as_ptr()];
+    let mut iter = unsafe { Argv::new(3, terminated.as_ptr()) };
+    assert_eq!(iter.next(), Some(c"a"));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(unsafe { Argv::new(0, ptr::null()) }.next(), None);
+    assert_eq!(unsafe { Argv::new(-1, ptr::null()) }.next(), None);
+
+    let mut opts = Options::new(unsafe { Argv::new(3, argv.as_ptr()) }.bytes().skip(1));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'v'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some(&b""[..]));
+}
@@ -0,0 +1,82 @@
+/*
+ * C API for getargs, a truly zero-cost argument parser.
+ *
+ * See the documentation of the getargs_capi crate for details.
+ */
+
+#ifndef GETARGS_H
+#define GETARGS_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The kinds of options. */
+#define GETARGS_SHORT 0
+#define GETARGS_LONG 1
+#define GETARGS_SHORT_PLUS 2
+#define GETARGS_NUMERIC 3
+
+/* Returned when the arguments could not be parsed. */
+#define GETARGS_ERROR (-1)
+/* Returned when a function was called out of order. */
+#define GETARGS_MISUSE (-2)
+
+/* A parser over a copy of some arguments. */
+typedef struct getargs_parser getargs_parser;
+
+/* An option returned by getargs_next_opt. */
+typedef struct getargs_opt {
+    /* One of GETARGS_SHORT, GETARGS_LONG, GETARGS_SHORT_PLUS or
+     * GETARGS_NUMERIC. */
+    int kind;
+    /* The name of a short option, or 0 for other options. */
+    char short_opt;
+    /* The name of a long option, or the digits of a numeric option,
+     * without the leading dashes. NULL for short options. */
+    const char *name;
+    /* The length of name in bytes. */
+    size_t name_len;
+} getargs_opt;
+
+/* Creates a parser over a copy of the argc arguments in argv, which
+ * should not include the program name. */
+getargs_parser *getargs_new(int argc, const char *const *argv);
+
+/* Frees a parser. Does nothing if parser is NULL. */
+void getargs_free(getargs_parser *parser);
+
+/* Parses the next option into opt. Returns 1 if there is an option, 0
+ * if there are no more options, or GETARGS_ERROR. */
+int getargs_next_opt(getargs_parser *parser, getargs_opt *opt);
+
+/* Takes the value of the last option. Returns 0 on success,
+ * GETARGS_ERROR if there is no value, or GETARGS_MISUSE. value and len
+ * can be NULL. */
+int getargs_value(getargs_parser *parser, const char **value, size_t *len);
+
+/* Takes the value of the last option only if it was attached to it.
+ * Returns 1 if there is a value, 0 if there is not, or GETARGS_MISUSE.
+ * value and len can be NULL. */
+int getargs_value_opt(getargs_parser *parser, const char **value, size_t *len);
+
+/* Takes the next positional argument. Returns 1 if there is one, 0 if
+ * there are no more arguments, or GETARGS_MISUSE if option parsing has
+ * not finished. arg and len can be NULL. */
+int getargs_next_positional(getargs_parser *parser, const char **arg, size_t *len);
+
+/* Returns the message of the last error, or NULL if there has not been
+ * one. */
+const char *getargs_error(const getargs_parser *parser);
+
+/* Returns the sysexits.h exit code for the last error, or 0 if there
+ * has not been one. */
+int getargs_exit_code(const getargs_parser *parser);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GETARGS_H */
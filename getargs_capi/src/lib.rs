@@ -0,0 +1,362 @@
+//! C API for [`getargs`](https://docs.rs/getargs).
+//!
+//! This crate builds a static and a dynamic library that let C and C++
+//! programs parse arguments with `getargs`. It lives outside of
+//! `getargs` itself so that `getargs` can stay free of `unsafe` code;
+//! Rust programs should use `getargs` directly.
+//!
+//! The API is declared in `include/getargs.h`, where [`GetargsParser`]
+//! and [`GetargsOpt`] are called `getargs_parser` and `getargs_opt`. A
+//! parser is created from `argc` and `argv` with [`getargs_new`], and is
+//! then used exactly like an `Options`, one call at a time:
+//!
+//! ```c
+//! getargs_parser *parser = getargs_new(argc - 1, argv + 1);
+//! const char *output = NULL;
+//! getargs_opt opt;
+//! int result;
+//!
+//! while ((result = getargs_next_opt(parser, &opt)) == 1) {
+//!     if (opt.kind == GETARGS_SHORT && opt.short_opt == 'o' &&
+//!         (result = getargs_value(parser, &output, NULL)) < 0)
+//!         break;
+//! }
+//!
+//! if (result < 0) {
+//!     fprintf(stderr, "error: %s\n", getargs_error(parser));
+//!     int code = getargs_exit_code(parser);
+//!     getargs_free(parser);
+//!     return code;
+//! }
+//!
+//! const char *positional;
+//! while (getargs_next_positional(parser, &positional, NULL) == 1)
+//!     puts(positional);
+//!
+//! getargs_free(parser);
+//! ```
+//!
+//! Arguments are copied into the parser, so `argv` doesn't need to
+//! outlive it. Short options are single bytes, and every string that is
+//! returned is NUL-terminated and also returned with its length. Option
+//! names stay valid until the next call to `getargs_next_opt`, and
+//! values and positional arguments stay valid until the next call that
+//! returns another one, or until the parser is freed.
+//!
+//! Functions return a negative number on failure: `GETARGS_ERROR` if
+//! the arguments could not be parsed, in which case `getargs_error` and
+//! `getargs_exit_code` describe the error, and `GETARGS_MISUSE` if a
+//! function was called out of order, like `getargs_value` without a
+//! previous option.
+//!
+//! [`Argv`], which reads `argc` and `argv`, is re-exported from the
+//! `getargs_argv` crate. Use that crate directly to parse the arguments
+//! of a `#[no_main]` binary or a C entry point with `getargs`, since it
+//! does not need `std` or an allocator.
+//!
+//! The `defmt` feature of `getargs` can't be enabled in a build of this
+//! crate, since `defmt` exports symbols that the dynamic library can't
+//! link. Build the rest of the workspace with `--all-features
+//! --exclude getargs_capi` instead.
+
+use std::ffi::{c_char, c_int, CString};
+use std::vec::IntoIter;
+
+use getargs::{Opt, Options};
+pub use getargs_argv::Argv;
+
+/// The option is a short option, like `-v`.
+pub const GETARGS_SHORT: c_int = 0;
+
+/// The option is a long option, like `--verbose`.
+pub const GETARGS_LONG: c_int = 1;
+
+/// The option is a short option with a `+` prefix, like `+v`.
+pub const GETARGS_SHORT_PLUS: c_int = 2;
+
+/// The option is a negative number, like `-5`.
+pub const GETARGS_NUMERIC: c_int = 3;
+
+/// The arguments could not be parsed.
+pub const GETARGS_ERROR: c_int = -1;
+
+/// A function was called out of order.
+pub const GETARGS_MISUSE: c_int = -2;
+
+/// An option returned by [`getargs_next_opt`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct GetargsOpt {
+    /// The kind of option, which is one of [`GETARGS_SHORT`],
+    /// [`GETARGS_LONG`], [`GETARGS_SHORT_PLUS`] or [`GETARGS_NUMERIC`].
+    pub kind: c_int,
+    /// The name of a short option, or `0` for other options.
+    pub short_opt: c_char,
+    /// The name of a long option, or the digits of a numeric option,
+    /// without the leading dashes. This is null for short options.
+    pub name: *const c_char,
+    /// The length of `name` in bytes.
+    pub name_len: usize,
+}
+
+/// A parser over a copy of some arguments, which is created with
+/// [`getargs_new`] and freed with [`getargs_free`].
+pub struct GetargsParser {
+    opts: Options<Vec<u8>, IntoIter<Vec<u8>>>,
+    /// Whether the last call returned an option whose value can still
+    /// be taken.
+    has_opt: bool,
+    /// Whether positional arguments can be taken, because option
+    /// parsing hasn't started or has finished.
+    positionals: bool,
+    /// The name of the last option, NUL-terminated.
+    name: Vec<u8>,
+    /// The last value or positional argument, NUL-terminated.
+    value: Vec<u8>,
+    error: Option<(CString, u8)>,
+}
+
+impl GetargsParser {
+    fn fail(&mut self, error: getargs::Error<Vec<u8>>) -> c_int {
+        let error = error.into_owned();
+        let message = CString::new(error.to_string()).unwrap_or_default();
+        self.error = Some((message, error.exit_code()));
+        GETARGS_ERROR
+    }
+}
+
+/// Stores `bytes` in `buf` with a NUL terminator, and writes a pointer
+/// to it and its length to `ptr` and `len` if they are not null.
+///
+/// # Safety
+///
+/// `ptr` and `len` must be null or valid for writes.
+unsafe fn write_str(buf: &mut Vec<u8>, bytes: Vec<u8>, ptr: *mut *const c_char, len: *mut usize) {
+    *buf = bytes;
+    let bytes_len = buf.len();
+    buf.push(0);
+
+    if !ptr.is_null() {
+        *ptr = buf.as_ptr().cast();
+    }
+
+    if !len.is_null() {
+        *len = bytes_len;
+    }
+}
+
+/// Creates a parser over the `argc` arguments in `argv`, which are
+/// copied. The program name should not be included, so this is usually
+/// called as `getargs_new(argc - 1, argv + 1)`.
+///
+/// # Safety
+///
+/// `argv` must be valid for [`Argv::new`].
+#[no_mangle]
+pub unsafe extern "C" fn getargs_new(
+    argc: c_int,
+    argv: *const *const c_char,
+) -> *mut GetargsParser {
+    let args: Vec<Vec<u8>> = Argv::new(argc, argv).bytes().map(Vec::from).collect();
+
+    Box::into_raw(Box::new(GetargsParser {
+        opts: Options::new(args.into_iter()),
+        has_opt: false,
+        positionals: true,
+        name: Vec::new(),
+        value: Vec::new(),
+        error: None,
+    }))
+}
+
+/// Frees a parser created with [`getargs_new`]. Nothing happens if
+/// `parser` is null.
+///
+/// # Safety
+///
+/// `parser` must be null or a parser returned by [`getargs_new`] that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn getargs_free(parser: *mut GetargsParser) {
+    if !parser.is_null() {
+        drop(Box::from_raw(parser));
+    }
+}
+
+/// Parses the next option into `opt`, like `Options::next_opt`.
+///
+/// Returns `1` if there is an option, `0` if there are no more options,
+/// or [`GETARGS_ERROR`] if the arguments could not be parsed.
+///
+/// # Safety
+///
+/// `parser` must be a valid parser, and `opt` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn getargs_next_opt(
+    parser: *mut GetargsParser,
+    opt: *mut GetargsOpt,
+) -> c_int {
+    let parser = &mut *parser;
+    parser.has_opt = false;
+    parser.positionals = false;
+
+    let next = match parser.opts.next_opt() {
+        Ok(Some(next)) => next,
+        Ok(None) => {
+            parser.positionals = true;
+            return 0;
+        }
+        Err(error) => return parser.fail(error),
+    };
+
+    parser.has_opt = true;
+
+    let (kind, short_opt, name) = match next {
+        Opt::Short(short) => (GETARGS_SHORT, short, None),
+        Opt::Long(name) => (GETARGS_LONG, 0, Some(name)),
+        Opt::ShortPlus(short) => (GETARGS_SHORT_PLUS, short, None),
+        Opt::Numeric(digits) => (GETARGS_NUMERIC, 0, Some(digits)),
+        opt => return parser.fail(getargs::Error::UnknownOption(opt)),
+    };
+
+    let mut name_ptr = std::ptr::null();
+    let mut name_len = 0;
+
+    if let Some(name) = name {
+        write_str(&mut parser.name, name, &mut name_ptr, &mut name_len);
+    }
+
+    *opt = GetargsOpt {
+        kind,
+        short_opt: short_opt as c_char,
+        name: name_ptr,
+        name_len,
+    };
+
+    1
+}
+
+/// Takes the value of the last option returned by [`getargs_next_opt`],
+/// like `Options::value`, and stores it in `value` and `len`, which can
+/// be null.
+///
+/// Returns `0` on success, [`GETARGS_ERROR`] if there is no value, or
+/// [`GETARGS_MISUSE`] if the last call did not return an option.
+///
+/// # Safety
+///
+/// `parser` must be a valid parser, and `value` and `len` must be null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn getargs_value(
+    parser: *mut GetargsParser,
+    value: *mut *const c_char,
+    len: *mut usize,
+) -> c_int {
+    let parser = &mut *parser;
+
+    if !std::mem::replace(&mut parser.has_opt, false) {
+        return GETARGS_MISUSE;
+    }
+
+    match parser.opts.value() {
+        Ok(arg) => {
+            write_str(&mut parser.value, arg, value, len);
+            0
+        }
+        Err(error) => parser.fail(error),
+    }
+}
+
+/// Takes the value of the last option returned by [`getargs_next_opt`]
+/// only if it was attached to the option, like `Options::value_opt`,
+/// and stores it in `value` and `len`, which can be null.
+///
+/// Returns `1` if there is a value, `0` if there is not, or
+/// [`GETARGS_MISUSE`] if the last call did not return an option.
+///
+/// # Safety
+///
+/// `parser` must be a valid parser, and `value` and `len` must be null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn getargs_value_opt(
+    parser: *mut GetargsParser,
+    value: *mut *const c_char,
+    len: *mut usize,
+) -> c_int {
+    let parser = &mut *parser;
+
+    if !std::mem::replace(&mut parser.has_opt, false) {
+        return GETARGS_MISUSE;
+    }
+
+    match parser.opts.value_opt() {
+        Some(arg) => {
+            write_str(&mut parser.value, arg, value, len);
+            1
+        }
+        None => 0,
+    }
+}
+
+/// Takes the next positional argument, like `Options::next_positional`,
+/// and stores it in `arg` and `len`, which can be null.
+///
+/// Returns `1` if there is a positional argument, `0` if there are no
+/// more arguments, or [`GETARGS_MISUSE`] if [`getargs_next_opt`] has
+/// been called but has not returned `0` yet.
+///
+/// # Safety
+///
+/// `parser` must be a valid parser, and `arg` and `len` must be null or
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn getargs_next_positional(
+    parser: *mut GetargsParser,
+    arg: *mut *const c_char,
+    len: *mut usize,
+) -> c_int {
+    let parser = &mut *parser;
+
+    if !parser.positionals {
+        return GETARGS_MISUSE;
+    }
+
+    match parser.opts.next_positional() {
+        Some(positional) => {
+            write_str(&mut parser.value, positional, arg, len);
+            1
+        }
+        None => 0,
+    }
+}
+
+/// Returns the message of the last error, or null if there has not
+/// been one. The message stays valid until the next error or until the
+/// parser is freed.
+///
+/// # Safety
+///
+/// `parser` must be a valid parser.
+#[no_mangle]
+pub unsafe extern "C" fn getargs_error(parser: *const GetargsParser) -> *const c_char {
+    match &(*parser).error {
+        Some((message, _)) => message.as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// Returns the conventional `sysexits.h` exit code for the last error,
+/// or `0` if there has not been one.
+///
+/// # Safety
+///
+/// `parser` must be a valid parser.
+#[no_mangle]
+pub unsafe extern "C" fn getargs_exit_code(parser: *const GetargsParser) -> c_int {
+    match &(*parser).error {
+        Some((_, exit_code)) => (*exit_code).into(),
+        None => 0,
+    }
+}
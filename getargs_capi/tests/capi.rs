@@ -0,0 +1,133 @@
+use std::ffi::{c_char, CStr};
+use std::ptr;
+
+use getargs_capi::*;
+
+fn args(args: &[&CStr]) -> Vec<*const c_char> {
+    args.iter().map(|arg| arg.as_ptr()).collect()
+}
+
+unsafe fn name(opt: &GetargsOpt) -> &[u8] {
+    assert_eq!(CStr::from_ptr(opt.name).to_bytes().len(), opt.name_len);
+    std::slice::from_raw_parts(opt.name.cast(), opt.name_len)
+}
+
+#[test]
+fn opts_values_and_positionals() {
+    let argv = args(&[
+        c"-vofile",
+        c"--level",
+        c"3",
+        c"--color=auto",
+        c"--",
+        c"-a",
+        c"b",
+    ]);
+
+    unsafe {
+        let parser = getargs_new(argv.len() as _, argv.as_ptr());
+        let mut opt = GetargsOpt {
+            kind: -1,
+            short_opt: 0,
+            name: ptr::null(),
+            name_len: 0,
+        };
+        let mut value = ptr::null();
+        let mut len = 0;
+
+        assert_eq!(getargs_next_opt(parser, &mut opt), 1);
+        assert_eq!((opt.kind, opt.short_opt as u8), (GETARGS_SHORT, b'v'));
+        assert!(opt.name.is_null());
+
+        assert_eq!(getargs_next_opt(parser, &mut opt), 1);
+        assert_eq!((opt.kind, opt.short_opt as u8), (GETARGS_SHORT, b'o'));
+        assert_eq!(getargs_value(parser, &mut value, &mut len), 0);
+        assert_eq!(CStr::from_ptr(value), c"file");
+        assert_eq!(len, 4);
+
+        assert_eq!(getargs_next_opt(parser, &mut opt), 1);
+        assert_eq!(opt.kind, GETARGS_LONG);
+        assert_eq!(name(&opt), b"level");
+        assert_eq!(getargs_value(parser, &mut value, &mut len), 0);
+        assert_eq!(CStr::from_ptr(value), c"3");
+
+        assert_eq!(getargs_next_opt(parser, &mut opt), 1);
+        assert_eq!(opt.kind, GETARGS_LONG);
+        assert_eq!(name(&opt), b"color");
+        assert_eq!(getargs_value_opt(parser, &mut value, ptr::null_mut()), 1);
+        assert_eq!(CStr::from_ptr(value), c"auto");
+
+        assert_eq!(getargs_next_opt(parser, &mut opt), 0);
+        assert_eq!(getargs_next_positional(parser, &mut value, &mut len), 1);
+        assert_eq!(CStr::from_ptr(value), c"-a");
+        assert_eq!(
+            getargs_next_positional(parser, ptr::null_mut(), ptr::null_mut()),
+            1
+        );
+        assert_eq!(getargs_next_positional(parser, &mut value, &mut len), 0);
+
+        assert!(getargs_error(parser).is_null());
+        assert_eq!(getargs_exit_code(parser), 0);
+
+        getargs_free(parser);
+    }
+}
+
+#[test]
+fn errors_and_misuse() {
+    let argv = args(&[c"-a", c"--flag=value", c"-o"]);
+
+    unsafe {
+        let parser = getargs_new(argv.len() as _, argv.as_ptr());
+        let mut opt = GetargsOpt {
+            kind: -1,
+            short_opt: 0,
+            name: ptr::null(),
+            name_len: 0,
+        };
+        let mut value = ptr::null();
+
+        assert_eq!(
+            getargs_value(parser, &mut value, ptr::null_mut()),
+            GETARGS_MISUSE
+        );
+        assert_eq!(getargs_next_opt(parser, &mut opt), 1);
+        assert_eq!(
+            getargs_next_positional(parser, &mut value, ptr::null_mut()),
+            GETARGS_MISUSE
+        );
+
+        assert_eq!(getargs_next_opt(parser, &mut opt), 1);
+        assert_eq!(getargs_next_opt(parser, &mut opt), GETARGS_ERROR);
+        assert_eq!(
+            CStr::from_ptr(getargs_error(parser)),
+            c"option does not require a value: --flag"
+        );
+        assert_eq!(getargs_exit_code(parser), 64);
+        assert_eq!(
+            getargs_value(parser, &mut value, ptr::null_mut()),
+            GETARGS_MISUSE
+        );
+
+        assert_eq!(getargs_next_opt(parser, &mut opt), 1);
+        assert_eq!(opt.short_opt as u8, b'o');
+        assert_eq!(
+            getargs_value(parser, &mut value, ptr::null_mut()),
+            GETARGS_ERROR
+        );
+        assert_eq!(
+            CStr::from_ptr(getargs_error(parser)),
+            c"option requires a value: -o"
+        );
+
+        getargs_free(parser);
+        getargs_free(ptr::null_mut());
+
+        let parser = getargs_new(0, ptr::null());
+        assert_eq!(
+            getargs_next_positional(parser, &mut value, ptr::null_mut()),
+            0
+        );
+        getargs_free(parser);
+    }
+}
@@ -0,0 +1,253 @@
+//! Derive macro for [`getargs`](https://docs.rs/getargs).
+//!
+//! This crate provides `#[derive(FromOptions)]`, which generates the
+//! usual `next_opt()` match loop for a struct. It should not be used
+//! directly; enable the `derive` feature of `getargs` instead, which
+//! re-exports the macro as `getargs::FromOptions`.
+//!
+//! Each field marked with `#[opt(...)]` is an option:
+//!
+//! - `bool` fields are flags, which are set to `true` when the option
+//!   is present.
+//! - `Option<T>` fields take a value, which is converted into `T` with
+//!   [`Into`]. If the option is present more than once, the last value
+//!   wins.
+//!
+//! The names of an option are given with `short = 'f'` and
+//! `long = "file"`. If neither is given, the long name is the name of
+//! the field, with underscores replaced by dashes. Fields without
+//! `#[opt]` are initialized with [`Default::default`].
+//!
+//! Options are parsed until the first positional argument, which is
+//! left in the [`Options`](https://docs.rs/getargs/*/getargs/struct.Options.html)
+//! along with any others. Unknown options are reported as
+//! `Error::UnknownOption`. Only arguments whose short options are
+//! [`char`]s, like `&str`, are supported.
+//!
+//! # Example
+//!
+//! ```
+//! use getargs::{FromOptions, Options};
+//!
+//! #[derive(FromOptions)]
+//! struct Args<'a> {
+//!     #[opt(short = 'v', long = "verbose")]
+//!     verbose: bool,
+//!     #[opt(short = 'o')]
+//!     output: Option<&'a str>,
+//!     #[opt]
+//!     dry_run: bool,
+//! }
+//!
+//! let args = ["-vo", "out.txt", "--dry-run", "in.txt"];
+//! let mut opts = Options::new(args.into_iter());
+//! let parsed = Args::from_options(&mut opts)?;
+//!
+//! assert!(parsed.verbose);
+//! assert_eq!(parsed.output, Some("out.txt"));
+//! assert!(parsed.dry_run);
+//! assert_eq!(opts.next_positional(), Some("in.txt"));
+//! # Ok::<(), getargs::Error<&'static str>>(())
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericArgument, LitChar,
+    LitStr, PathArguments, Type,
+};
+
+/// Derives `getargs::FromOptions` for a struct with named fields. See
+/// the [crate documentation](crate) for details.
+#[proc_macro_derive(FromOptions, attributes(opt))]
+pub fn derive_from_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// What to do with a field when its option is found.
+enum Kind<'a> {
+    /// Set the field to `true`.
+    Flag,
+    /// Set the field to `Some(value.into())`.
+    Value(&'a Type),
+    /// Not an option; set the field to its default.
+    Default,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "FromOptions can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "FromOptions can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut locals = Vec::new();
+    let mut arms = Vec::new();
+    let mut inits = Vec::new();
+    let mut bounds = Vec::new();
+
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let local = format_ident!("__field_{}", ident);
+        let mut short = None;
+        let mut long = None;
+        let mut is_opt = false;
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("opt"))
+        {
+            is_opt = true;
+
+            if matches!(attr.meta, syn::Meta::Path(_)) {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("short") {
+                    short = Some(meta.value()?.parse::<LitChar>()?);
+                    Ok(())
+                } else if meta.path.is_ident("long") {
+                    long = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `short` or `long`"))
+                }
+            })?;
+        }
+
+        let kind = if !is_opt {
+            Kind::Default
+        } else if is_bool(&field.ty) {
+            Kind::Flag
+        } else if let Some(inner) = option_inner(&field.ty) {
+            Kind::Value(inner)
+        } else {
+            return Err(Error::new_spanned(
+                &field.ty,
+                "option fields must be `bool` or `Option<T>`",
+            ));
+        };
+
+        if is_opt && short.is_none() && long.is_none() {
+            let name = ident.to_string().trim_start_matches("r#").replace('_', "-");
+            long = Some(LitStr::new(&name, ident.span()));
+        }
+
+        let short = short.map(|short| quote!(.short(#short)));
+        let long = long.map(|long| quote!(.long(#long)));
+        let spec = quote! {
+            ::getargs::OptSpec::<char, &'static str>::new() #short #long
+        };
+
+        match kind {
+            Kind::Flag => {
+                locals.push(quote!(let mut #local = false;));
+                arms.push(quote! {
+                    if #spec.matches(&__opt) {
+                        #local = true;
+                        continue;
+                    }
+                });
+                inits.push(quote!(#ident: #local));
+            }
+            Kind::Value(inner) => {
+                locals.push(quote!(let mut #local = ::core::option::Option::None;));
+                arms.push(quote! {
+                    if #spec.matches(&__opt) {
+                        #local = ::core::option::Option::Some(
+                            ::core::convert::Into::<#inner>::into(opts.value()?),
+                        );
+                        continue;
+                    }
+                });
+                inits.push(quote!(#ident: #local));
+                bounds.push(quote!(__A: ::core::convert::Into<#inner>));
+            }
+            Kind::Default => {
+                inits.push(quote!(#ident: ::core::default::Default::default()));
+            }
+        }
+    }
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    generics.params.push(parse_quote!(__A));
+    generics.make_where_clause().predicates.push(parse_quote! {
+        __A: ::getargs::Argument<ShortOpt = char> + ::core::cmp::PartialEq<&'static str>
+    });
+
+    for bound in bounds {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#bound));
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::getargs::FromOptions<__A> for #name #ty_generics #where_clause {
+            fn from_options<__I: ::core::iter::Iterator<Item = __A>>(
+                opts: &mut ::getargs::Options<__A, __I>,
+            ) -> ::getargs::Result<__A, Self> {
+                #(#locals)*
+
+                while let ::core::option::Option::Some(__opt) = opts.next_opt()? {
+                    #(#arms)*
+
+                    return ::core::result::Result::Err(::getargs::Error::UnknownOption(__opt));
+                }
+
+                ::core::result::Result::Ok(Self {
+                    #(#inits,)*
+                })
+            }
+        }
+    })
+}
+
+/// Returns `true` if `ty` is `bool`.
+fn is_bool(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("bool"))
+}
+
+/// Returns `T` if `ty` is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,77 @@
+use getargs::{Error, FromOptions, Opt, Options};
+
+#[derive(Debug, Default, PartialEq, FromOptions)]
+struct Args<'a> {
+    #[opt(short = 'v', long = "verbose")]
+    verbose: bool,
+    #[opt(short = 'o')]
+    output: Option<&'a str>,
+    #[opt(long = "level")]
+    level: Option<String>,
+    #[opt]
+    dry_run: bool,
+    extra: Vec<u8>,
+}
+
+#[test]
+fn flags_and_values() {
+    let args = [
+        "-vofirst",
+        "--level",
+        "3",
+        "-o",
+        "second",
+        "--dry-run",
+        "x",
+        "-v",
+    ];
+    let mut opts = Options::new(args.into_iter());
+    let parsed = Args::from_options(&mut opts).unwrap();
+    assert_eq!(
+        parsed,
+        Args {
+            verbose: true,
+            output: Some("second"),
+            level: Some("3".to_string()),
+            dry_run: true,
+            extra: Vec::new(),
+        }
+    );
+    assert_eq!(opts.next_positional(), Some("x"));
+    assert_eq!(opts.next_positional(), Some("-v"));
+}
+
+#[test]
+fn errors() {
+    let mut opts = Options::new(["--dry-run", "--bogus"].into_iter());
+    assert_eq!(
+        Args::from_options(&mut opts),
+        Err(Error::UnknownOption(Opt::Long("bogus")))
+    );
+
+    let mut opts = Options::new(["-o"].into_iter());
+    assert_eq!(
+        Args::from_options(&mut opts),
+        Err(Error::RequiresValue(Opt::Short('o')))
+    );
+
+    let mut opts = Options::new(["--verbose=yes"].into_iter());
+    assert_eq!(
+        Args::from_options(&mut opts),
+        Err(Error::DoesNotRequireValue(Opt::Long("verbose")))
+    );
+}
+
+#[test]
+fn owned_args() {
+    #[derive(FromOptions)]
+    struct Owned {
+        #[opt(short = 'n')]
+        name: Option<String>,
+    }
+
+    let args = ["-n".to_string(), "foo".to_string()];
+    let mut opts = Options::new(args.into_iter());
+    let parsed = Owned::from_options(&mut opts).unwrap();
+    assert_eq!(parsed.name.as_deref(), Some("foo"));
+}
@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+
+use crate::{Injectable, Options};
+
+/// A table of aliases that expand into other arguments, like git
+/// aliases.
+///
+/// Aliases are expanded by calling [`Aliases::expand`] with a
+/// positional argument, usually a subcommand name, which injects the
+/// expansion in front of the remaining arguments of an [`Options`] over
+/// an [`Injectable`]. Expansions can contain options as well as
+/// positional arguments, and are parsed exactly as if they had been
+/// given on the command line.
+///
+/// An argument of the form `$1`, `$2` and so on in an expansion is a
+/// placeholder, which is replaced by the argument that many places
+/// after the alias. Arguments used by placeholders are removed, and any
+/// following arguments are left in place after the expansion.
+///
+/// Expansions are not expanded again, so aliases cannot refer to other
+/// aliases. This type requires the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Aliases, Injectable, Opt, Options};
+/// #
+/// let aliases = Aliases::new()
+///     .alias("co", ["checkout", "--progress"])
+///     .alias("cb", ["checkout", "-b", "$1", "--track"]);
+///
+/// let args = ["cb", "topic", "origin/main"];
+/// let mut opts = Options::new(Injectable::new(args.into_iter()));
+///
+/// let command = opts.next_positional().unwrap();
+/// assert!(aliases.expand(&mut opts, command));
+///
+/// assert_eq!(opts.next_positional(), Some("checkout"));
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+/// assert_eq!(opts.value(), Ok("topic"));
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("track"))));
+/// assert_eq!(opts.next_opt(), Ok(None));
+/// assert_eq!(opts.next_positional(), Some("origin/main"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Aliases<'a> {
+    aliases: Vec<(&'a str, Vec<&'a str>)>,
+}
+
+impl<'a> Aliases<'a> {
+    /// Creates an empty table of aliases.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an alias called `name` that expands into `expansion`. If
+    /// there already is an alias called `name`, it is replaced.
+    pub fn alias<E: IntoIterator<Item = &'a str>>(mut self, name: &'a str, expansion: E) -> Self {
+        let expansion = expansion.into_iter().collect();
+
+        match self.aliases.iter_mut().find(|(other, _)| *other == name) {
+            Some((_, other)) => *other = expansion,
+            None => self.aliases.push((name, expansion)),
+        }
+
+        self
+    }
+
+    /// Returns the expansion of the alias called `name`, if there is
+    /// one.
+    pub fn get(&self, name: &str) -> Option<&[&'a str]> {
+        self.aliases
+            .iter()
+            .find(|(other, _)| *other == name)
+            .map(|(_, expansion)| &expansion[..])
+    }
+
+    /// If `name` is an alias, injects its expansion into `opts` and
+    /// returns `true`. Otherwise, returns `false` and leaves `opts`
+    /// untouched.
+    ///
+    /// This should be called right after `name` is returned as a
+    /// positional argument, so that the expansion takes its place.
+    /// Placeholders whose arguments are missing expand to nothing.
+    pub fn expand<I: Iterator<Item = &'a str>>(
+        &self,
+        opts: &mut Options<&'a str, Injectable<&'a str, I>>,
+        name: &str,
+    ) -> bool {
+        let Some(expansion) = self.get(name) else {
+            return false;
+        };
+
+        let params = expansion
+            .iter()
+            .filter_map(|arg| placeholder(arg))
+            .max()
+            .unwrap_or(0);
+
+        let params: Vec<_> = (0..params).map_while(|_| opts.next_positional()).collect();
+
+        let expanded: Vec<_> = expansion
+            .iter()
+            .filter_map(|arg| match placeholder(arg) {
+                Some(index) => params.get(index - 1).copied(),
+                None => Some(*arg),
+            })
+            .collect();
+
+        opts.inject(expanded);
+        true
+    }
+}
+
+/// Returns `n` if `arg` is a placeholder like `$n`, where `n` is at
+/// least 1.
+fn placeholder(arg: &str) -> Option<usize> {
+    let digits = arg.strip_prefix('$')?;
+
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse().ok().filter(|&index| index > 0)
+}
@@ -6,24 +6,35 @@ use core::fmt::{Debug, Display, Formatter};
 /// This enum can be returned by calls to
 /// [`Options::next_arg`][crate::Options::next_arg] and represents a
 /// short or long command-line option name (but not value) like [`Opt`],
-/// or a positional argument.
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+/// or a positional argument. Like [`Opt`], it implements [`Hash`] and
+/// [`Ord`], and is `#[non_exhaustive]`.
+#[non_exhaustive]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Arg<A: Argument> {
     /// A short option, like `-f`. Does not include the leading `-`.
     Short(A::ShortOpt),
     /// A long option, like `--file`. Does not include the leading `--`.
     Long(A),
+    /// A short option with a leading `+`, like `+x`. Does not include
+    /// the leading `+`. See [`Opt::ShortPlus`].
+    ShortPlus(A::ShortOpt),
+    /// A numeric option, like `-5`. Does not include the leading `-`.
+    /// See [`Opt::Numeric`].
+    Numeric(A),
     /// A positional argument, like `foo.txt`.
     Positional(A),
 }
 
 impl<A: Argument> Arg<A> {
     /// Retrieves an equivalent [`Opt`] represented by this [`Arg`], if
-    /// it is [`Arg::Short`] or [`Arg::Long`], otherwise `None`.
+    /// it is not [`Arg::Positional`], otherwise `None`.
     pub fn opt(self) -> Option<Opt<A>> {
         match self {
             Self::Short(short) => Some(Opt::Short(short)),
             Self::Long(long) => Some(Opt::Long(long)),
+            Self::ShortPlus(short) => Some(Opt::ShortPlus(short)),
+            Self::Numeric(digits) => Some(Opt::Numeric(digits)),
             _ => None,
         }
     }
@@ -43,6 +54,8 @@ impl<A: Argument> From<Opt<A>> for Arg<A> {
         match opt {
             Opt::Short(short) => Self::Short(short),
             Opt::Long(long) => Self::Long(long),
+            Opt::ShortPlus(short) => Self::ShortPlus(short),
+            Opt::Numeric(digits) => Self::Numeric(digits),
         }
     }
 }
@@ -58,7 +71,43 @@ impl<S: Display, A: Argument<ShortOpt = S> + Display> Display for Arg<A> {
         match self {
             Self::Short(short) => write!(f, "-{}", short),
             Self::Long(long) => write!(f, "--{}", long),
+            Self::ShortPlus(short) => write!(f, "+{}", short),
+            Self::Numeric(digits) => write!(f, "-{}", digits),
             Self::Positional(arg) => Display::fmt(arg, f),
         }
     }
 }
+
+/// Formats an [`Arg`] like its [`Display`] implementation. This
+/// requires the `defmt` feature.
+#[cfg(feature = "defmt")]
+impl<S: defmt::Format, A: Argument<ShortOpt = S> + defmt::Format> defmt::Format for Arg<A> {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Self::Short(short) => defmt::write!(f, "-{}", short),
+            Self::Long(long) => defmt::write!(f, "--{}", long),
+            Self::ShortPlus(short) => defmt::write!(f, "+{}", short),
+            Self::Numeric(digits) => defmt::write!(f, "-{}", digits),
+            Self::Positional(arg) => arg.format(f),
+        }
+    }
+}
+
+/// Generates any kind of [`Arg`] with an arbitrary name or value. This
+/// requires the `arbitrary` feature.
+#[cfg(feature = "arbitrary")]
+impl<'a, S, A> arbitrary::Arbitrary<'a> for Arg<A>
+where
+    S: arbitrary::Arbitrary<'a>,
+    A: Argument<ShortOpt = S> + arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => Self::Short(u.arbitrary()?),
+            1 => Self::Long(u.arbitrary()?),
+            2 => Self::ShortPlus(u.arbitrary()?),
+            3 => Self::Numeric(u.arbitrary()?),
+            _ => Self::Positional(u.arbitrary()?),
+        })
+    }
+}
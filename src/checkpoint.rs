@@ -0,0 +1,11 @@
+use crate::{Argument, Options};
+
+/// A saved position of an [`Options`], which can be restored later.
+///
+/// This type can be obtained by calling [`Options::checkpoint`], and
+/// restored with [`Options::restore`]. It is an opaque handle: the
+/// only thing it can be used for is going back to where it was taken.
+#[derive(Copy, Clone, Debug)]
+pub struct Checkpoint<A: Argument, I: Iterator<Item = A>> {
+    pub(crate) opts: Options<A, I>,
+}
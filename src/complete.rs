@@ -0,0 +1,340 @@
+use core::fmt::Display;
+use std::io::{self, Write};
+
+use crate::OptSpec;
+
+/// A shell that [`Completions`] can generate a script for.
+#[non_exhaustive]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum Shell {
+    /// GNU Bash, using `complete -F`.
+    Bash,
+    /// Zsh, using `_arguments`.
+    Zsh,
+    /// The fish shell, using `complete -c`.
+    Fish,
+    /// Elvish, using `edit:completion:arg-completer`.
+    Elvish,
+    /// Nushell, using `extern`.
+    Nushell,
+}
+
+/// Generates shell completion scripts for a table of [`OptSpec`]s.
+///
+/// The generated scripts complete the names of all options when the
+/// current word starts with `-`, and file names otherwise, including
+/// for the values of options that take one. This is enough for small
+/// utilities to offer completions without having to write them by
+/// hand for every shell.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Completions, OptSpec, Shell};
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().short('v').long("verbose").help("print more output"),
+///     OptSpec::new().short('o').takes_value(true).help("output file"),
+/// ];
+///
+/// let mut script = Vec::new();
+/// Completions::new("prog", SPECS).write_to(Shell::Fish, &mut script)?;
+///
+/// assert_eq!(
+///     String::from_utf8(script).unwrap(),
+///     "complete -c prog -s v -l verbose -d 'print more output'
+/// complete -c prog -s o -r -d 'output file'
+/// "
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Completions<'a, S = char, L = &'static str> {
+    program: &'a str,
+    specs: &'a [OptSpec<S, L>],
+}
+
+impl<'a, S: Copy + Display, L: Copy + Display> Completions<'a, S, L> {
+    /// Creates a new [`Completions`] for `program` with the options in
+    /// `specs`.
+    pub const fn new(program: &'a str, specs: &'a [OptSpec<S, L>]) -> Self {
+        Self { program, specs }
+    }
+
+    /// Writes a completion script for `shell` to `writer`.
+    pub fn write_to<W: Write + ?Sized>(&self, shell: Shell, writer: &mut W) -> io::Result<()> {
+        match shell {
+            Shell::Bash => self.write_bash(writer),
+            Shell::Zsh => self.write_zsh(writer),
+            Shell::Fish => self.write_fish(writer),
+            Shell::Elvish => self.write_elvish(writer),
+            Shell::Nushell => self.write_nushell(writer),
+        }
+    }
+
+    fn write_bash<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        let function: String = self
+            .program
+            .chars()
+            .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+            .collect();
+
+        writeln!(writer, "_{}() {{", function)?;
+        writeln!(writer, "    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"")?;
+        writeln!(writer, "    local prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"")?;
+
+        let mut values = self.specs.iter().filter(|spec| spec.takes_value).peekable();
+
+        if values.peek().is_some() {
+            writeln!(writer, "    case \"$prev\" in")?;
+            write!(writer, "        ")?;
+
+            for (i, spec) in values.enumerate() {
+                if i > 0 {
+                    write!(writer, "|")?;
+                }
+
+                write_names(spec, "|", writer)?;
+            }
+
+            writeln!(writer, ")")?;
+            writeln!(
+                writer,
+                "            COMPREPLY=($(compgen -f -- \"$cur\"))\n            return\n            ;;"
+            )?;
+            writeln!(writer, "    esac")?;
+        }
+
+        writeln!(writer, "    if [[ \"$cur\" == -* ]]; then")?;
+        write!(writer, "        COMPREPLY=($(compgen -W \"")?;
+
+        for (i, spec) in self.specs.iter().enumerate() {
+            if i > 0 {
+                write!(writer, " ")?;
+            }
+
+            write_names(spec, " ", writer)?;
+        }
+
+        writeln!(writer, "\" -- \"$cur\"))")?;
+        writeln!(writer, "    else")?;
+        writeln!(writer, "        COMPREPLY=($(compgen -f -- \"$cur\"))")?;
+        writeln!(writer, "    fi")?;
+        writeln!(writer, "}}")?;
+        writeln!(writer, "complete -F _{} {}", function, self.program)
+    }
+
+    fn write_zsh<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "#compdef {}", self.program)?;
+        writeln!(writer)?;
+        writeln!(writer, "_arguments \\")?;
+
+        for spec in self.specs {
+            write!(writer, "    ")?;
+
+            match (spec.short, spec.long) {
+                (Some(short), Some(long)) => write!(
+                    writer,
+                    "'(-{short} --{long})'{{-{short},--{long}}}'",
+                    short = short,
+                    long = long
+                )?,
+                (Some(short), None) => write!(writer, "'-{}", short)?,
+                (None, Some(long)) => write!(writer, "'--{}", long)?,
+                (None, None) => continue,
+            }
+
+            write!(writer, "[")?;
+            write_escaped(spec.help.unwrap_or(""), "'[]:\\", writer)?;
+            write!(writer, "]")?;
+
+            if spec.takes_value {
+                write!(writer, ":")?;
+                write_escaped(spec.value_name.unwrap_or("VALUE"), "':\\", writer)?;
+                write!(writer, ":_files")?;
+            }
+
+            writeln!(writer, "' \\")?;
+        }
+
+        writeln!(writer, "    '*:file:_files'")
+    }
+
+    fn write_fish<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        for spec in self.specs {
+            if spec.short.is_none() && spec.long.is_none() {
+                continue;
+            }
+
+            write!(writer, "complete -c {}", self.program)?;
+
+            if let Some(short) = spec.short {
+                write!(writer, " -s {}", short)?;
+            }
+
+            if let Some(long) = spec.long {
+                write!(writer, " -l {}", long)?;
+            }
+
+            if spec.takes_value {
+                write!(writer, " -r")?;
+            }
+
+            if let Some(help) = spec.help {
+                write!(writer, " -d '")?;
+                write_escaped(help, "'\\", writer)?;
+                write!(writer, "'")?;
+            }
+
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_elvish<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "use str")?;
+        writeln!(writer)?;
+        write!(writer, "set edit:completion:arg-completer['")?;
+        write_escaped_elvish(self.program, writer)?;
+        writeln!(writer, "'] = {{|@words|")?;
+        writeln!(writer, "    var cur = $words[-1]")?;
+
+        let mut values = self
+            .specs
+            .iter()
+            .filter(|spec| spec.takes_value)
+            .flat_map(dashed_names)
+            .peekable();
+
+        if values.peek().is_some() {
+            write!(writer, "    if (and (> (count $words) 2) (has-value [")?;
+
+            for (i, name) in values.enumerate() {
+                if i > 0 {
+                    write!(writer, " ")?;
+                }
+
+                write!(writer, "'")?;
+                write_escaped_elvish(&name, writer)?;
+                write!(writer, "'")?;
+            }
+
+            writeln!(writer, "] $words[-2])) {{")?;
+            writeln!(writer, "        edit:complete-filename $cur")?;
+            write!(writer, "    }} elif ")?;
+        } else {
+            write!(writer, "    if ")?;
+        }
+
+        writeln!(writer, "(str:has-prefix $cur -) {{")?;
+
+        for spec in self.specs {
+            for name in dashed_names(spec) {
+                write!(writer, "        edit:complex-candidate '")?;
+                write_escaped_elvish(&name, writer)?;
+                write!(writer, "'")?;
+
+                if let Some(help) = spec.help {
+                    write!(writer, " &display='")?;
+                    write_escaped_elvish(&name, writer)?;
+                    write!(writer, " (")?;
+                    write_escaped_elvish(help, writer)?;
+                    write!(writer, ")'")?;
+                }
+
+                writeln!(writer)?;
+            }
+        }
+
+        writeln!(writer, "    }} else {{")?;
+        writeln!(writer, "        edit:complete-filename $cur")?;
+        writeln!(writer, "    }}")?;
+        writeln!(writer, "}}")
+    }
+
+    fn write_nushell<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        write!(writer, "export extern \"")?;
+        write_escaped(self.program, "\"\\", writer)?;
+        writeln!(writer, "\" [")?;
+
+        for spec in self.specs {
+            write!(writer, "    ")?;
+
+            match (spec.short, spec.long) {
+                (Some(short), Some(long)) => write!(writer, "--{}(-{})", long, short)?,
+                (Some(short), None) => write!(writer, "-{}", short)?,
+                (None, Some(long)) => write!(writer, "--{}", long)?,
+                (None, None) => continue,
+            }
+
+            if spec.takes_value {
+                write!(writer, ": path")?;
+            }
+
+            if let Some(help) = spec.help {
+                // Comments end at the end of the line.
+                write!(writer, " # {}", help.replace('\n', " "))?;
+            }
+
+            writeln!(writer)?;
+        }
+
+        writeln!(writer, "    ...args: path")?;
+        writeln!(writer, "]")
+    }
+}
+
+/// Writes the names of `spec` with their dashes, separated by
+/// `separator`, like `-o|--output`.
+fn write_names<S: Display, L: Display, W: Write + ?Sized>(
+    spec: &OptSpec<S, L>,
+    separator: &str,
+    writer: &mut W,
+) -> io::Result<()> {
+    match (&spec.short, &spec.long) {
+        (Some(short), Some(long)) => write!(writer, "-{}{}--{}", short, separator, long),
+        (Some(short), None) => write!(writer, "-{}", short),
+        (None, Some(long)) => write!(writer, "--{}", long),
+        (None, None) => Ok(()),
+    }
+}
+
+/// Returns the names of `spec` with their dashes, like `-o` and
+/// `--output`.
+fn dashed_names<S: Display, L: Display>(spec: &OptSpec<S, L>) -> impl Iterator<Item = String> {
+    let short = spec.short.as_ref().map(|short| format!("-{}", short));
+    let long = spec.long.as_ref().map(|long| format!("--{}", long));
+    short.into_iter().chain(long)
+}
+
+/// Writes `s` for use in an Elvish single-quoted string, where single
+/// quotes are doubled.
+fn write_escaped_elvish<W: Write + ?Sized>(s: &str, writer: &mut W) -> io::Result<()> {
+    for (i, part) in s.split('\'').enumerate() {
+        if i > 0 {
+            write!(writer, "''")?;
+        }
+
+        write!(writer, "{}", part)?;
+    }
+
+    Ok(())
+}
+
+/// Writes `s`, escaping each character in `special` with a backslash.
+/// Single quotes are written as `'\''` instead, which ends and restarts
+/// a single-quoted string.
+fn write_escaped<W: Write + ?Sized>(s: &str, special: &str, writer: &mut W) -> io::Result<()> {
+    for ch in s.chars() {
+        if ch == '\'' && special.contains('\'') {
+            write!(writer, "'\\''")?;
+        } else if special.contains(ch) {
+            write!(writer, "\\{}", ch)?;
+        } else {
+            write!(writer, "{}", ch)?;
+        }
+    }
+
+    Ok(())
+}
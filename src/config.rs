@@ -0,0 +1,387 @@
+/// Behavior switches for [`Options`][crate::Options].
+///
+/// A [`ParseConfig`] can be passed to
+/// [`Options::with_config`][crate::Options::with_config] to change
+/// parts of the command-line syntax that `getargs` accepts, without
+/// having to implement [`Argument`][crate::Argument] yourself. The
+/// default configuration, which is used by
+/// [`Options::new`][crate::Options::new], matches the behavior
+/// described in the rest of the documentation.
+///
+/// Each switch is a public field that can be read directly, and set
+/// using the builder method of the same name:
+///
+/// ```
+/// # use getargs::ParseConfig;
+/// #
+/// const CONFIG: ParseConfig = ParseConfig::new().stop_at_positional(true);
+///
+/// assert!(CONFIG.stop_at_positional);
+/// ```
+#[non_exhaustive]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ParseConfig {
+    /// Whether option parsing stops at the first positional argument.
+    ///
+    /// When enabled, [`Options`][crate::Options] switches to
+    /// [raw mode][crate::Options::set_raw] as soon as a positional
+    /// argument is found, so every argument after it is treated as
+    /// positional too, even by
+    /// [`Options::next_arg`][crate::Options::next_arg]. Raw mode can be
+    /// turned off again to continue parsing options, for example after
+    /// a subcommand.
+    ///
+    /// This is the behavior that POSIX requires of utilities, and that
+    /// GNU tools switch to when the `POSIXLY_CORRECT` environment
+    /// variable is set. [`ParseConfig::posixly_correct_from_env`] can be
+    /// used to do the same.
+    ///
+    /// Defaults to `false`.
+    pub stop_at_positional: bool,
+
+    /// Whether options starting with a single dash are long options.
+    ///
+    /// When enabled, an argument like `-name` is parsed as the long
+    /// option [`Opt::Long("name")`][crate::Opt::Long] instead of the
+    /// short options `-n`, `-a`, `-m` and `-e`, like `find` and X11
+    /// programs do. A value can be attached with an equals sign, as in
+    /// `-geometry=80x24`, if the [`Argument`][crate::Argument] supports
+    /// [`Argument::split_on`][crate::Argument::split_on]. Options
+    /// starting with two dashes are still accepted.
+    ///
+    /// Defaults to `false`.
+    pub single_dash_long: bool,
+
+    /// Whether arguments like `/flag` and `/flag:value` are options, as
+    /// in classic Windows command-line tools.
+    ///
+    /// Slash options are parsed as long options, so `/out:file.txt`
+    /// returns [`Opt::Long("out")`][crate::Opt::Long] with the value
+    /// `file.txt`. Only single-word arguments are options: anything
+    /// with another slash before the value, like `/path/to/file`, is
+    /// still a positional argument. Options starting with dashes are
+    /// unaffected. This requires the [`Argument`][crate::Argument] to
+    /// support [`Argument::strip_ascii_prefix`][crate::Argument::strip_ascii_prefix]
+    /// and [`Argument::split_on`][crate::Argument::split_on].
+    ///
+    /// Defaults to `false`.
+    pub slash_opts: bool,
+
+    /// Whether arguments starting with `+` are short options, as used
+    /// by tools like `set` and `xterm` to turn options off.
+    ///
+    /// When enabled, `+x` is parsed as
+    /// [`Opt::ShortPlus('x')`][crate::Opt::ShortPlus], and clusters like
+    /// `+xv` work the same way as `-xv`. A lone `+` is still a
+    /// positional argument. This requires the
+    /// [`Argument`][crate::Argument] to support
+    /// [`Argument::strip_ascii_prefix`][crate::Argument::strip_ascii_prefix].
+    ///
+    /// Defaults to `false`.
+    pub plus_opts: bool,
+
+    /// Whether the first argument is a short option cluster even
+    /// without a leading `-`, like old-style `tar xvf file.tar`.
+    ///
+    /// This only applies to the very first argument, and only if it is
+    /// not already an option, so `tar -xvf file.tar` still works the
+    /// same way. Values can be taken from the following arguments as
+    /// usual. This requires the [`Argument`][crate::Argument] to
+    /// support
+    /// [`Argument::parse_bundled_cluster`][crate::Argument::parse_bundled_cluster].
+    ///
+    /// Defaults to `false`.
+    pub bundled_first: bool,
+
+    /// Whether short options made up only of digits are numeric
+    /// options, like `head -5`.
+    ///
+    /// When enabled, `-42` is parsed as
+    /// [`Opt::Numeric("42")`][crate::Opt::Numeric] instead of the short
+    /// options `-4` and `-2`. Clusters that contain anything other than
+    /// digits, like `-5v`, are still parsed as short options. This
+    /// requires the [`Argument`][crate::Argument] to support
+    /// [`Argument::parse_numeric`][crate::Argument::parse_numeric].
+    ///
+    /// Defaults to `false`.
+    pub numeric_opts: bool,
+
+    /// Whether long options with an empty attached value, like
+    /// `--name=`, are rejected.
+    ///
+    /// When enabled, [`Options::next_opt`][crate::Options::next_opt]
+    /// and [`Options::next_arg`][crate::Options::next_arg] return
+    /// [`Error::EmptyValue`][crate::Error::EmptyValue] for such options
+    /// instead of returning the option and leaving the empty value to
+    /// [`Options::value`][crate::Options::value]. This also applies to
+    /// values attached with
+    /// [`single_dash_long`][Self#structfield.single_dash_long] and
+    /// [`slash_opts`][Self#structfield.slash_opts]. This requires the
+    /// [`Argument`][crate::Argument] to support
+    /// [`Argument::is_empty_value`][crate::Argument::is_empty_value].
+    ///
+    /// Defaults to `false`.
+    pub reject_empty_values: bool,
+
+    /// Whether a lone `-` is rejected instead of being a positional
+    /// argument.
+    ///
+    /// By default, `-` is a positional argument, which conventionally
+    /// means standard input or output. When enabled,
+    /// [`Options::next_opt`][crate::Options::next_opt] and
+    /// [`Options::next_arg`][crate::Options::next_arg] return
+    /// [`Error::LoneDash`][crate::Error::LoneDash] for it instead,
+    /// for grammars where it is not valid. A `-` after `--` is still a
+    /// positional argument. This requires the
+    /// [`Argument`][crate::Argument] to support
+    /// [`Argument::split_on`][crate::Argument::split_on] and
+    /// [`Argument::is_empty_value`][crate::Argument::is_empty_value].
+    ///
+    /// Defaults to `false`.
+    pub reject_lone_dash: bool,
+
+    /// Whether arguments that look like negative numbers, like `-5` or
+    /// `-12.5`, are positional arguments.
+    ///
+    /// When enabled, a `-` followed by ASCII digits, optionally with a
+    /// fractional part after a `.`, is a positional argument instead of
+    /// a short option cluster, which suits calculators and other tools
+    /// that take numbers. This should only be enabled if none of the
+    /// short options are digits. It takes precedence over
+    /// [`numeric_opts`][Self#structfield.numeric_opts]. This requires
+    /// the [`Argument`][crate::Argument] to support
+    /// [`Argument::parse_numeric`][crate::Argument::parse_numeric] and
+    /// [`Argument::split_on`][crate::Argument::split_on].
+    ///
+    /// Defaults to `false`.
+    pub negative_numbers: bool,
+
+    /// Whether a value attached to a short option with an equals sign,
+    /// like `-o=file`, has the equals sign removed.
+    ///
+    /// By default, the value of `-o=file` is `=file`, because
+    /// everything after a short option is its value. When enabled, it
+    /// is `file` instead, like `--output=file`, which is what users of
+    /// tools like `cmake` expect. This applies to
+    /// [`Options::value`][crate::Options::value] and
+    /// [`Options::value_opt`][crate::Options::value_opt], and only to
+    /// the `=` directly after the option. This requires the
+    /// [`Argument`][crate::Argument] to support
+    /// [`Argument::split_on`][crate::Argument::split_on] and
+    /// [`Argument::is_empty_value`][crate::Argument::is_empty_value].
+    ///
+    /// Defaults to `false`.
+    pub short_equals: bool,
+
+    /// The ASCII character that options start with.
+    ///
+    /// Short options start with this character once, and long options
+    /// start with it twice, so setting it to `b'+'` parses `+v` and
+    /// `++verbose` like `-v` and `--verbose` normally are. Arguments
+    /// starting with `-` are then positional arguments. The `--` that
+    /// ends options is unaffected. For any prefix other than `-`, this
+    /// requires the [`Argument`][crate::Argument] to support
+    /// [`Argument::strip_ascii_prefix`][crate::Argument::strip_ascii_prefix]
+    /// and [`Argument::split_on`][crate::Argument::split_on].
+    ///
+    /// This must be ASCII, or [`Options::with_config`] panics.
+    ///
+    /// Defaults to `b'-'`.
+    ///
+    /// [`Options::with_config`]: crate::Options::with_config
+    pub opt_prefix: u8,
+
+    /// The argument that ends option parsing, after which every
+    /// argument is positional.
+    ///
+    /// Some grammars use another terminator, like `;`, or have none at
+    /// all. If `--` does not end options, it is a positional argument.
+    /// See [`Terminator`] for the requirements of each terminator.
+    ///
+    /// Defaults to [`Terminator::DoubleDash`].
+    pub terminator: Terminator,
+
+    /// Long options that can also be given with a single dash, like
+    /// `-std=c++17` or `-mcpu` in compiler frontends.
+    ///
+    /// A short option cluster whose name, up to an optional `=value`,
+    /// is one of these names is parsed as a long option, so
+    /// `-std=c++17` returns [`Opt::Long("std")`][crate::Opt::Long] with
+    /// the value `c++17`. Other clusters are still parsed as short
+    /// options, unlike with
+    /// [`single_dash_long`][Self#structfield.single_dash_long]. Names
+    /// must be ASCII. This requires the [`Argument`][crate::Argument]
+    /// to support
+    /// [`Argument::strip_ascii_prefix`][crate::Argument::strip_ascii_prefix],
+    /// [`Argument::split_on`][crate::Argument::split_on] and
+    /// [`Argument::is_empty_value`][crate::Argument::is_empty_value].
+    ///
+    /// Defaults to no names.
+    pub single_dash_names: &'static [&'static str],
+}
+
+impl ParseConfig {
+    /// Creates the default [`ParseConfig`].
+    pub const fn new() -> Self {
+        Self {
+            stop_at_positional: false,
+            single_dash_long: false,
+            slash_opts: false,
+            plus_opts: false,
+            bundled_first: false,
+            numeric_opts: false,
+            reject_empty_values: false,
+            reject_lone_dash: false,
+            negative_numbers: false,
+            short_equals: false,
+            opt_prefix: b'-',
+            terminator: Terminator::DoubleDash,
+            single_dash_names: &[],
+        }
+    }
+
+    /// Sets [`ParseConfig::stop_at_positional`][Self#structfield.stop_at_positional].
+    pub const fn stop_at_positional(mut self, stop_at_positional: bool) -> Self {
+        self.stop_at_positional = stop_at_positional;
+        self
+    }
+
+    /// Enables [`ParseConfig::stop_at_positional`][Self#structfield.stop_at_positional]
+    /// if the `POSIXLY_CORRECT` environment variable is set, like GNU
+    /// tools do, and leaves this [`ParseConfig`] unchanged otherwise.
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Options, ParseConfig};
+    /// #
+    /// let config = ParseConfig::new().posixly_correct_from_env();
+    /// let opts = Options::with_config(["-a", "file", "-b"].into_iter(), config);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn posixly_correct_from_env(self) -> Self {
+        if std::env::var_os("POSIXLY_CORRECT").is_some() {
+            self.stop_at_positional(true)
+        } else {
+            self
+        }
+    }
+
+    /// Sets [`ParseConfig::single_dash_long`][Self#structfield.single_dash_long].
+    pub const fn single_dash_long(mut self, single_dash_long: bool) -> Self {
+        self.single_dash_long = single_dash_long;
+        self
+    }
+
+    /// Sets [`ParseConfig::slash_opts`][Self#structfield.slash_opts].
+    pub const fn slash_opts(mut self, slash_opts: bool) -> Self {
+        self.slash_opts = slash_opts;
+        self
+    }
+
+    /// Sets [`ParseConfig::plus_opts`][Self#structfield.plus_opts].
+    pub const fn plus_opts(mut self, plus_opts: bool) -> Self {
+        self.plus_opts = plus_opts;
+        self
+    }
+
+    /// Sets [`ParseConfig::bundled_first`][Self#structfield.bundled_first].
+    pub const fn bundled_first(mut self, bundled_first: bool) -> Self {
+        self.bundled_first = bundled_first;
+        self
+    }
+
+    /// Sets [`ParseConfig::numeric_opts`][Self#structfield.numeric_opts].
+    pub const fn numeric_opts(mut self, numeric_opts: bool) -> Self {
+        self.numeric_opts = numeric_opts;
+        self
+    }
+
+    /// Sets [`ParseConfig::reject_empty_values`][Self#structfield.reject_empty_values].
+    pub const fn reject_empty_values(mut self, reject_empty_values: bool) -> Self {
+        self.reject_empty_values = reject_empty_values;
+        self
+    }
+
+    /// Sets [`ParseConfig::reject_lone_dash`][Self#structfield.reject_lone_dash].
+    pub const fn reject_lone_dash(mut self, reject_lone_dash: bool) -> Self {
+        self.reject_lone_dash = reject_lone_dash;
+        self
+    }
+
+    /// Sets [`ParseConfig::negative_numbers`][Self#structfield.negative_numbers].
+    pub const fn negative_numbers(mut self, negative_numbers: bool) -> Self {
+        self.negative_numbers = negative_numbers;
+        self
+    }
+
+    /// Sets [`ParseConfig::short_equals`][Self#structfield.short_equals].
+    pub const fn short_equals(mut self, short_equals: bool) -> Self {
+        self.short_equals = short_equals;
+        self
+    }
+
+    /// Sets [`ParseConfig::opt_prefix`][Self#structfield.opt_prefix].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `opt_prefix` is not ASCII, since a lone byte of a
+    /// multi-byte UTF-8 character would split characters apart.
+    pub const fn opt_prefix(mut self, opt_prefix: u8) -> Self {
+        assert!(opt_prefix.is_ascii(), "opt_prefix must be ASCII");
+        self.opt_prefix = opt_prefix;
+        self
+    }
+
+    /// Sets [`ParseConfig::terminator`][Self#structfield.terminator].
+    pub const fn terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+
+    /// Sets [`ParseConfig::single_dash_names`][Self#structfield.single_dash_names].
+    pub const fn single_dash_names(mut self, single_dash_names: &'static [&'static str]) -> Self {
+        self.single_dash_names = single_dash_names;
+        self
+    }
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The argument that ends option parsing, for
+/// [`ParseConfig::terminator`][ParseConfig#structfield.terminator].
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Arg, Options, ParseConfig, Terminator};
+/// #
+/// let config = ParseConfig::new().terminator(Terminator::Char(b';'));
+/// let args = ["-a", ";", "-b", "--"];
+/// let mut opts = Options::with_config(args.into_iter(), config);
+///
+/// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+/// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("-b"))));
+/// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("--"))));
+/// assert_eq!(opts.next_arg(), Ok(None));
+/// ```
+#[non_exhaustive]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum Terminator {
+    /// `--`, as recognized by
+    /// [`Argument::ends_opts`][crate::Argument::ends_opts].
+    DoubleDash,
+    /// A single ASCII character, like `;`. This requires the
+    /// [`Argument`][crate::Argument] to support
+    /// [`Argument::split_on`][crate::Argument::split_on] and
+    /// [`Argument::is_empty_value`][crate::Argument::is_empty_value].
+    Char(u8),
+    /// Nothing ends option parsing.
+    Disabled,
+}
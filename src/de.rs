@@ -0,0 +1,371 @@
+//! Deserializing structs directly from command-line arguments with
+//! [`serde`].
+//!
+//! [`from_iter`] and [`from_options`] deserialize a struct whose fields
+//! are named after long options, like `--output` for a field named
+//! `output`. Short options are passed to `serde` as one-character
+//! names, so they can be accepted with `#[serde(alias = "o")]`, and
+//! `#[serde(rename_all = "kebab-case")]` turns a field like `dry_run`
+//! into `--dry-run`.
+//!
+//! Options are still parsed lazily: `bool` and `()` fields are flags,
+//! and only accept an explicit value like `--color=false`, while every
+//! other field takes a value using [`Options::value`], so both
+//! `--jobs=4` and `--jobs 4` work. Values are parsed with [`FromStr`],
+//! and unit enum variants are matched by name. [`Option`] fields
+//! become [`None`] if the option is not given, and flags should be
+//! marked with `#[serde(default)]` so that they can be left out.
+//! Options that appear more than once are rejected by `serde` as
+//! duplicate fields.
+//!
+//! This module requires the `serde` and `alloc` features.
+//!
+//! # Example
+//!
+//! ```
+//! # use serde::Deserialize;
+//! #
+//! #[derive(Deserialize, Debug, PartialEq)]
+//! #[serde(rename_all = "kebab-case")]
+//! struct Config<'a> {
+//!     #[serde(default, alias = "v")]
+//!     verbose: bool,
+//!     #[serde(alias = "j")]
+//!     jobs: Option<u32>,
+//!     output: &'a str,
+//!     #[serde(default)]
+//!     dry_run: bool,
+//! }
+//!
+//! let args = ["-v", "--output", "out.txt", "-j4", "--dry-run"];
+//! let config: Config = getargs::from_iter(args)?;
+//!
+//! assert_eq!(
+//!     config,
+//!     Config {
+//!         verbose: true,
+//!         jobs: Some(4),
+//!         output: "out.txt",
+//!         dry_run: true,
+//!     }
+//! );
+//! # Ok::<(), getargs::de::Error>(())
+//! ```
+
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+
+use serde::de::value::{BorrowedStrDeserializer, StrDeserializer};
+use serde::de::{self, DeserializeSeed, MapAccess, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize};
+
+use crate::{sysexits, ErrorOwned, Opt, Options};
+
+/// Deserializes a `T` from the options in `args`.
+///
+/// `args` should not include the program name. Parsing stops at the
+/// first positional argument or `--`, like [`Options::next_opt`], and
+/// [`Error::UnexpectedPositional`] is returned if any positional
+/// arguments are left over. Use [`from_options`] to handle them
+/// yourself.
+pub fn from_iter<'de, T: Deserialize<'de>, I: IntoIterator<Item = &'de str>>(
+    args: I,
+) -> Result<T, Error> {
+    let mut opts = Options::new(args.into_iter());
+    let value = from_options(&mut opts)?;
+
+    match opts.next_positional() {
+        Some(positional) => Err(Error::UnexpectedPositional(positional.into())),
+        None => Ok(value),
+    }
+}
+
+/// Deserializes a `T` from the options in `opts`, leaving any
+/// positional arguments for the caller.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::Options;
+/// # use serde::Deserialize;
+/// #
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(default)]
+///     force: bool,
+/// }
+///
+/// let args = ["--force", "a.txt", "b.txt"];
+/// let mut opts = Options::new(args.into_iter());
+/// let config: Config = getargs::de::from_options(&mut opts)?;
+///
+/// assert!(config.force);
+/// assert_eq!(opts.positionals().collect::<Vec<_>>(), ["a.txt", "b.txt"]);
+/// # Ok::<(), getargs::de::Error>(())
+/// ```
+pub fn from_options<'de, T: Deserialize<'de>, I: Iterator<Item = &'de str>>(
+    opts: &mut Options<&'de str, I>,
+) -> Result<T, Error> {
+    T::deserialize(Deserializer { opts, opt: None })
+}
+
+/// An error that can occur when deserializing from [`Options`].
+#[non_exhaustive]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Error {
+    /// The arguments could not be parsed.
+    Parse(ErrorOwned<String>),
+
+    /// The value of an option could not be parsed into the type of its
+    /// field.
+    InvalidValue {
+        /// The option that was given the value.
+        opt: Opt<String>,
+        /// The value that could not be parsed.
+        value: String,
+        /// Why the value could not be parsed.
+        reason: String,
+    },
+
+    /// A positional argument was left over after deserializing with
+    /// [`from_iter`].
+    UnexpectedPositional(String),
+
+    /// Any other error reported by `serde`, such as a missing field.
+    Custom(String),
+}
+
+impl Error {
+    /// Returns the conventional [`sysexits`] exit code for this error.
+    /// This is the exit code of the parse error for
+    /// [`Error::Parse`], and [`EX_USAGE`][sysexits::EX_USAGE]
+    /// otherwise.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Parse(error) => error.exit_code(),
+            _ => sysexits::EX_USAGE,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(error) => error.fmt(f),
+            Error::InvalidValue { opt, value, reason } => {
+                write!(f, "invalid value '{}' for {}: {}", value, opt, reason)
+            }
+            Error::UnexpectedPositional(positional) => {
+                write!(f, "unexpected positional argument: {}", positional)
+            }
+            Error::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Custom(msg.to_string())
+    }
+}
+
+impl From<crate::Error<&str>> for Error {
+    fn from(error: crate::Error<&str>) -> Self {
+        Error::Parse(error.into_owned())
+    }
+}
+
+/// Deserializes a struct or map from the options in an [`Options`],
+/// one option at a time.
+struct Deserializer<'o, 'de, I: Iterator<Item = &'de str>> {
+    opts: &'o mut Options<&'de str, I>,
+    opt: Option<Opt<&'de str>>,
+}
+
+impl<'de, I: Iterator<Item = &'de str>> de::Deserializer<'de> for Deserializer<'_, 'de, I> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, I: Iterator<Item = &'de str>> MapAccess<'de> for Deserializer<'_, 'de, I> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let opt = match self.opts.next_opt()? {
+            Some(opt) => opt,
+            None => return Ok(None),
+        };
+
+        self.opt = Some(opt);
+
+        match opt {
+            Opt::Long(name) => seed
+                .deserialize(BorrowedStrDeserializer::new(name))
+                .map(Some),
+            Opt::Short(short) => {
+                let mut buf = [0; 4];
+                let name = short.encode_utf8(&mut buf);
+                seed.deserialize(StrDeserializer::new(name)).map(Some)
+            }
+            _ => Err(crate::Error::UnknownOption(opt).into()),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let opt = self
+            .opt
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+
+        seed.deserialize(Value {
+            opts: self.opts,
+            opt,
+        })
+    }
+}
+
+/// Deserializes the value of a single option, taking it from the
+/// [`Options`] only if the type requires one.
+struct Value<'o, 'de, I: Iterator<Item = &'de str>> {
+    opts: &'o mut Options<&'de str, I>,
+    opt: Opt<&'de str>,
+}
+
+impl<'de, I: Iterator<Item = &'de str>> Value<'_, 'de, I> {
+    /// Returns the explicit value of a long option, like `--color=false`.
+    /// Short options are never given one, because the rest of their
+    /// cluster could be more flags.
+    fn explicit(&mut self) -> Option<&'de str> {
+        match self.opt {
+            Opt::Long(_) => self.opts.value_opt(),
+            _ => None,
+        }
+    }
+
+    fn parse<T: FromStr>(mut self) -> Result<T, Error>
+    where
+        T::Err: Display,
+    {
+        let value = self.opts.value()?;
+        self.parse_str(value)
+    }
+
+    fn parse_str<T: FromStr>(&mut self, value: &'de str) -> Result<T, Error>
+    where
+        T::Err: Display,
+    {
+        value.parse().map_err(|reason: T::Err| Error::InvalidValue {
+            opt: self.opt.into_owned(),
+            value: value.into(),
+            reason: reason.to_string(),
+        })
+    }
+}
+
+macro_rules! deserialize_parse {
+    ($($method:ident => $visit:ident,)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.$visit(self.parse()?)
+        }
+    )*};
+}
+
+impl<'de, I: Iterator<Item = &'de str>> de::Deserializer<'de> for Value<'_, 'de, I> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_str(self.opts.value()?)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        match self.explicit() {
+            Some(value) => visitor.visit_bool(self.parse_str(value)?),
+            None => visitor.visit_bool(true),
+        }
+    }
+
+    deserialize_parse! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_bytes(self.opts.value()?.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        if self.explicit().is_some() {
+            return Err(crate::Error::DoesNotRequireValue(self.opt).into());
+        }
+
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(BorrowedStrDeserializer::new(self.opts.value()?))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.explicit();
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        str string seq tuple tuple_struct map struct identifier
+    }
+}
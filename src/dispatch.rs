@@ -0,0 +1,129 @@
+use core::fmt::{self, Debug, Formatter};
+
+use crate::{suggest, Argument, Error, Options, Result};
+
+/// The type of the handlers in a [`Dispatch`] table. Closures that do
+/// not capture anything can be used as handlers, too.
+pub type Handler<A, I, T> = fn(&mut Options<A, I>) -> Result<A, T>;
+
+/// A table of subcommands and their handlers, for git-style programs.
+///
+/// [`Dispatch::dispatch`] reads the name of a subcommand using
+/// [`Options::subcommand`] and calls its handler, which parses the rest
+/// of the arguments. Names that are not in the table are reported as
+/// [`Error::UnknownSubcommand`], and [`Dispatch::suggest`] can be used
+/// to find what was probably meant. Subcommands can also have aliases,
+/// like `rm` for `remove`, which are added with [`Dispatch::aliases`].
+///
+/// The table is a slice, so it can be declared as a constant, and
+/// [`Dispatch`] works without `std` or allocation.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Dispatch, Error, Opt, Options};
+/// #
+/// type Args = std::vec::IntoIter<&'static str>;
+///
+/// fn add(opts: &mut Options<&'static str, Args>) -> getargs::Result<&'static str, i32> {
+///     assert_eq!(opts.next_opt()?, Some(Opt::Short('f')));
+///     Ok(1)
+/// }
+///
+/// const DISPATCH: Dispatch<&str, Args, i32> = Dispatch::new(&[
+///     ("add", add),
+///     ("remove", |_| Ok(2)),
+/// ])
+/// .aliases(&[("rm", "remove")]);
+///
+/// let mut opts = Options::new(vec!["add", "-f"].into_iter());
+/// assert_eq!(DISPATCH.dispatch(&mut opts), Ok(Some(1)));
+///
+/// let mut opts = Options::new(vec!["rm"].into_iter());
+/// assert_eq!(DISPATCH.dispatch(&mut opts), Ok(Some(2)));
+///
+/// let mut opts = Options::new(vec!["rmove"].into_iter());
+/// assert_eq!(DISPATCH.dispatch(&mut opts), Err(Error::UnknownSubcommand("rmove")));
+/// assert_eq!(DISPATCH.suggest("rmove"), Some("remove"));
+/// ```
+pub struct Dispatch<'a, A: Argument, I: Iterator<Item = A>, T> {
+    commands: &'a [(&'static str, Handler<A, I, T>)],
+    aliases: &'a [(&'static str, &'static str)],
+}
+
+impl<'a, A: Argument, I: Iterator<Item = A>, T> Dispatch<'a, A, I, T> {
+    /// Creates a new [`Dispatch`] for the subcommands in `commands`,
+    /// which are pairs of names and handlers.
+    pub const fn new(commands: &'a [(&'static str, Handler<A, I, T>)]) -> Self {
+        Self {
+            commands,
+            aliases: &[],
+        }
+    }
+
+    /// Sets the aliases of subcommands to `aliases`, which are pairs of
+    /// aliases and the names of the subcommands they stand for. An
+    /// alias is dispatched to the handler of its subcommand.
+    pub const fn aliases(mut self, aliases: &'a [(&'static str, &'static str)]) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Reads the name of a subcommand with [`Options::subcommand`] and
+    /// calls its handler, returning what the handler returns.
+    ///
+    /// Returns `Ok(None)` if there are no more arguments, and
+    /// [`Error::UnknownSubcommand`] if the subcommand is not in the
+    /// table and is not an alias of one.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Options::next_positional`].
+    pub fn dispatch(&self, opts: &mut Options<A, I>) -> Result<A, Option<T>>
+    where
+        A: PartialEq<&'static str>,
+    {
+        let Some((name, opts)) = opts.subcommand() else {
+            return Ok(None);
+        };
+
+        let alias = self.aliases.iter().find(|(alias, _)| name == *alias);
+        let handler = self.commands.iter().find(|(command, _)| match alias {
+            Some((_, target)) => target == command,
+            None => name == *command,
+        });
+
+        match handler {
+            Some((_, handler)) => handler(opts).map(Some),
+            None => Err(Error::UnknownSubcommand(name)),
+        }
+    }
+
+    /// Returns the names of all subcommands, in order, not including
+    /// aliases.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + 'a {
+        self.commands.iter().map(|&(name, _)| name)
+    }
+
+    /// Returns the name of the subcommand or alias that is closest to
+    /// `name`, if any is close enough to be a plausible typo. See
+    /// [`suggest::closest`].
+    pub fn suggest(&self, name: &str) -> Option<&'static str> {
+        let aliases = self.aliases.iter().map(|&(alias, _)| alias);
+        suggest::closest_in(name, self.names().chain(aliases))
+    }
+}
+
+impl<A: Argument, I: Iterator<Item = A>, T> Clone for Dispatch<'_, A, I, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: Argument, I: Iterator<Item = A>, T> Copy for Dispatch<'_, A, I, T> {}
+
+impl<A: Argument, I: Iterator<Item = A>, T> Debug for Dispatch<'_, A, I, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
@@ -1,6 +1,6 @@
 use core::fmt::{Display, Formatter};
 
-use crate::{Argument, Opt};
+use crate::{sysexits, Argument, Opt};
 
 /// An argument parsing error.
 ///
@@ -20,8 +20,45 @@ use crate::{Argument, Opt};
 ///   [`Options::value`][crate::Options::value] and
 ///   [`Options::value_opt`][crate::Options::value_opt] have both not
 ///   been.
+///
+/// - When a long option is given an empty value like `--name=` and
+///   [`ParseConfig::reject_empty_values`][crate::ParseConfig#structfield.reject_empty_values]
+///   is enabled.
+///
+/// - When a lone `-` is given and
+///   [`ParseConfig::reject_lone_dash`][crate::ParseConfig#structfield.reject_lone_dash]
+///   is enabled.
+///
+/// - When an option requires a group of values, but the argument list
+///   ends partway through the group.
+///
+/// - When fewer positional arguments are present than a helper like
+///   [`Options::split_last_positional`][crate::Options::split_last_positional]
+///   requires.
+///
+/// - When more positional arguments or values are present than fit in
+///   a fixed-capacity collection.
+///
+/// - When an option is not declared in the table of
+///   [`OptSpec`][crate::OptSpec]s passed to
+///   [`Options::next_spec`][crate::Options::next_spec] or
+///   [`Options::validate`][crate::Options::validate].
+///
+/// - When a subcommand is not in the table of a
+///   [`Dispatch`][crate::Dispatch].
+///
+/// - When an argument is not valid UTF-8 in a
+///   [`Utf8Options`][crate::Utf8Options].
 #[non_exhaustive]
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "A: serde::Serialize, A::ShortOpt: serde::Serialize",
+        deserialize = "A: serde::Deserialize<'de>, A::ShortOpt: serde::Deserialize<'de>"
+    ))
+)]
 pub enum Error<A: Argument> {
     /// The option requires a value, but one was not supplied.
     ///
@@ -37,20 +74,328 @@ pub enum Error<A: Argument> {
     /// [`Options::next_arg`][crate::Options::next_arg] is called
     /// without the value being consumed.
     DoesNotRequireValue(Opt<A>),
+
+    /// The option was given an empty value, like `--name=`.
+    ///
+    /// This error is returned by
+    /// [`Options::next_opt`][crate::Options::next_opt] and
+    /// [`Options::next_arg`][crate::Options::next_arg] when
+    /// [`ParseConfig::reject_empty_values`][crate::ParseConfig#structfield.reject_empty_values]
+    /// is enabled.
+    EmptyValue(Opt<A>),
+
+    /// A lone `-` was given where it is not accepted.
+    ///
+    /// This error is returned by
+    /// [`Options::next_opt`][crate::Options::next_opt] and
+    /// [`Options::next_arg`][crate::Options::next_arg] when
+    /// [`ParseConfig::reject_lone_dash`][crate::ParseConfig#structfield.reject_lone_dash]
+    /// is enabled.
+    LoneDash,
+
+    /// The option requires a group of values, but the argument list
+    /// ended before all of them were supplied.
+    ///
+    /// This error is returned when a call to
+    /// [`Options::value_group`][crate::Options::value_group] runs out
+    /// of arguments in the middle of a group.
+    RequiresValues {
+        /// The option whose values were being retrieved.
+        opt: Opt<A>,
+        /// The number of values the option requires.
+        expected: usize,
+        /// The number of values that were supplied.
+        found: usize,
+    },
+
+    /// Fewer positional arguments were supplied than were required.
+    ///
+    /// This error is returned by
+    /// [`Options::split_last_positional`][crate::Options::split_last_positional]
+    /// when there are not enough positional arguments to form both a
+    /// list of sources and a destination.
+    TooFewPositionals {
+        /// The minimum number of positional arguments required.
+        expected: usize,
+        /// The number of positional arguments that were supplied.
+        found: usize,
+    },
+
+    /// More positional arguments were supplied than could be stored.
+    ///
+    /// This error is returned by `Options::positionals_into`, which
+    /// requires the `heapless` feature, when the collection it fills
+    /// is full.
+    TooManyPositionals {
+        /// The maximum number of positional arguments that can be
+        /// stored.
+        capacity: usize,
+    },
+
+    /// The option was given more values than could be stored.
+    ///
+    /// This error is returned by `Options::value_into`, which requires
+    /// the `heapless` feature, when the collection it fills is full.
+    TooManyValues {
+        /// The option whose value could not be stored.
+        opt: Opt<A>,
+        /// The maximum number of values that can be stored.
+        capacity: usize,
+    },
+
+    /// The option is not known.
+    ///
+    /// This error is returned by
+    /// [`Options::next_spec`][crate::Options::next_spec] and
+    /// [`Validated`][crate::Validated] when an option does not match
+    /// any of the given [`OptSpec`][crate::OptSpec]s.
+    UnknownOption(Opt<A>),
+
+    /// The subcommand is not known.
+    ///
+    /// This error is returned by
+    /// [`Dispatch::dispatch`][crate::Dispatch::dispatch] when a
+    /// subcommand is not in its table.
+    UnknownSubcommand(A),
+
+    /// An argument is not valid UTF-8.
+    ///
+    /// This error is returned by [`Utf8Options`][crate::Utf8Options]
+    /// when it converts an argument that is not valid UTF-8.
+    InvalidUtf8 {
+        /// The position of the argument, counting from 1 like
+        /// [`Options::arg_index`][crate::Options::arg_index].
+        arg_index: usize,
+        /// The length of the valid UTF-8 at the start of the argument,
+        /// in bytes. See [`Utf8Error::valid_up_to`][core::str::Utf8Error::valid_up_to].
+        valid_up_to: usize,
+    },
+
+    /// A help option was given.
+    ///
+    /// This error is returned by
+    /// [`Options::next_opt`][crate::Options::next_opt] and
+    /// [`Options::next_arg`][crate::Options::next_arg] for options
+    /// that match the predicate given to
+    /// [`Options::set_help_opt`][crate::Options::set_help_opt]. It is
+    /// not a usage error, so its exit code is
+    /// [`EX_OK`][sysexits::EX_OK].
+    HelpRequested(Opt<A>),
 }
 
-impl<'arg, S: Display, A: Argument<ShortOpt = S> + Display + 'arg> Display for Error<A> {
+impl<A: Argument> Error<A> {
+    /// Returns the conventional [`sysexits`] exit code for this error.
+    ///
+    /// Errors produced by parsing are usage errors, and map to
+    /// [`EX_USAGE`][sysexits::EX_USAGE], except for
+    /// [`Error::InvalidUtf8`], which maps to
+    /// [`EX_DATAERR`][sysexits::EX_DATAERR], and
+    /// [`Error::HelpRequested`], which maps to [`EX_OK`][sysexits::EX_OK].
+    ///
+    /// With the `std` feature, [`Error`] can also be converted directly
+    /// into a [`std::process::ExitCode`]:
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// # #[cfg(feature = "std")] {
+    /// use std::process::ExitCode;
+    ///
+    /// fn main() -> ExitCode {
+    ///     let args = ["--file", "foo.txt"];
+    ///     let mut opts = Options::new(args.into_iter());
+    ///
+    ///     while let Some(opt) = opts.next_opt().unwrap() {
+    ///         if let Opt::Long("file") = opt {
+    ///             if let Err(error) = opts.value() {
+    ///                 eprintln!("error: {}", error);
+    ///                 return error.into();
+    ///             }
+    ///         }
+    ///     }
+    ///
+    ///     ExitCode::SUCCESS
+    /// }
+    /// # }
+    /// ```
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::RequiresValue(_)
+            | Error::DoesNotRequireValue(_)
+            | Error::EmptyValue(_)
+            | Error::LoneDash
+            | Error::RequiresValues { .. }
+            | Error::TooFewPositionals { .. }
+            | Error::TooManyPositionals { .. }
+            | Error::TooManyValues { .. }
+            | Error::UnknownOption(_)
+            | Error::UnknownSubcommand(_) => sysexits::EX_USAGE,
+            Error::InvalidUtf8 { .. } => sysexits::EX_DATAERR,
+            Error::HelpRequested(_) => sysexits::EX_OK,
+        }
+    }
+}
+
+impl<S: Display, A: Argument<ShortOpt = S> + Display> Display for Error<A> {
     fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         match self {
             Error::RequiresValue(opt) => write!(f, "option requires a value: {}", opt),
             Error::DoesNotRequireValue(opt) => {
                 write!(f, "option does not require a value: {}", opt)
             }
+            Error::EmptyValue(opt) => write!(f, "option requires a non-empty value: {}", opt),
+            Error::LoneDash => write!(f, "unexpected argument: -"),
+            Error::RequiresValues {
+                opt,
+                expected,
+                found,
+            } => write!(
+                f,
+                "option requires {} values, but {} were supplied: {}",
+                expected, found, opt
+            ),
+            Error::TooFewPositionals { expected, found } => write!(
+                f,
+                "expected at least {} positional arguments, found {}",
+                expected, found
+            ),
+            Error::TooManyPositionals { capacity } => {
+                write!(f, "expected at most {} positional arguments", capacity)
+            }
+            Error::TooManyValues { opt, capacity } => {
+                write!(f, "option accepts at most {} values: {}", capacity, opt)
+            }
+            Error::UnknownOption(opt) => write!(f, "unknown option: {}", opt),
+            Error::UnknownSubcommand(name) => write!(f, "unknown subcommand: {}", name),
+            Error::InvalidUtf8 { arg_index, .. } => {
+                write!(f, "argument {} is not valid UTF-8", arg_index)
+            }
+            Error::HelpRequested(opt) => write!(f, "help requested: {}", opt),
         }
     }
 }
 
+/// Formats an [`Error`] like its [`Display`] implementation. This
+/// requires the `defmt` feature.
+#[cfg(feature = "defmt")]
+impl<S: defmt::Format, A: Argument<ShortOpt = S> + defmt::Format> defmt::Format for Error<A> {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Error::RequiresValue(opt) => defmt::write!(f, "option requires a value: {}", opt),
+            Error::DoesNotRequireValue(opt) => {
+                defmt::write!(f, "option does not require a value: {}", opt)
+            }
+            Error::EmptyValue(opt) => {
+                defmt::write!(f, "option requires a non-empty value: {}", opt)
+            }
+            Error::LoneDash => defmt::write!(f, "unexpected argument: -"),
+            Error::RequiresValues {
+                opt,
+                expected,
+                found,
+            } => defmt::write!(
+                f,
+                "option requires {} values, but {} were supplied: {}",
+                expected,
+                found,
+                opt
+            ),
+            Error::TooFewPositionals { expected, found } => defmt::write!(
+                f,
+                "expected at least {} positional arguments, found {}",
+                expected,
+                found
+            ),
+            Error::TooManyPositionals { capacity } => {
+                defmt::write!(f, "expected at most {} positional arguments", capacity)
+            }
+            Error::TooManyValues { opt, capacity } => {
+                defmt::write!(f, "option accepts at most {} values: {}", capacity, opt)
+            }
+            Error::UnknownOption(opt) => defmt::write!(f, "unknown option: {}", opt),
+            Error::UnknownSubcommand(name) => defmt::write!(f, "unknown subcommand: {}", name),
+            Error::InvalidUtf8 { arg_index, .. } => {
+                defmt::write!(f, "argument {} is not valid UTF-8", arg_index)
+            }
+            Error::HelpRequested(opt) => defmt::write!(f, "help requested: {}", opt),
+        }
+    }
+}
+
+impl<S: Display, A: Argument<ShortOpt = S> + Display> core::error::Error for Error<A> {}
+
 #[cfg(feature = "std")]
-impl<'arg, S: Display, A: Argument<ShortOpt = S> + Display + 'arg> std::error::Error for Error<A> {}
+impl<A: Argument> From<Error<A>> for std::process::ExitCode {
+    fn from(error: Error<A>) -> Self {
+        Self::from(error.exit_code())
+    }
+}
 
 pub type Result<A, T> = core::result::Result<T, Error<A>>;
+
+/// An error returned by
+/// [`Options::value_parsed`][crate::Options::value_parsed] and
+/// [`Options::value_parsed_with`][crate::Options::value_parsed_with],
+/// as well as the typed getters of `ParsedArgs`.
+///
+/// This is separate from [`Error`] because it carries the error `E`
+/// returned by the parser, which can be any type.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ValueError<A: Argument, E> {
+    /// The option requires a value, but one was not supplied. See
+    /// [`Error::RequiresValue`].
+    RequiresValue(Opt<A>),
+
+    /// The value supplied to the option could not be parsed.
+    InvalidValue {
+        /// The option that was given the value.
+        opt: Opt<A>,
+        /// The value that could not be parsed.
+        value: A,
+        /// The error returned by the parser.
+        error: E,
+    },
+}
+
+impl<A: Argument, E> ValueError<A, E> {
+    /// Returns the conventional [`sysexits`] exit code for this error,
+    /// which is [`EX_USAGE`][sysexits::EX_USAGE].
+    pub fn exit_code(&self) -> u8 {
+        sysexits::EX_USAGE
+    }
+}
+
+impl<A: Argument, E> TryFrom<Error<A>> for ValueError<A, E> {
+    type Error = Error<A>;
+
+    /// Converts an [`Error::RequiresValue`] into a
+    /// [`ValueError::RequiresValue`]. Any other [`Error`] is returned
+    /// unchanged.
+    fn try_from(error: Error<A>) -> core::result::Result<Self, Error<A>> {
+        match error {
+            Error::RequiresValue(opt) => Ok(ValueError::RequiresValue(opt)),
+            error => Err(error),
+        }
+    }
+}
+
+impl<S: Display, A: Argument<ShortOpt = S> + Display, E: Display> Display for ValueError<A, E> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            ValueError::RequiresValue(opt) => write!(f, "option requires a value: {}", opt),
+            ValueError::InvalidValue { opt, value, error } => {
+                write!(f, "invalid value '{}' for {}: {}", value, opt, error)
+            }
+        }
+    }
+}
+
+impl<S: Display, A: Argument<ShortOpt = S> + Display, E: core::error::Error + 'static>
+    core::error::Error for ValueError<A, E>
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ValueError::RequiresValue(_) => None,
+            ValueError::InvalidValue { error, .. } => Some(error),
+        }
+    }
+}
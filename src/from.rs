@@ -0,0 +1,51 @@
+use crate::{Argument, Options, Result};
+
+/// A type that can be parsed from the options of an [`Options`].
+///
+/// With the `derive` feature, this trait can be implemented with
+/// `#[derive(FromOptions)]`, which generates the usual [`next_opt`]
+/// loop from `#[opt(short = 'f', long = "file")]` attributes on the
+/// fields of a struct. See the documentation of the `getargs_derive`
+/// crate for details.
+///
+/// Implementations should parse options until [`next_opt`] returns
+/// `Ok(None)`, leaving any positional arguments in `opts`.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Error, FromOptions, Opt, Options, Result};
+/// #
+/// struct Args {
+///     verbose: bool,
+/// }
+///
+/// impl<'a> FromOptions<&'a str> for Args {
+///     fn from_options<I: Iterator<Item = &'a str>>(
+///         opts: &mut Options<&'a str, I>,
+///     ) -> Result<&'a str, Self> {
+///         let mut verbose = false;
+///
+///         while let Some(opt) = opts.next_opt()? {
+///             match opt {
+///                 Opt::Short('v') | Opt::Long("verbose") => verbose = true,
+///                 opt => return Err(Error::UnknownOption(opt)),
+///             }
+///         }
+///
+///         Ok(Args { verbose })
+///     }
+/// }
+///
+/// let mut opts = Options::new(["-v", "foo"].into_iter());
+///
+/// assert!(Args::from_options(&mut opts)?.verbose);
+/// assert_eq!(opts.next_positional(), Some("foo"));
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+///
+/// [`next_opt`]: Options::next_opt
+pub trait FromOptions<A: Argument>: Sized {
+    /// Parses the options of `opts` into a new value of this type.
+    fn from_options<I: Iterator<Item = A>>(opts: &mut Options<A, I>) -> Result<A, Self>;
+}
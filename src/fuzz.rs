@@ -0,0 +1,161 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// The most arguments a [`FuzzArgs`] will generate.
+const MAX_ARGS: u32 = 32;
+
+/// A command line for fuzzing, made of arguments that are shaped like
+/// the ones an argument parser has to deal with.
+///
+/// Arbitrary strings rarely look like options, so each argument is
+/// generated in one of these shapes instead:
+///
+/// * A cluster of short options, like `-abc`, sometimes followed by an
+///   attached value, like `-ofile`.
+/// * A long option, like `--name`, sometimes with an attached value,
+///   like `--name=value`, which can also be empty.
+/// * A short option with a `+` prefix, like `+x`.
+/// * A negative number, like `-12`.
+/// * `--` or a lone `-`.
+/// * An empty string.
+/// * Any other string, which can be a positional argument or the value
+///   of the option before it.
+///
+/// Option names are arbitrary when using the [`Arbitrary`]
+/// implementation. [`FuzzArgs::with_names`] picks them from the names
+/// your application accepts instead, so that fuzzing reaches further
+/// into its option handling.
+///
+/// This type requires the `arbitrary` feature.
+///
+/// # Example
+///
+/// ```
+/// # use arbitrary::Unstructured;
+/// # use getargs::{FuzzArgs, Opt, Options};
+/// #
+/// // This would come from the fuzzer.
+/// let data = [7, 1, 42, 0, 3, 99, 5, 18, 2, 64, 1, 0, 255, 9, 31, 4];
+/// let mut u = Unstructured::new(&data);
+///
+/// let args = FuzzArgs::with_names(&mut u, &['v', 'o'], &["verbose", "output"])?;
+/// let mut opts = Options::new(args.iter());
+///
+/// while let Ok(Some(opt)) = opts.next_opt() {
+///     if opt == Opt::Short('o') || opt == Opt::Long("output") {
+///         let _ = opts.value();
+///     }
+/// }
+/// # Ok::<(), arbitrary::Error>(())
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct FuzzArgs {
+    args: Vec<String>,
+}
+
+impl FuzzArgs {
+    /// Generates a [`FuzzArgs`] from `u`, picking short option names
+    /// from `shorts` and long option names from `longs`. If either is
+    /// empty, those names are arbitrary instead.
+    pub fn with_names(u: &mut Unstructured, shorts: &[char], longs: &[&str]) -> Result<Self> {
+        let names = Names { shorts, longs };
+        let mut args = Vec::new();
+
+        u.arbitrary_loop(None, Some(MAX_ARGS), |u| {
+            args.push(names.arg(u)?);
+            Ok(ControlFlow::Continue(()))
+        })?;
+
+        Ok(Self { args })
+    }
+
+    /// Returns the generated arguments.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Returns an iterator over the generated arguments, which can be
+    /// passed to [`Options::new`][crate::Options::new].
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.args.iter().map(String::as_str)
+    }
+
+    /// Returns the generated arguments, consuming this [`FuzzArgs`].
+    pub fn into_inner(self) -> Vec<String> {
+        self.args
+    }
+}
+
+impl<'a> Arbitrary<'a> for FuzzArgs {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Self::with_names(u, &[], &[])
+    }
+}
+
+/// The option names to generate arguments with.
+struct Names<'n> {
+    shorts: &'n [char],
+    longs: &'n [&'n str],
+}
+
+impl Names<'_> {
+    fn short(&self, u: &mut Unstructured) -> Result<char> {
+        match self.shorts {
+            [] => u.arbitrary(),
+            shorts => u.choose(shorts).copied(),
+        }
+    }
+
+    fn long(&self, u: &mut Unstructured) -> Result<String> {
+        match self.longs {
+            [] => u.arbitrary(),
+            longs => u.choose(longs).map(|&long| long.into()),
+        }
+    }
+
+    /// Generates a single argument in one of the shapes listed on
+    /// [`FuzzArgs`].
+    fn arg(&self, u: &mut Unstructured) -> Result<String> {
+        let mut arg = String::new();
+
+        match u.int_in_range(0..=9)? {
+            0 | 1 => {
+                arg.push('-');
+
+                for _ in 0..u.int_in_range(1..=4)? {
+                    arg.push(self.short(u)?);
+                }
+
+                if u.ratio(1, 4)? {
+                    arg.push_str(u.arbitrary()?);
+                }
+            }
+            2 | 3 => {
+                arg.push_str("--");
+                arg.push_str(&self.long(u)?);
+
+                if u.ratio(1, 3)? {
+                    arg.push('=');
+                    arg.push_str(u.arbitrary()?);
+                }
+            }
+            4 => {
+                arg.push('+');
+                arg.push(self.short(u)?);
+            }
+            5 => {
+                arg.push('-');
+                arg.push_str(&u.int_in_range(0..=999u16)?.to_string());
+            }
+            6 => arg.push_str("--"),
+            7 => arg.push('-'),
+            8 => {}
+            _ => arg.push_str(u.arbitrary()?),
+        }
+
+        Ok(arg)
+    }
+}
@@ -0,0 +1,459 @@
+use crate::{Arg, Argument, Error, Opt, Options};
+
+/// An [`Options`] that recognizes options using a classic `getopt(3)`
+/// optstring, to make porting C programs mechanical.
+///
+/// This type can be obtained by calling [`Options::getopt`]. It is an
+/// iterator of `(char, Option<A>)` pairs, which are the value `getopt`
+/// would return and its `optarg`, and it ends where `getopt` would
+/// return `-1`. Like `getopt`, each character in the optstring is a
+/// short option, followed by `:` if it requires a value, or `::` if it
+/// takes an optional value, which must be attached like `-cvalue`.
+///
+/// Errors are returned in-band, the same way as `getopt`:
+///
+/// * An unknown option returns `'?'`.
+/// * An option whose value is missing returns `':'` if the optstring
+///   starts with `:`, and `'?'` otherwise.
+///
+/// [`Getopt::optopt`] then returns the option character, like
+/// `optopt`, and [`Getopt::error`] returns the [`Error`], which can be
+/// used to print a message. `getargs` never prints anything itself, so
+/// the `:` prefix only changes the return value.
+///
+/// Option parsing stops at the first positional argument, like POSIX
+/// `getopt` and glibc's `+` prefix, which is also accepted. If the
+/// optstring starts with `-` instead, positional arguments are returned
+/// in order as `'\u{1}'` with the argument as the value, like glibc.
+/// Positional arguments that were not returned can be retrieved with
+/// [`Getopt::next_positional`], which takes the place of `optind`.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::Options;
+/// #
+/// let args = ["-a", "-bfoo", "-c", "-x", "-b"];
+/// let mut opts = Options::new(args.into_iter()).getopt(":ab:c::");
+///
+/// assert_eq!(opts.next(), Some(('a', None)));
+/// assert_eq!(opts.next(), Some(('b', Some("foo"))));
+/// assert_eq!(opts.next(), Some(('c', None)));
+/// assert_eq!(opts.next(), Some(('?', None)));
+/// assert_eq!(opts.optopt(), Some('x'));
+/// assert_eq!(opts.next(), Some((':', None)));
+/// assert_eq!(opts.optopt(), Some('b'));
+/// assert_eq!(opts.next(), None);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Getopt<'s, A: Argument, I: Iterator<Item = A>> {
+    opts: Options<A, I>,
+    optstring: &'s str,
+    silent: bool,
+    in_order: bool,
+    optopt: Option<char>,
+    error: Option<Error<A>>,
+}
+
+impl<'s, A: Argument, I: Iterator<Item = A>> Getopt<'s, A, I>
+where
+    A::ShortOpt: Into<char>,
+{
+    pub(crate) fn new(opts: Options<A, I>, optstring: &'s str) -> Self {
+        let (in_order, optstring) = match optstring.as_bytes().first() {
+            Some(b'-') => (true, &optstring[1..]),
+            Some(b'+') => (false, &optstring[1..]),
+            _ => (false, optstring),
+        };
+
+        let (silent, optstring) = match optstring.strip_prefix(':') {
+            Some(optstring) => (true, optstring),
+            None => (false, optstring),
+        };
+
+        Self {
+            opts,
+            optstring,
+            silent,
+            in_order,
+            optopt: None,
+            error: None,
+        }
+    }
+
+    /// Returns how many colons follow `short` in the optstring, or
+    /// [`None`] if it is not an option.
+    fn colons(&self, short: char) -> Option<usize> {
+        if short == ':' {
+            return None;
+        }
+
+        let start = self.optstring.find(short)? + short.len_utf8();
+        let rest = &self.optstring.as_bytes()[start..];
+        Some(
+            rest.iter()
+                .take(2)
+                .take_while(|&&byte| byte == b':')
+                .count(),
+        )
+    }
+
+    fn fail(&mut self, code: char, error: Error<A>) -> (char, Option<A>) {
+        self.error = Some(error);
+        (code, None)
+    }
+
+    /// Returns the next option, or the item to return instead if it was
+    /// a positional argument or an error.
+    #[allow(clippy::type_complexity)]
+    fn fetch(&mut self) -> Option<core::result::Result<Opt<A>, (char, Option<A>)>> {
+        self.optopt = None;
+        self.error = None;
+
+        let next = if self.in_order {
+            self.opts.next_arg()
+        } else {
+            self.opts.next_opt().map(|opt| opt.map(Arg::from))
+        };
+
+        match next {
+            Ok(Some(Arg::Positional(positional))) => Some(Err(('\u{1}', Some(positional)))),
+            Ok(Some(arg)) => Some(Ok(arg.opt().unwrap())),
+            Ok(None) => None,
+            Err(error) => Some(Err(self.fail('?', error))),
+        }
+    }
+
+    /// Looks `opt` up in the optstring and takes its value.
+    fn short(&mut self, opt: Opt<A>) -> (char, Option<A>) {
+        let short = match opt {
+            Opt::Short(short) => short.into(),
+            _ => return self.fail('?', Error::UnknownOption(opt)),
+        };
+
+        let colons = match self.colons(short) {
+            Some(colons) => colons,
+            None => {
+                self.optopt = Some(short);
+                return self.fail('?', Error::UnknownOption(opt));
+            }
+        };
+
+        match colons {
+            0 => (short, None),
+            1 => self.required(short),
+            _ => (short, self.opts.value_opt()),
+        }
+    }
+
+    /// Takes the required value of an option that returns `code`.
+    fn required(&mut self, code: char) -> (char, Option<A>) {
+        match self.opts.value() {
+            Ok(value) => (code, Some(value)),
+            Err(error) => self.missing(code, error),
+        }
+    }
+
+    /// Reports that the option that returns `code` is missing its
+    /// value.
+    fn missing(&mut self, code: char, error: Error<A>) -> (char, Option<A>) {
+        self.optopt = Some(code);
+        let code = if self.silent { ':' } else { '?' };
+        self.fail(code, error)
+    }
+
+    /// Returns the character of the option that caused the last error,
+    /// like `optopt`. This is [`None`] if the last call to
+    /// [`Getopt::next`] succeeded, or if the error was not caused by a
+    /// short option.
+    pub fn optopt(&self) -> Option<char> {
+        self.optopt
+    }
+
+    /// Returns the error behind the last `'?'` or `':'`, or [`None`] if
+    /// the last call to [`Getopt::next`] succeeded.
+    pub fn error(&self) -> Option<&Error<A>> {
+        self.error.as_ref()
+    }
+
+    /// Returns [`Options::next_positional`] for the wrapped
+    /// [`Options`].
+    pub fn next_positional(&mut self) -> Option<A> {
+        self.opts.next_positional()
+    }
+
+    /// Returns [`Options::opts_ended`] for the wrapped [`Options`].
+    pub fn opts_ended(&self) -> bool {
+        self.opts.opts_ended()
+    }
+
+    /// Returns [`Options::is_empty`] for the wrapped [`Options`].
+    pub fn is_empty(&self) -> bool {
+        self.opts.is_empty()
+    }
+
+    /// Consumes this [`Getopt`], returning the wrapped [`Options`].
+    pub fn into_inner(self) -> Options<A, I> {
+        self.opts
+    }
+}
+
+impl<A: Argument, I: Iterator<Item = A>> Iterator for Getopt<'_, A, I>
+where
+    A::ShortOpt: Into<char>,
+{
+    type Item = (char, Option<A>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.fetch()? {
+            Ok(opt) => Some(self.short(opt)),
+            Err(item) => Some(item),
+        }
+    }
+}
+
+/// Whether a [`LongOptSpec`] takes a value, like the `has_arg` field of
+/// `struct option`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum HasArg {
+    /// The option does not take a value, like `no_argument`.
+    No,
+    /// The option requires a value, which can be attached like
+    /// `--name=value` or be the next argument, like
+    /// `required_argument`.
+    Required,
+    /// The option takes an optional value, which must be attached like
+    /// `--name=value`, like `optional_argument`.
+    Optional,
+}
+
+/// A long option recognized by [`GetoptLong`], like an entry of the
+/// `longopts` array passed to `getopt_long(3)`.
+///
+/// There is no equivalent of the `flag` field: [`GetoptLong`] always
+/// returns [`LongOptSpec::val`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct LongOptSpec<'s> {
+    /// The name of the option, without the leading dashes.
+    pub name: &'s str,
+    /// Whether the option takes a value.
+    pub has_arg: HasArg,
+    /// The value returned when the option is found.
+    pub val: char,
+}
+
+impl<'s> LongOptSpec<'s> {
+    /// Creates a new [`LongOptSpec`].
+    pub const fn new(name: &'s str, has_arg: HasArg, val: char) -> Self {
+        Self { name, has_arg, val }
+    }
+}
+
+/// A [`Getopt`] that also recognizes long options from a table of
+/// [`LongOptSpec`]s, like `getopt_long(3)`.
+///
+/// This type can be obtained by calling [`Options::getopt_long`]. Short
+/// options and errors behave the same as in [`Getopt`]. Long options
+/// return the [`LongOptSpec::val`] of the matching entry, and
+/// [`GetoptLong::longindex`] returns its index, like `longindex`.
+///
+/// Like glibc, long options can be abbreviated to any unambiguous
+/// prefix, so `--verb` matches `--verbose`. Prefixes that match several
+/// entries are only accepted if the entries are identical apart from
+/// their names, and are reported as unknown options otherwise. A
+/// [`HasArg::No`] option given a value, like `--verbose=yes`, returns
+/// `'?'`.
+///
+/// If the optstring contains `W;`, the GNU `-W` extension is enabled,
+/// like glibc: `-W name=value` and `-Wname` are parsed as the long
+/// options `--name=value` and `--name`, and return the same results.
+/// Otherwise, `-W` is an ordinary short option.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{HasArg, LongOptSpec, Options};
+/// #
+/// const LONGOPTS: &[LongOptSpec] = &[
+///     LongOptSpec::new("verbose", HasArg::No, 'v'),
+///     LongOptSpec::new("output", HasArg::Required, 'o'),
+///     LongOptSpec::new("color", HasArg::Optional, 'C'),
+/// ];
+///
+/// let args = ["--verb", "--output", "out.txt", "--color=always", "-v"];
+/// let mut opts = Options::new(args.into_iter()).getopt_long("vo:", LONGOPTS);
+///
+/// assert_eq!(opts.next(), Some(('v', None)));
+/// assert_eq!(opts.longindex(), Some(0));
+/// assert_eq!(opts.next(), Some(('o', Some("out.txt"))));
+/// assert_eq!(opts.next(), Some(('C', Some("always"))));
+/// assert_eq!(opts.longindex(), Some(2));
+/// assert_eq!(opts.next(), Some(('v', None)));
+/// assert_eq!(opts.longindex(), None);
+/// assert_eq!(opts.next(), None);
+///
+/// let args = ["-W", "output=out.txt", "-Wverbose"];
+/// let mut opts = Options::new(args.into_iter()).getopt_long("W;", LONGOPTS);
+///
+/// assert_eq!(opts.next(), Some(('o', Some("out.txt"))));
+/// assert_eq!(opts.next(), Some(('v', None)));
+/// assert_eq!(opts.next(), None);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct GetoptLong<'s, A: Argument, I: Iterator<Item = A>> {
+    getopt: Getopt<'s, A, I>,
+    longopts: &'s [LongOptSpec<'s>],
+    longindex: Option<usize>,
+    w_long: bool,
+}
+
+impl<'s, A: Argument + AsRef<[u8]>, I: Iterator<Item = A>> GetoptLong<'s, A, I>
+where
+    A::ShortOpt: Into<char>,
+{
+    pub(crate) fn new(
+        opts: Options<A, I>,
+        optstring: &'s str,
+        longopts: &'s [LongOptSpec<'s>],
+    ) -> Self {
+        Self {
+            getopt: Getopt::new(opts, optstring),
+            longopts,
+            longindex: None,
+            w_long: optstring.contains("W;"),
+        }
+    }
+
+    /// Returns the index of the entry in `longopts` that `name` matches
+    /// exactly, or as an unambiguous prefix.
+    fn lookup(&self, name: &[u8]) -> Option<usize> {
+        if name.is_empty() {
+            return None;
+        }
+
+        if let Some(index) = self
+            .longopts
+            .iter()
+            .position(|spec| spec.name.as_bytes() == name)
+        {
+            return Some(index);
+        }
+
+        let mut matches = self
+            .longopts
+            .iter()
+            .enumerate()
+            .filter(|(_, spec)| spec.name.as_bytes().starts_with(name));
+        let (index, first) = matches.next()?;
+
+        matches
+            .all(|(_, spec)| spec.has_arg == first.has_arg && spec.val == first.val)
+            .then_some(index)
+    }
+
+    /// Handles the long option at `index`, whose name was given as
+    /// `opt`, with the value `attached` to it.
+    fn long(&mut self, opt: Opt<A>, index: usize, attached: Option<A>) -> (char, Option<A>) {
+        let spec = self.longopts[index];
+        self.longindex = Some(index);
+
+        match (spec.has_arg, attached) {
+            (HasArg::No, Some(_)) => {
+                self.getopt.optopt = Some(spec.val);
+                self.getopt.fail('?', Error::DoesNotRequireValue(opt))
+            }
+            (HasArg::Required, None) => match self.getopt.opts.next_value_if(|_, _| true) {
+                Some(value) => (spec.val, Some(value)),
+                None => self.getopt.missing(spec.val, Error::RequiresValue(opt)),
+            },
+            (_, value) => (spec.val, value),
+        }
+    }
+
+    /// Handles `-W name=value` as the long option `--name=value`.
+    fn w_option(&mut self) -> (char, Option<A>) {
+        let value = match self.getopt.opts.value() {
+            Ok(value) => value,
+            Err(error) => return self.getopt.missing('W', error),
+        };
+
+        let (name, attached) = value.split_on(b'=');
+
+        match self.lookup(name.as_ref()) {
+            Some(index) => self.long(Opt::Long(name), index, attached),
+            None => self.getopt.fail('?', Error::UnknownOption(Opt::Long(name))),
+        }
+    }
+
+    /// Returns the index in `longopts` of the long option that was last
+    /// returned, like `longindex`, or [`None`] if the last call to
+    /// [`GetoptLong::next`] did not return a long option.
+    pub fn longindex(&self) -> Option<usize> {
+        self.longindex
+    }
+
+    /// Returns [`Getopt::optopt`] for the wrapped [`Getopt`]. For long
+    /// options, this is the [`LongOptSpec::val`] of the option whose
+    /// value was missing or unexpected.
+    pub fn optopt(&self) -> Option<char> {
+        self.getopt.optopt()
+    }
+
+    /// Returns [`Getopt::error`] for the wrapped [`Getopt`].
+    pub fn error(&self) -> Option<&Error<A>> {
+        self.getopt.error()
+    }
+
+    /// Returns [`Options::next_positional`] for the wrapped
+    /// [`Options`].
+    pub fn next_positional(&mut self) -> Option<A> {
+        self.getopt.next_positional()
+    }
+
+    /// Returns [`Options::opts_ended`] for the wrapped [`Options`].
+    pub fn opts_ended(&self) -> bool {
+        self.getopt.opts_ended()
+    }
+
+    /// Returns [`Options::is_empty`] for the wrapped [`Options`].
+    pub fn is_empty(&self) -> bool {
+        self.getopt.is_empty()
+    }
+
+    /// Consumes this [`GetoptLong`], returning the wrapped [`Options`].
+    pub fn into_inner(self) -> Options<A, I> {
+        self.getopt.into_inner()
+    }
+}
+
+impl<A: Argument + AsRef<[u8]>, I: Iterator<Item = A>> Iterator for GetoptLong<'_, A, I>
+where
+    A::ShortOpt: Into<char>,
+{
+    type Item = (char, Option<A>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.longindex = None;
+
+        let opt = match self.getopt.fetch()? {
+            Ok(opt) => opt,
+            Err(item) => return Some(item),
+        };
+
+        let found = match &opt {
+            Opt::Long(name) => self.lookup(name.as_ref()),
+            Opt::Short(short) if self.w_long && (*short).into() == 'W' => {
+                return Some(self.w_option())
+            }
+            _ => return Some(self.getopt.short(opt)),
+        };
+
+        let index = match found {
+            Some(index) => index,
+            None => return Some(self.getopt.fail('?', Error::UnknownOption(opt))),
+        };
+
+        let attached = self.getopt.opts.value_opt();
+        Some(self.long(opt, index, attached))
+    }
+}
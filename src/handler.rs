@@ -0,0 +1,152 @@
+use core::ops::ControlFlow;
+
+use crate::{Argument, Error, Opt, Options, Result, ValueSource};
+
+/// A handler that is called for every option and positional argument
+/// by [`Options::parse_with`], which runs the parsing loop internally.
+///
+/// This lets frameworks accept option handlers from plugins without
+/// handing them the [`Options`] itself: handlers only see the options
+/// and positional arguments, and a [`ValueAccess`] for retrieving the
+/// value of the current option.
+///
+/// Each method returns [`ControlFlow::Break`] to stop parsing early,
+/// with a value of type `B` that is returned by
+/// [`Options::parse_with`]. Errors are of type `E`, which parse errors
+/// are converted to with [`From`]. [`ParseHandler::on_positional`]
+/// has a default implementation that ignores positional arguments.
+///
+/// # Example
+///
+/// ```
+/// # use core::ops::ControlFlow;
+/// # use getargs::{Opt, Options, ParseHandler, ValueAccess};
+/// #
+/// #[derive(Default)]
+/// struct Config<'a> {
+///     verbose: bool,
+///     output: Option<&'a str>,
+///     files: Vec<&'a str>,
+/// }
+///
+/// impl<'a> ParseHandler<&'a str> for Config<'a> {
+///     fn on_opt(
+///         &mut self,
+///         opt: Opt<&'a str>,
+///         value: &mut ValueAccess<'_, &'a str>,
+///     ) -> getargs::Result<&'a str, ControlFlow<()>> {
+///         match opt {
+///             Opt::Short('v') => self.verbose = true,
+///             Opt::Short('o') => self.output = Some(value.value()?),
+///             _ => return Err(getargs::Error::UnknownOption(opt)),
+///         }
+///
+///         Ok(ControlFlow::Continue(()))
+///     }
+///
+///     fn on_positional(
+///         &mut self,
+///         positional: &'a str,
+///     ) -> getargs::Result<&'a str, ControlFlow<()>> {
+///         self.files.push(positional);
+///         Ok(ControlFlow::Continue(()))
+///     }
+/// }
+///
+/// let args = ["-v", "-o", "out.txt", "a.txt", "b.txt"];
+/// let mut opts = Options::new(args.into_iter());
+/// let mut config = Config::default();
+///
+/// assert_eq!(opts.parse_with(&mut config), Ok(ControlFlow::Continue(())));
+/// assert!(config.verbose);
+/// assert_eq!(config.output, Some("out.txt"));
+/// assert_eq!(config.files, ["a.txt", "b.txt"]);
+/// ```
+pub trait ParseHandler<A: Argument, B = (), E = Error<A>> {
+    /// Called when an option is parsed. Its value, if it takes one, can
+    /// be retrieved from `value`.
+    fn on_opt(
+        &mut self,
+        opt: Opt<A>,
+        value: &mut ValueAccess<'_, A>,
+    ) -> core::result::Result<ControlFlow<B>, E>;
+
+    /// Called when a positional argument is parsed.
+    fn on_positional(&mut self, positional: A) -> core::result::Result<ControlFlow<B>, E> {
+        let _ = positional;
+        Ok(ControlFlow::Continue(()))
+    }
+}
+
+impl<A: Argument, B, E, H: ParseHandler<A, B, E> + ?Sized> ParseHandler<A, B, E> for &'_ mut H {
+    fn on_opt(
+        &mut self,
+        opt: Opt<A>,
+        value: &mut ValueAccess<'_, A>,
+    ) -> core::result::Result<ControlFlow<B>, E> {
+        (**self).on_opt(opt, value)
+    }
+
+    fn on_positional(&mut self, positional: A) -> core::result::Result<ControlFlow<B>, E> {
+        (**self).on_positional(positional)
+    }
+}
+
+/// Gives a [`ParseHandler`] access to the value of the current option,
+/// and nothing else.
+///
+/// Its methods behave like the [`Options`] methods of the same names.
+/// The type of the underlying iterator is hidden, so that handlers
+/// don't depend on it.
+pub struct ValueAccess<'o, A: Argument> {
+    opts: &'o mut dyn TakeValue<A>,
+}
+
+impl<'o, A: Argument> ValueAccess<'o, A> {
+    pub(crate) fn new<I: Iterator<Item = A>>(opts: &'o mut Options<A, I>) -> Self {
+        Self { opts }
+    }
+
+    /// Returns [`Options::value`] for the current option.
+    pub fn value(&mut self) -> Result<A, A> {
+        self.opts.value()
+    }
+
+    /// Returns [`Options::value_opt`] for the current option.
+    pub fn value_opt(&mut self) -> Option<A> {
+        self.opts.value_opt()
+    }
+
+    /// Returns [`Options::value_with_source`] for the current option.
+    pub fn value_with_source(&mut self) -> Result<A, (A, ValueSource)> {
+        self.opts.value_with_source()
+    }
+}
+
+impl<A: Argument> core::fmt::Debug for ValueAccess<'_, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ValueAccess").finish_non_exhaustive()
+    }
+}
+
+/// The methods of [`Options`] that a [`ValueAccess`] forwards to,
+/// without the type of its iterator.
+trait TakeValue<A: Argument> {
+    fn value(&mut self) -> Result<A, A>;
+    fn value_opt(&mut self) -> Option<A>;
+    fn value_with_source(&mut self) -> Result<A, (A, ValueSource)>;
+}
+
+impl<A: Argument, I: Iterator<Item = A>> TakeValue<A> for Options<A, I> {
+    fn value(&mut self) -> Result<A, A> {
+        Options::value(self)
+    }
+
+    fn value_opt(&mut self) -> Option<A> {
+        Options::value_opt(self)
+    }
+
+    fn value_with_source(&mut self) -> Result<A, (A, ValueSource)> {
+        Options::value_with_source(self)
+    }
+}
@@ -0,0 +1,289 @@
+use core::fmt::{self, Display, Formatter, Write};
+
+use crate::OptSpec;
+
+/// Renders help text for a table of [`OptSpec`]s.
+///
+/// Each option is written on its own line, with its names on the left
+/// and its [`help`][OptSpec#structfield.help] text aligned in a column
+/// on the right, like GNU tools do:
+///
+/// ```text
+///   -v, --verbose        print more output
+///   -o, --output FILE    write output to FILE
+///       --dry-run        don't write anything
+/// ```
+///
+/// Help text can be written to any [`core::fmt::Write`] using
+/// [`Help::write_to`], so this works without `std` or allocation.
+/// [`Help`] also implements [`Display`], so it can be printed directly.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Help, OptSpec};
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().short('v').long("verbose").help("print more output"),
+///     OptSpec::new()
+///         .short('o')
+///         .long("output")
+///         .takes_value(true)
+///         .value_name("FILE")
+///         .help("write output to FILE"),
+///     OptSpec::new().long("dry-run").help("don't write anything"),
+/// ];
+///
+/// assert_eq!(
+///     Help::new(SPECS).to_string(),
+///     "  -v, --verbose        print more output
+///   -o, --output FILE    write output to FILE
+///       --dry-run        don't write anything
+/// "
+/// );
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Help<'a, S = char, L = &'static str> {
+    specs: &'a [OptSpec<S, L>],
+}
+
+impl<'a, S: Copy + Display, L: Copy + Display> Help<'a, S, L> {
+    /// Creates a new [`Help`] for `specs`.
+    pub const fn new(specs: &'a [OptSpec<S, L>]) -> Self {
+        Self { specs }
+    }
+
+    /// Writes the help text to `writer`.
+    pub fn write_to<W: Write + ?Sized>(&self, writer: &mut W) -> fmt::Result {
+        let width = self
+            .specs
+            .iter()
+            .map(|spec| {
+                let mut counter = Counter(0);
+                let _ = write_names(spec, true, &mut counter);
+                counter.0
+            })
+            .max()
+            .unwrap_or(0);
+
+        for spec in self.specs {
+            let mut counter = Counter(0);
+            let _ = write_names(spec, true, &mut counter);
+
+            writer.write_str("  ")?;
+            write_names(spec, true, writer)?;
+
+            if let Some(help) = spec.help {
+                write!(writer, "{:pad$}{}", "", help, pad = width - counter.0 + 4)?;
+            }
+
+            writer.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the help text to `writer` as a Markdown table, for
+    /// embedding into READMEs and other documentation. Pipes are
+    /// escaped so that they don't end table cells early.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Help, OptSpec};
+    /// #
+    /// const SPECS: &[OptSpec] = &[
+    ///     OptSpec::new().short('v').long("verbose").help("print more output"),
+    ///     OptSpec::new().long("level").takes_value(true).help("set level (1|2)"),
+    /// ];
+    ///
+    /// let mut markdown = String::new();
+    /// Help::new(SPECS).to_markdown(&mut markdown)?;
+    ///
+    /// assert_eq!(
+    ///     markdown,
+    ///     r"| Option | Description |
+    /// | --- | --- |
+    /// | `-v, --verbose` | print more output |
+    /// | `--level VALUE` | set level (1\|2) |
+    /// "
+    /// );
+    /// # Ok::<(), core::fmt::Error>(())
+    /// ```
+    pub fn to_markdown<W: Write + ?Sized>(&self, writer: &mut W) -> fmt::Result {
+        writer.write_str("| Option | Description |\n| --- | --- |\n")?;
+
+        for spec in self.specs {
+            writer.write_str("| `")?;
+            write_names(spec, false, &mut EscapePipes(writer))?;
+            writer.write_str("` | ")?;
+            EscapePipes(writer).write_str(spec.help.unwrap_or(""))?;
+            writer.write_str(" |\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S: Copy + Display, L: Copy + Display> Display for Help<'_, S, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+/// Writes the names of `spec` (and its value, if any) as they appear
+/// in the left column of help text, like `-o, --output FILE`. If
+/// `indent` is `true`, long options without a short name are indented
+/// to line up with those that have one. Negatable options are written
+/// like `--[no-]color`.
+fn write_names<S: Display, L: Display, W: Write + ?Sized>(
+    spec: &OptSpec<S, L>,
+    indent: bool,
+    writer: &mut W,
+) -> fmt::Result {
+    let no = if spec.negatable { "[no-]" } else { "" };
+
+    match (&spec.short, &spec.long) {
+        (Some(short), Some(long)) => write!(writer, "-{}, --{}{}", short, no, long)?,
+        (Some(short), None) => write!(writer, "-{}", short)?,
+        (None, Some(long)) if indent => write!(writer, "    --{}{}", no, long)?,
+        (None, Some(long)) => write!(writer, "--{}{}", no, long)?,
+        (None, None) => {}
+    }
+
+    for _ in 0..spec.value_count() {
+        write!(writer, " {}", spec.value_name.unwrap_or("VALUE"))?;
+    }
+
+    Ok(())
+}
+
+/// A [`Write`] that escapes pipes for Markdown tables before passing
+/// them on to the wrapped writer.
+struct EscapePipes<'w, W: ?Sized>(&'w mut W);
+
+impl<W: Write + ?Sized> Write for EscapePipes<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, part) in s.split('|').enumerate() {
+            if i > 0 {
+                self.0.write_str("\\|")?;
+            }
+
+            self.0.write_str(part)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`Write`] that only counts the characters written to it.
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Renders a terse usage line for a table of [`OptSpec`]s.
+///
+/// Flags with short names are grouped together, and every other option
+/// is listed separately with its value, like
+/// `usage: prog [-abc] [-o FILE] [--dry-run] [--] ARGS...`. Like
+/// [`Help`], this can be written to any [`core::fmt::Write`] using
+/// [`Usage::write_to`], or printed directly using [`Display`]. No
+/// newline is written at the end.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{OptSpec, Usage};
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().short('a'),
+///     OptSpec::new().short('b').long("bee"),
+///     OptSpec::new().short('o').takes_value(true).value_name("FILE"),
+///     OptSpec::new().long("dry-run"),
+/// ];
+///
+/// assert_eq!(
+///     Usage::new("prog", SPECS).operands("ARGS...").to_string(),
+///     "usage: prog [-ab] [-o FILE] [--dry-run] [--] ARGS..."
+/// );
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Usage<'a, S = char, L = &'static str> {
+    program: &'a str,
+    specs: &'a [OptSpec<S, L>],
+    operands: Option<&'a str>,
+}
+
+impl<'a, S: Copy + Display, L: Copy + Display> Usage<'a, S, L> {
+    /// Creates a new [`Usage`] for `program` with the options in
+    /// `specs`.
+    pub const fn new(program: &'a str, specs: &'a [OptSpec<S, L>]) -> Self {
+        Self {
+            program,
+            specs,
+            operands: None,
+        }
+    }
+
+    /// Sets a description of the positional arguments, like `ARGS...`,
+    /// which is written at the end after `[--]`.
+    pub const fn operands(mut self, operands: &'a str) -> Self {
+        self.operands = Some(operands);
+        self
+    }
+
+    /// Writes the usage line to `writer`.
+    pub fn write_to<W: Write + ?Sized>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "usage: {}", self.program)?;
+
+        let mut flags = self
+            .specs
+            .iter()
+            .filter(|spec| !spec.takes_value)
+            .filter_map(|spec| spec.short)
+            .peekable();
+
+        if flags.peek().is_some() {
+            writer.write_str(" [-")?;
+
+            for short in flags {
+                write!(writer, "{}", short)?;
+            }
+
+            writer.write_char(']')?;
+        }
+
+        for spec in self.specs {
+            match (spec.short, spec.long) {
+                (Some(_), _) if !spec.takes_value => continue,
+                (Some(short), _) => write!(writer, " [-{}", short)?,
+                (None, Some(long)) if spec.negatable => write!(writer, " [--[no-]{}", long)?,
+                (None, Some(long)) => write!(writer, " [--{}", long)?,
+                (None, None) => continue,
+            }
+
+            for _ in 0..spec.value_count() {
+                write!(writer, " {}", spec.value_name.unwrap_or("VALUE"))?;
+            }
+
+            writer.write_char(']')?;
+        }
+
+        if let Some(operands) = self.operands {
+            write!(writer, " [--] {}", operands)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S: Copy + Display, L: Copy + Display> Display for Usage<'_, S, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::collections::VecDeque;
+
+/// An iterator adapter that allows arguments to be inserted in front
+/// of the remaining arguments while they are being parsed.
+///
+/// [`Options`] over an [`Injectable`] gain an [`Options::inject`]
+/// method, which can be used to expand aliases or to parse arguments
+/// from other sources, like configuration files, with the same
+/// machinery. When used on its own, [`Injectable::inject`] does the
+/// same thing, but on the iterator directly.
+///
+/// This type requires the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Injectable, Opt, Options};
+/// #
+/// let args = ["-A", "file"];
+/// let mut opts = Options::new(Injectable::new(args.into_iter()));
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('A'))));
+/// opts.inject(["--foo", "--bar=1"]);
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("foo"))));
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("bar"))));
+/// assert_eq!(opts.value(), Ok("1"));
+/// assert_eq!(opts.next_opt(), Ok(None));
+/// assert_eq!(opts.next_positional(), Some("file"));
+/// ```
+///
+/// [`Options`]: crate::Options
+/// [`Options::inject`]: crate::Options::inject
+#[derive(Clone, Debug)]
+pub struct Injectable<A, I: Iterator<Item = A>> {
+    injected: VecDeque<A>,
+    iter: I,
+}
+
+impl<A, I: Iterator<Item = A>> Injectable<A, I> {
+    /// Creates a new [`Injectable`] with no injected arguments.
+    pub fn new(iter: I) -> Self {
+        Self {
+            injected: VecDeque::new(),
+            iter,
+        }
+    }
+
+    /// Inserts `args` in front of the remaining arguments, so that they
+    /// are returned next, in order.
+    pub fn inject<J: IntoIterator<Item = A>>(&mut self, args: J) {
+        let len = self.injected.len();
+        self.injected.extend(args);
+        self.injected.rotate_left(len);
+    }
+
+    /// Consumes this [`Injectable`], returning the wrapped iterator.
+    /// Injected arguments that have not been returned yet are lost.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<A, I: Iterator<Item = A>> Iterator for Injectable<A, I> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.injected.pop_front().or_else(|| self.iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let len = self.injected.len();
+        (
+            lower.saturating_add(len),
+            upper.and_then(|upper| upper.checked_add(len)),
+        )
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Argument, Options};
+use crate::{Arg, Argument, Error, Opt, Options};
 
 /// An iterator over the positional arguments of an [`Options`].
 ///
@@ -40,6 +40,10 @@ impl<'opts, A: Argument, I: Iterator<Item = A>> Iterator for Positionals<'opts,
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next_positional()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.positionals_size_hint()
+    }
 }
 
 /// An iterator over what used to be the positional arguments of an
@@ -67,13 +71,13 @@ impl<'opts, A: Argument, I: Iterator<Item = A>> Iterator for Positionals<'opts,
 /// ```
 #[derive(Copy, Clone, Debug)]
 pub struct IntoPositionals<A: Argument, I: Iterator<Item = A>> {
-    positional: Option<A>,
+    positionals: [Option<A>; 2],
     iter: I,
 }
 
 impl<A: Argument, I: Iterator<Item = A>> IntoPositionals<A, I> {
-    pub(crate) fn new(positional: Option<A>, iter: I) -> Self {
-        Self { positional, iter }
+    pub(crate) fn new(positionals: [Option<A>; 2], iter: I) -> Self {
+        Self { positionals, iter }
     }
 }
 
@@ -81,6 +85,242 @@ impl<A: Argument, I: Iterator<Item = A>> Iterator for IntoPositionals<A, I> {
     type Item = A;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.positional.take().or_else(|| self.iter.next())
+        match self.positionals[0].take() {
+            Some(positional) => {
+                self.positionals[0] = self.positionals[1].take();
+                Some(positional)
+            }
+            None => self.iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let extra = self.positionals.iter().flatten().count();
+        (
+            lower.saturating_add(extra),
+            upper.and_then(|upper| upper.checked_add(extra)),
+        )
+    }
+}
+
+impl<A: Argument, I: ExactSizeIterator<Item = A>> ExactSizeIterator for IntoPositionals<A, I> {}
+
+/// An iterator over the positional arguments of an [`Options`], split
+/// into `key=value` operands.
+///
+/// Calls to [`Iterator::next`] will forward to
+/// [`Options::next_positional`] and split the result with
+/// [`Argument::split_on`]. This iterator can be obtained by calling
+/// [`Options::operands`].
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, Options};
+/// #
+/// let args = ["-v", "count=3", "seek=1"];
+/// let mut opts = Options::new(args.into_iter());
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+/// assert_eq!(opts.next_opt(), Ok(None));
+///
+/// let operands: Vec<_> = opts.operands().collect();
+///
+/// assert_eq!(operands, [("count", Some("3")), ("seek", Some("1"))]);
+/// ```
+#[derive(Debug)]
+pub struct Operands<'opts, A: Argument, I: Iterator<Item = A>> {
+    inner: &'opts mut Options<A, I>,
+}
+
+impl<'opts, A: Argument, I: Iterator<Item = A>> Operands<'opts, A, I> {
+    pub(crate) fn new(inner: &'opts mut Options<A, I>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'opts, A: Argument, I: Iterator<Item = A>> Iterator for Operands<'opts, A, I> {
+    type Item = (A, Option<A>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_positional()
+            .map(|operand| operand.split_on(b'='))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.positionals_size_hint()
+    }
+}
+
+/// An iterator over the arguments of an [`Options`], both options and
+/// positional arguments.
+///
+/// Calls to [`Iterator::next`] will forward to [`Options::next_arg`],
+/// so that parsing loops can use `for` and iterator adapters. This
+/// iterator can be obtained by calling [`Options::iter_args`].
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Arg, Options};
+/// #
+/// let args = ["-a", "one", "--bee", "two"];
+/// let mut opts = Options::new(args.into_iter());
+///
+/// let positionals: Result<Vec<_>, _> = opts
+///     .iter_args()
+///     .filter_map(|arg| arg.map(Arg::positional).transpose())
+///     .collect();
+///
+/// assert_eq!(positionals, Ok(vec!["one", "two"]));
+/// ```
+#[derive(Debug)]
+pub struct Args<'opts, A: Argument, I: Iterator<Item = A>> {
+    inner: &'opts mut Options<A, I>,
+}
+
+impl<'opts, A: Argument, I: Iterator<Item = A>> Args<'opts, A, I> {
+    pub(crate) fn new(inner: &'opts mut Options<A, I>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'opts, A: Argument, I: Iterator<Item = A>> Iterator for Args<'opts, A, I> {
+    type Item = Result<Arg<A>, Error<A>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_arg().transpose()
+    }
+}
+
+/// An iterator over the options of an [`Options`].
+///
+/// Calls to [`Iterator::next`] will forward to [`Options::next_opt`],
+/// so the iterator ends at the first positional argument, `--`, or the
+/// end of the arguments. This iterator can be obtained by calling
+/// [`Options::opts`].
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, Options};
+/// #
+/// let args = ["-a", "--bee", "file"];
+/// let mut opts = Options::new(args.into_iter());
+/// let mut iter = opts.opts();
+///
+/// assert_eq!(iter.next(), Some(Ok(Opt::Short('a'))));
+/// assert_eq!(iter.next(), Some(Ok(Opt::Long("bee"))));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Debug)]
+pub struct Opts<'opts, A: Argument, I: Iterator<Item = A>> {
+    inner: &'opts mut Options<A, I>,
+}
+
+impl<'opts, A: Argument, I: Iterator<Item = A>> Opts<'opts, A, I> {
+    pub(crate) fn new(inner: &'opts mut Options<A, I>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'opts, A: Argument, I: Iterator<Item = A>> Iterator for Opts<'opts, A, I> {
+    type Item = Result<Opt<A>, Error<A>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_opt().transpose()
+    }
+}
+
+/// An iterator over the values of an option that takes any number of
+/// them.
+///
+/// This iterator can be obtained by calling [`Options::values`] or
+/// [`Options::values_while`]. The values it doesn't take are left for
+/// [`Options::next_opt`].
+#[derive(Debug)]
+pub struct Values<'opts, A: Argument, I: Iterator<Item = A>, F> {
+    inner: &'opts mut Options<A, I>,
+    accept: F,
+    /// Whether to stop before arguments that would be parsed as
+    /// options, for [`Options::values`].
+    until_opt: bool,
+}
+
+impl<'opts, A: Argument, I: Iterator<Item = A>, F> Values<'opts, A, I, F> {
+    pub(crate) fn new(inner: &'opts mut Options<A, I>, accept: F) -> Self {
+        Self {
+            inner,
+            accept,
+            until_opt: false,
+        }
+    }
+
+    /// Makes this iterator also stop before arguments that would be
+    /// parsed as options.
+    pub(crate) fn until_opt(mut self) -> Self {
+        self.until_opt = true;
+        self
+    }
+}
+
+impl<A: Argument, I: Iterator<Item = A>, F: FnMut(&A) -> bool> Iterator for Values<'_, A, I, F> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let until_opt = self.until_opt;
+        let accept = &mut self.accept;
+
+        self.inner
+            .next_value_if(|opts, arg| !(until_opt && opts.looks_like_opt(arg)) && accept(arg))
+    }
+}
+
+/// An iterator over the parts of an argument separated by an ASCII
+/// delimiter, like `a`, `b` and `c` in `a,b,c`.
+///
+/// Like [`str::split`], empty parts are kept, so `a,,b` yields an empty
+/// part between `a` and `b`, and an empty argument yields one empty
+/// part. Arguments are split with [`Argument::split_on`], so arguments
+/// that don't override it are never split.
+///
+/// This iterator can be obtained by calling
+/// [`Options::values_delimited`], or by calling [`Delimited::new`] on
+/// any argument.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::Delimited;
+/// #
+/// let parts = Delimited::new(&b"x:y:"[..], b':');
+/// assert!(parts.eq([&b"x"[..], b"y", b""]));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Delimited<A: Argument> {
+    rest: Option<A>,
+    delimiter: u8,
+}
+
+impl<A: Argument> Delimited<A> {
+    /// Creates a new [`Delimited`] over the parts of `arg` separated
+    /// by `delimiter`.
+    pub fn new(arg: A, delimiter: u8) -> Self {
+        Self {
+            rest: Some(arg),
+            delimiter,
+        }
+    }
+}
+
+impl<A: Argument> Iterator for Delimited<A> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (part, rest) = self.rest.take()?.split_on(self.delimiter);
+        self.rest = rest;
+        Some(part)
     }
 }
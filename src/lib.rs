@@ -91,6 +91,26 @@
 //!
 //! On other platforms, `argv` will leak memory, so be careful!
 //!
+//! In `#[no_main]` binaries and functions called from C, the `argc` and
+//! `argv` given to the entry point can be iterated over with `Argv`
+//! from the `#![no_std]` [`getargs_argv`] crate, which keeps the
+//! `unsafe` code needed to read them out of `getargs`.
+//!
+//! `&OsStr` can also be parsed directly, so arguments from
+//! [`args_os()`][std::env::args_os] don't need to be valid UTF-8. On
+//! platforms other than Unix, arguments that aren't valid Unicode are
+//! always positional arguments:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use getargs::Options;
+//! use std::ffi::OsString;
+//!
+//! let args = std::env::args_os().skip(1).collect::<Vec<_>>();
+//! let opts = Options::new(args.iter().map(OsString::as_os_str));
+//! # }
+//! ```
+//!
 //! Then, pass the iterator to [`Options::new`]:
 //!
 //! ```
@@ -225,30 +245,143 @@
 #![doc = include_str!("../examples/anywhere.rs")]
 #![doc = "```"]
 //!
+//! ## Upgrading from 0.5
+//!
+//! [`Opt`] and [`Arg`] are now `#[non_exhaustive]`, and have the new
+//! variants [`Opt::ShortPlus`] and [`Opt::Numeric`] (and their [`Arg`]
+//! counterparts) for options like `+x` and `-5`. These are only
+//! returned when [`ParseConfig::plus_opts`] or
+//! [`ParseConfig::numeric_opts`] is enabled, but a `match` on [`Opt`]
+//! or [`Arg`] now needs a `_` arm. New kinds of options will be added
+//! the same way, behind a [`ParseConfig`] switch, without breaking
+//! `match`es again.
+//!
+//! [`Argument`] now requires `Clone + Eq + Debug` instead of `Copy`, so
+//! that owned arguments like `String` and `Vec<u8>` can be parsed.
+//! Code that is generic over `A: Argument` and relied on copying
+//! arguments needs to `.clone()` them instead, and custom
+//! [`Argument`] types need to derive or implement those traits. For
+//! `&str` and `&[u8]` a clone is still just a copy.
+//!
 //! # Examples
 //!
 //! There are other examples available on [GitHub].
 //!
 //! [`argv`]: https://crates.io/crates/argv
+//! [`getargs_argv`]: https://docs.rs/getargs_argv
 //! [`OsStrExt`]: std::os::unix::ffi::OsStrExt
 //! [`while let`]: https://doc.rust-lang.org/rust-by-example/flow_control/while_let.html
 //! [GitHub]: https://github.com/j-tai/getargs/tree/master/examples
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+mod alias;
 mod arg;
+mod checkpoint;
+#[cfg(feature = "std")]
+mod complete;
+mod config;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub mod de;
+mod dispatch;
 mod error;
+mod from;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+mod getopt;
+#[cfg(feature = "unicode")]
+mod grapheme;
+mod handler;
+mod help;
+#[cfg(feature = "alloc")]
+mod inject;
 mod iter;
+mod macros;
+#[cfg(feature = "unicode")]
+mod normalize;
+mod observe;
 mod opt;
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "alloc")]
+mod parsed;
+#[cfg(feature = "alloc")]
+mod permute;
+#[cfg(feature = "std")]
+mod response;
+mod rewind;
+mod shell;
+mod span;
+mod spec;
+pub mod suggest;
+pub mod sysexits;
 #[cfg(test)]
 mod tests;
 mod traits;
+mod utf8;
+mod validate;
+mod value;
+mod windows;
 
+#[cfg(feature = "alloc")]
+pub use alias::Aliases;
 pub use arg::Arg;
-pub use error::{Error, Result};
-pub use iter::{IntoPositionals, Positionals};
+pub use checkpoint::Checkpoint;
+#[cfg(feature = "std")]
+pub use complete::{Completions, Shell};
+pub use config::{ParseConfig, Terminator};
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub use de::from_iter;
+pub use dispatch::{Dispatch, Handler};
+pub use error::{Error, Result, ValueError};
+pub use from::FromOptions;
+#[cfg(feature = "arbitrary")]
+pub use fuzz::FuzzArgs;
+#[cfg(feature = "derive")]
+pub use getargs_derive::FromOptions;
+pub use getopt::{Getopt, GetoptLong, HasArg, LongOptSpec};
+#[cfg(feature = "unicode")]
+pub use grapheme::Graphemes;
+pub use handler::{ParseHandler, ValueAccess};
+pub use help::{Help, Usage};
+#[cfg(feature = "alloc")]
+pub use inject::Injectable;
+pub use iter::{Args, Delimited, IntoPositionals, Operands, Opts, Positionals, Values};
+#[doc(hidden)]
+pub use macros::__OptName;
+#[cfg(feature = "std")]
+pub use observe::JsonLines;
+pub use observe::{Deprecations, Observed, Occurrences, ParseObserver};
 pub use opt::Opt;
-pub use traits::Argument;
+#[cfg(feature = "alloc")]
+pub use owned::{ErrorOwned, IntoOwnedArgument};
+#[cfg(feature = "alloc")]
+pub use parsed::{OptName, ParsedArgs};
+#[cfg(feature = "alloc")]
+pub use permute::Permuting;
+#[cfg(feature = "std")]
+pub use response::ResponseFiles;
+pub use rewind::Rewindable;
+#[cfg(feature = "alloc")]
+pub use shell::CmdlineWords;
+pub use shell::ShellWords;
+pub use span::{Span, Spanned};
+pub use spec::{
+    Ambiguous, Conflict, Deprecated, MissingRequired, OptSpec, SpecTable, SpecValues, Tracker,
+};
+pub use traits::{Argument, SplitArgument};
+pub use utf8::{Utf8Args, Utf8Options};
+pub use validate::Validated;
+pub use value::{OptValue, ValueSource};
+pub use windows::WindowsArgs;
+
+use core::iter::Take;
+use core::ops::ControlFlow;
+use core::str::FromStr;
 
 /// An argument parser.
 ///
@@ -390,8 +523,29 @@ pub use traits::Argument;
 pub struct Options<A: Argument, I: Iterator<Item = A>> {
     /// Iterator over the arguments.
     iter: I,
+    /// Arguments that were taken from the iterator by
+    /// [`Options::peek_opt`] or [`Options::peek_arg`], and will be
+    /// returned before the rest of the iterator.
+    peeked: [Option<A>; 2],
+    /// The number of arguments that have been parsed so far.
+    index: usize,
+    /// The last argument that was parsed, before it was split up, if
+    /// `track_current` is enabled.
+    current: Option<A>,
+    /// Whether to keep a copy of each argument in `current`.
+    track_current: bool,
     /// State information.
     state: State<A>,
+    /// Whether option parsing is disabled.
+    raw: bool,
+    /// The predicate set with [`Options::set_help_opt`].
+    help: Option<fn(&Opt<A>) -> bool>,
+    /// Whether `config` is [`ParseConfig::default`] and arguments are
+    /// free to copy, so [`Options::next_opt`] can parse each argument
+    /// in a single pass instead of classifying it first.
+    fast: bool,
+    /// Behavior switches.
+    config: ParseConfig,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -400,6 +554,10 @@ enum State<A: Argument> {
     /// previous option. We may get a positional argument or an
     /// option.
     Start { ended_opts: bool },
+    /// Like [`State::Start`], but no argument has been read yet and
+    /// [`ParseConfig::bundled_first`] is enabled, so the first argument
+    /// may be a short option cluster without a leading `-`.
+    Begin,
     /// We found a positional option and want to preserve it, since it
     /// will no longer be returned from the iterator.
     Positional(A),
@@ -421,7 +579,39 @@ enum State<A: Argument> {
     End { ended_opts: bool },
 }
 
-impl<'arg, A: Argument + 'arg, I: Iterator<Item = A>> Options<A, I> {
+/// What [`Options::next_opt`] found in a new argument, as worked out by
+/// [`Options::classify`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum Kind {
+    /// The argument ends options.
+    EndOfOpts,
+    /// The argument is positional.
+    Positional,
+    /// A long option, like `--name` or `--name=value`.
+    Long,
+    /// A numeric option, like `-5`, if
+    /// [`ParseConfig::numeric_opts`] is enabled.
+    Numeric,
+    /// A long option with a single dash, if
+    /// [`ParseConfig::single_dash_long`] is enabled or its name is one
+    /// of [`ParseConfig::single_dash_names`].
+    SingleDashLong,
+    /// A short option cluster, like `-abc`.
+    Short,
+    /// A `/flag` or `/flag:value`, if [`ParseConfig::slash_opts`] is
+    /// enabled.
+    Slash,
+    /// A cluster of `+` options, if [`ParseConfig::plus_opts`] is
+    /// enabled.
+    Plus,
+    /// A lone `-`, if [`ParseConfig::reject_lone_dash`] is enabled.
+    LoneDash,
+    /// A short option cluster without a leading `-` in the first
+    /// argument, if [`ParseConfig::bundled_first`] is enabled.
+    Bundled,
+}
+
+impl<A: Argument, I: Iterator<Item = A>> Options<A, I> {
     /// Creates a new [`Options`] given an iterator over arguments of
     /// type [`A`][Argument].
     ///
@@ -430,360 +620,2112 @@ impl<'arg, A: Argument + 'arg, I: Iterator<Item = A>> Options<A, I> {
     /// iterator yields.
     pub fn new(iter: I) -> Options<A, I> {
         Options {
-            iter,
-            state: State::Start { ended_opts: false },
-        }
-    }
-
-    /// Retrieves the next option.
-    ///
-    /// Returns `Ok(None)` if there are no more options, or `Err(..)` if
-    /// a parse error occurs.
-    ///
-    /// This method also does not retrieve any value that goes with an
-    /// option. If the option requires an value, such as in
-    /// `--option=value`, then you should call [`Options::value`] after
-    /// receiving the option from this method. Alternatively, you can
-    /// call [`Options::value_opt`] instead for optional values.
-    ///
-    /// Once this method returns `Ok(None)`, then you should make sure
-    /// to use [`Options::next_positional`] or [`Options::positionals`]
-    /// to get the values of the following positional arguments. See the
-    /// [`Options`] documentation for more details.
-    ///
-    /// If your application accepts positional arguments in between
-    /// flags, you can use [`Options::next_arg`] instead of `next_opt`.
-    pub fn next_opt(&'_ mut self) -> Result<A, Option<Opt<A>>> {
-        match self.state {
-            State::Start { .. } | State::EndOfOption(_) => {
-                let next = self.iter.next();
-
-                if next.is_none() {
-                    self.state = State::End { ended_opts: false };
-                    return Ok(None);
-                }
-
-                let arg = next.unwrap();
-
-                if arg.ends_opts() {
-                    self.state = State::Start { ended_opts: true };
-                    Ok(None)
-                } else if let Some((name, value)) = arg.parse_long_opt() {
-                    let opt = Opt::Long(name);
-
-                    if let Some(value) = value {
-                        self.state = State::LongOptionWithValue(opt, value);
-                    } else {
-                        self.state = State::EndOfOption(opt);
-                    }
-
-                    Ok(Some(opt))
-                } else if let Some(cluster) = arg.parse_short_cluster() {
-                    let (opt, rest) = cluster.consume_short_opt();
-                    let opt = Opt::Short(opt);
-
-                    if let Some(rest) = rest {
-                        self.state = State::ShortOptionCluster(opt, rest);
-                    } else {
-                        self.state = State::EndOfOption(opt);
-                    }
-
-                    Ok(Some(opt))
-                } else {
-                    self.state = State::Positional(arg);
-                    Ok(None)
-                }
-            }
-
-            State::ShortOptionCluster(_, rest) => {
-                let (opt, rest) = rest.consume_short_opt();
-                let opt = Opt::Short(opt);
-
-                if let Some(rest) = rest {
-                    self.state = State::ShortOptionCluster(opt, rest);
-                } else {
-                    self.state = State::EndOfOption(opt);
-                }
-
-                Ok(Some(opt))
-            }
-
-            State::LongOptionWithValue(opt, _) => {
-                self.state = State::Start { ended_opts: false };
-                Err(Error::DoesNotRequireValue(opt))
-            }
-
-            State::Positional(_) | State::End { .. } => Ok(None),
+            fast: !core::mem::needs_drop::<A>(),
+            ..Self::with_config(iter, ParseConfig::new())
         }
     }
 
-    /// Retrieves the next *argument*. An *argument* is represented by
-    /// [`Arg`] and defined as either an option ([`Opt`]) or a
-    /// positional argument.
+    /// Creates a new [`Options`] like [`Options::new`], but with the
+    /// behavior switches in `config`.
     ///
     /// # Panics
     ///
-    /// Panics if [`Options::next_opt`] does.
+    /// Panics if [`ParseConfig::opt_prefix`] is not ASCII.
     ///
     /// # Example
     ///
     /// ```
-    /// # use getargs::{Arg, Opt, Options};
+    /// # use getargs::{Arg, Options, ParseConfig};
     /// #
-    /// let args = ["--option", "VALUE", "arg", "arg2", "-vvv", "--option", "VALUE", "--", "arg3", "arg4", "--not-an-option"];
-    /// let mut opts = Options::new(args.into_iter());
-    ///
-    /// // Regular options and values work...
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("option"))));
-    /// assert_eq!(opts.value(), Ok("VALUE"));
-    ///
-    /// // Positional arguments afterwards also work...
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("arg"))));
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("arg2"))));
-    ///
-    /// // Options after positional arguments also work
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('v'))));
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('v'))));
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('v'))));
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("option"))));
-    /// assert_eq!(opts.value(), Ok("VALUE"));
+    /// let args = ["-a", "foo", "-b"];
+    /// let config = ParseConfig::new().stop_at_positional(true);
+    /// let mut opts = Options::with_config(args.into_iter(), config);
     ///
-    /// // `--` is recognized and handled correctly
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("arg3"))));
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("arg4"))));
-    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("--not-an-option"))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("foo"))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("-b"))));
     /// assert_eq!(opts.next_arg(), Ok(None));
-    /// #
-    /// # assert_eq!(opts.is_empty(), true);
     /// ```
-    pub fn next_arg(&'_ mut self) -> Result<A, Option<Arg<A>>> {
-        if !self.opts_ended() {
-            if let Some(opt) = self.next_opt()? {
-                return Ok(Some(opt.into()));
-            }
+    pub fn with_config(iter: I, config: ParseConfig) -> Options<A, I> {
+        assert!(config.opt_prefix.is_ascii(), "opt_prefix must be ASCII");
+
+        Options {
+            iter,
+            peeked: [None, None],
+            index: 0,
+            current: None,
+            track_current: false,
+            state: Self::initial_state(config),
+            raw: false,
+            help: None,
+            fast: Self::is_fast(config),
+            config,
         }
+    }
 
-        Ok(self.next_positional().map(Arg::Positional))
+    /// Returns `true` if [`Options::next_opt`] can take the fast path
+    /// with `config`. See [`Options::next_opt_fast`].
+    fn is_fast(config: ParseConfig) -> bool {
+        !core::mem::needs_drop::<A>() && config == ParseConfig::new()
     }
 
-    /// Retrieves the value passed to the option last returned by
-    /// [`Options::next_opt`] or [`Options::next_arg`].
-    ///
-    /// This function returns an error if there is no value to return
-    /// because the end of the argument list has been reached.
-    ///
-    /// # Panics
+    /// Returns the state to start parsing in with `config`.
+    fn initial_state(config: ParseConfig) -> State<A> {
+        if config.bundled_first {
+            State::Begin
+        } else {
+            State::Start { ended_opts: false }
+        }
+    }
+
+    /// Returns the number of arguments that have been parsed so far.
+    /// This is the 1-based index of the argument that the last option,
+    /// value or positional argument came from, or that caused the last
+    /// error, so that error messages can point at it.
     ///
-    /// This method panics if [`Options::next_opt`] or
-    /// [`Options::next_arg`] have not yet been called, if `value` is
-    /// called twice for the same option, or if the last call to
-    /// `next_*` did not return an option.
+    /// Arguments that were only looked at by [`Options::peek_opt`] or
+    /// [`Options::peek_arg`] are not counted. [`Options::restart`] and
+    /// [`Options::rewind`] reset the index to zero.
     ///
     /// # Example
     ///
     /// ```
     /// # use getargs::{Opt, Options};
     /// #
-    /// let args = ["-aay", "--bee=foo", "-c", "see", "bar"];
+    /// let args = ["-ab", "--depth", "x", "--depth=y"];
     /// let mut opts = Options::new(args.into_iter());
     ///
+    /// assert_eq!(opts.arg_index(), 0);
     /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
-    /// assert_eq!(opts.value(), Ok("ay"));
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("bee"))));
-    /// assert_eq!(opts.value(), Ok("foo"));
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('c'))));
-    /// assert_eq!(opts.value(), Ok("see"));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    /// assert_eq!(opts.arg_index(), 1);
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("depth"))));
+    /// assert_eq!(opts.value(), Ok("x"));
+    /// assert_eq!(opts.arg_index(), 3);
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("depth"))));
+    ///
+    /// let error = opts.next_opt().unwrap_err();
+    /// let message = format!("error in argument {}: {}", opts.arg_index(), error);
+    ///
+    /// assert_eq!(message, "error in argument 4: option does not require a value: --depth");
     /// ```
-    pub fn value(&'_ mut self) -> Result<A, A> {
-        match self.state {
-            State::Start { .. } | State::Positional(_) | State::End { .. } => {
-                panic!("called Options::value() with no previous option")
-            }
-
-            State::EndOfOption(opt) => {
-                if let Some(val) = self.iter.next() {
-                    self.state = State::Start { ended_opts: false };
-                    Ok(val)
-                } else {
-                    self.state = State::End { ended_opts: false };
-                    Err(Error::RequiresValue(opt))
-                }
-            }
-
-            State::ShortOptionCluster(_, val) => {
-                self.state = State::Start { ended_opts: false };
-                Ok(val.consume_short_val())
-            }
-
-            State::LongOptionWithValue(_, val) => {
-                self.state = State::Start { ended_opts: false };
-                Ok(val)
-            }
-        }
+    pub fn arg_index(&self) -> usize {
+        self.index
     }
 
-    /// Retrieves an *optional* value for the option last returned by
-    /// [`Options::next_opt`] or [`Options::next_arg`]. Only explicit
-    /// values are accepted (`--flag=VALUE`, `-fVALUE`). Implicit values
-    /// (`--flag VALUE`, `-f VALUE`) will not be consumed, and they will
-    /// return `None`.
-    ///
-    /// The reasoning for this is that if implicit values were consumed
-    /// by this method, a subsequent flag could be mistaken as the
-    /// value. `--opt=` also needs to be distinct from `--opt` - the
-    /// former needs to have an empty value, whereas the latter needs to
-    /// have no value.
-    ///
-    /// Short options do not support empty values.
-    ///
-    /// # Panics
+    /// Returns the argument at [`Options::arg_index`], exactly as it
+    /// was given, before it was split into options and values. This is
+    /// useful for error messages about options in short option clusters
+    /// or with `=` values.
     ///
-    /// This method panics if [`Options::next_opt`] or
-    /// [`Options::next_arg`] have not yet been called, if `value_opt`
-    /// is called twice for the same option, or if the last call to
-    /// `next_*` did not return an option.
+    /// Returns `None` if no arguments have been parsed yet. Arguments
+    /// are only kept after calling [`Options::set_track_current_arg`],
+    /// since that costs a copy of every argument, which is not free
+    /// even for `&str`.
     ///
     /// # Example
     ///
     /// ```
     /// # use getargs::{Opt, Options};
     /// #
-    /// let args = ["--opt=value", "--other-flag", "--opt", "--opt=other", "-o", "-ovalue"];
+    /// let args = ["-xvzf", "archive.tar.gz"];
     /// let mut opts = Options::new(args.into_iter());
+    /// opts.set_track_current_arg(true);
     ///
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("opt"))));
-    /// assert_eq!(opts.value_opt(), Some("value"));
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("other-flag"))));
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("opt"))));
-    /// assert_eq!(opts.value_opt(), None); // does not return "--opt=other"
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("opt"))));
-    /// assert_eq!(opts.value_opt(), Some("other"));
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
-    /// assert_eq!(opts.value_opt(), None); // short options can't have empty values
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
-    /// assert_eq!(opts.value_opt(), Some("value"));
-    /// assert_eq!(opts.next_opt(), Ok(None));
+    /// assert_eq!(opts.current_arg(), None);
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('x'))));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.current_arg(), Some("-xvzf"));
     /// ```
-    pub fn value_opt(&'_ mut self) -> Option<A> {
-        match self.state {
-            State::Start { .. } | State::Positional(_) | State::End { .. } => {
-                panic!("called Options::value_opt() with no previous option")
-            }
+    pub fn current_arg(&self) -> Option<A> {
+        self.current.clone()
+    }
 
-            // If the option had no explicit `=value`, return None
-            State::EndOfOption(_) => None,
+    /// Returns `true` if `arg` would be parsed as an option or ends
+    /// options, so that [`Options::values`] should stop before it.
+    pub(crate) fn looks_like_opt(&self, arg: &A) -> bool {
+        !matches!(self.classify(arg, false), Kind::Positional | Kind::LoneDash)
+    }
 
-            State::ShortOptionCluster(_, val) | State::LongOptionWithValue(_, val) => {
-                self.state = State::Start { ended_opts: false };
-                Some(val)
-            }
-        }
+    /// Returns a reference to the argument at [`Options::arg_index`],
+    /// if it is being kept. See [`Options::current_arg`].
+    pub(crate) fn current(&self) -> Option<&A> {
+        self.current.as_ref()
     }
 
-    /// Retrieves the next positional argument. This method must be
-    /// called after all the last option has been fully parsed. Usually,
-    /// this is when [`Options::next_opt`] returns `Ok(None)`, but it
-    /// can also be after calling [`Options::value_opt`].
+    /// Enables or disables keeping the argument returned by
+    /// [`Options::current_arg`], starting with the next argument.
     ///
-    /// After this method is called, you can parse further options with
-    /// [`Options::next_opt`] or continue getting positional arguments
-    /// (which will not treat options specially).
-    ///
-    /// This method preserves the state of [`Options::opts_ended`].
-    ///
-    /// # Panics
-    ///
-    /// This method panics if the option parsing is not yet complete;
-    /// that is, it can panic if [`Options::next_opt`] has not yet
-    /// returned `None` at least once. If [`Options::next_opt`] returns
-    /// `None`, then [`Options::next_positional`] will always be safe to
-    /// call.
-    ///
-    /// # Examples
+    /// This is disabled by default, so that parsing doesn't pay for a
+    /// copy of every argument unless it is needed.
     ///
-    /// Basic usage:
+    /// # Example
     ///
     /// ```
+    /// # #[cfg(feature = "alloc")] {
     /// # use getargs::{Opt, Options};
     /// #
-    /// let args = ["-a", "foo", "bar"];
+    /// let args = vec![String::from("-xv"), String::from("-z")];
     /// let mut opts = Options::new(args.into_iter());
     ///
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
-    /// assert_eq!(opts.next_opt(), Ok(None));
-    /// assert_eq!(opts.next_positional(), Some("foo"));
-    /// assert_eq!(opts.next_positional(), Some("bar"));
-    /// assert_eq!(opts.next_positional(), None);
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('x'))));
+    /// assert_eq!(opts.current_arg(), None);
+    ///
+    /// opts.set_track_current_arg(true);
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.current_arg(), None);
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('z'))));
+    /// assert_eq!(opts.current_arg(), Some(String::from("-z")));
+    /// # }
     /// ```
-    pub fn next_positional(&'_ mut self) -> Option<A> {
-        match self.state {
-            State::Start { ended_opts } => self.iter.next().or_else(|| {
-                self.state = State::End { ended_opts };
-                None
-            }),
-
-            State::Positional(arg) => {
-                self.state = State::Start { ended_opts: false };
-                Some(arg)
-            }
-
-            State::End { .. } => None,
+    pub fn set_track_current_arg(&mut self, track: bool) {
+        self.track_current = track;
 
-            _ => {
-                panic!("called Options::next_positional() while option parsing hasn't finished")
-            }
+        if !track {
+            self.current = None;
         }
     }
 
-    /// Returns an iterator over the positional arguments of this
-    /// [`Options`]. The returned iterator will forward
-    /// [`Iterator::next`] calls to [`Options::next_positional`].
+    /// Makes [`Options::next_opt`] and [`Options::next_arg`] return
+    /// [`Error::HelpRequested`] for every option for which `is_help`
+    /// returns `true`, like `-h` and `--help` with
+    /// [`Opt::is_help`][Opt#method.is_help].
     ///
-    /// This method does not panic, but [`Iterator::next`] may panic
-    /// once it is called if option parsing has not finished
-    /// ([`Options::next_opt`] has not returned `Ok(None)`). The exact
-    /// requirements are the same as [`Options::next_positional`].
+    /// This reports help while parsing, so unlike
+    /// [`Options::help_requested`] it works with any iterator. Help is
+    /// only reported once parsing reaches it, though, so errors in
+    /// earlier arguments are returned first.
     ///
     /// # Example
     ///
     /// ```
-    /// # use getargs::{Opt, Options};
+    /// # use getargs::{Error, Opt, Options};
     /// #
-    /// let args = ["--flag", "one", "two"];
+    /// let args = ["-v", "--help", "-x"];
     /// let mut opts = Options::new(args.into_iter());
+    /// opts.set_help_opt(|opt| opt.is_help());
     ///
-    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("flag"))));
-    /// assert_eq!(opts.next_opt(), Ok(None));
-    ///
-    /// let mut args = opts.positionals();
-    ///
-    /// assert_eq!(args.next(), Some("one"));
-    /// assert_eq!(args.next(), Some("two"));
-    /// assert_eq!(args.next(), None);
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.next_opt(), Err(Error::HelpRequested(Opt::Long("help"))));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('x'))));
     /// ```
-    pub fn positionals(&mut self) -> Positionals<A, I> {
-        Positionals::new(self)
+    pub fn set_help_opt(&mut self, is_help: fn(&Opt<A>) -> bool) {
+        self.help = Some(is_help);
     }
 
-    /// Consumes this [`Options`], returning an iterator over the rest
-    /// of the arguments. The returned iterator wraps the one originally
-    /// passed to [`Options::new`].
-    ///
-    /// # Panics
+    /// Returns the [`ParseConfig`] this [`Options`] was created with.
+    pub fn config(&self) -> ParseConfig {
+        self.config
+    }
+
+    /// Retrieves the next option.
+    ///
+    /// Returns `Ok(None)` if there are no more options, or `Err(..)` if
+    /// a parse error occurs.
+    ///
+    /// This method also does not retrieve any value that goes with an
+    /// option. If the option requires an value, such as in
+    /// `--option=value`, then you should call [`Options::value`] after
+    /// receiving the option from this method. Alternatively, you can
+    /// call [`Options::value_opt`] instead for optional values.
+    ///
+    /// Once this method returns `Ok(None)`, then you should make sure
+    /// to use [`Options::next_positional`] or [`Options::positionals`]
+    /// to get the values of the following positional arguments. See the
+    /// [`Options`] documentation for more details.
+    ///
+    /// If your application accepts positional arguments in between
+    /// flags, you can use [`Options::next_arg`] instead of `next_opt`.
+    pub fn next_opt(&'_ mut self) -> Result<A, Option<Opt<A>>> {
+        let opt = self.parse_opt()?;
+
+        match (&opt, self.help) {
+            (Some(opt), Some(is_help)) if is_help(opt) => Err(Error::HelpRequested(opt.clone())),
+            _ => Ok(opt),
+        }
+    }
+
+    /// Parses the next option for [`Options::next_opt`], before
+    /// checking whether it asks for help.
+    #[inline(always)]
+    fn parse_opt(&mut self) -> Result<A, Option<Opt<A>>> {
+        match self.take_state() {
+            state @ (State::Start { .. } | State::Begin) if self.raw => {
+                self.state = state;
+                Ok(None)
+            }
+
+            State::EndOfOption(_) if self.raw => Ok(None),
+
+            state @ (State::Start { .. } | State::Begin | State::EndOfOption(_)) => {
+                let next = self.next_raw();
+
+                if next.is_none() {
+                    self.state = State::End { ended_opts: false };
+                    return Ok(None);
+                }
+
+                let arg = next.unwrap();
+
+                if self.fast {
+                    return Ok(self.next_opt_fast(arg));
+                }
+
+                self.next_opt_classified(arg, matches!(state, State::Begin))
+            }
+
+            State::ShortOptionCluster(opt, rest) => {
+                let plus = matches!(opt, Opt::ShortPlus(_));
+                Ok(Some(self.consume_short_opt(rest, plus)))
+            }
+
+            State::LongOptionWithValue(opt, _) => Err(Error::DoesNotRequireValue(opt)),
+
+            state @ (State::Positional(_) | State::End { .. }) => {
+                self.state = state;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Retrieves the next option like [`Options::next_opt`], and checks
+    /// it against a table of [`OptSpec`]s. If the option takes a value,
+    /// it is consumed with [`Options::value`] and returned alongside
+    /// the option, so there is no need to call `value` yourself. An
+    /// option with an [arity][OptSpec#structfield.arity] of more than 1
+    /// also consumes the arguments after its first value, like
+    /// [`Options::value_group`].
+    ///
+    /// Returns [`Error::UnknownOption`] if the option does not match
+    /// any of `specs`, [`Error::RequiresValues`] if there are too few
+    /// arguments left for an option with an arity of more than 1, and
+    /// the same errors as [`Options::next_opt`] and [`Options::value`]
+    /// otherwise. Like [`Options::next_opt`], returns `Ok(None)` once
+    /// there are no more options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Error, Opt, OptSpec, Options};
+    /// #
+    /// const SPECS: &[OptSpec] = &[
+    ///     OptSpec::new().short('v').long("verbose"),
+    ///     OptSpec::new().short('o').long("output").takes_value(true),
+    /// ];
+    ///
+    /// let args = ["-vo", "out.txt", "--verbose", "-x"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// let (opt, values) = opts.next_spec(SPECS)?.unwrap();
+    /// assert_eq!((opt, values.first()), (Opt::Short('v'), None));
+    ///
+    /// let (opt, values) = opts.next_spec(SPECS)?.unwrap();
+    /// assert_eq!((opt, values.first()), (Opt::Short('o'), Some(&"out.txt")));
+    ///
+    /// let (opt, values) = opts.next_spec(SPECS)?.unwrap();
+    /// assert_eq!((opt, values.first()), (Opt::Long("verbose"), None));
+    ///
+    /// assert_eq!(opts.next_spec(SPECS), Err(Error::UnknownOption(Opt::Short('x'))));
+    /// assert_eq!(opts.next_spec(SPECS), Ok(None));
+    /// # Ok::<(), Error<&'static str>>(())
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn next_spec<L: Copy>(
+        &mut self,
+        specs: &[OptSpec<A::ShortOpt, L>],
+    ) -> Result<A, Option<(Opt<A>, SpecValues<A>)>>
+    where
+        A: PartialEq<L>,
+    {
+        let opt = match self.next_opt()? {
+            Some(opt) => opt,
+            None => return Ok(None),
+        };
+
+        match specs.iter().find(|spec| spec.matches(&opt)) {
+            Some(spec) => {
+                let values = self.spec_values(&opt, spec)?;
+                Ok(Some((opt, values)))
+            }
+            None => Err(Error::UnknownOption(opt)),
+        }
+    }
+
+    /// Parses `arg` like [`Options::next_opt`] after classifying it
+    /// with [`Options::classify`], which is how every
+    /// [`ParseConfig`] other than the default is handled. `begin` is
+    /// whether the parser was in [`State::Begin`].
+    #[inline(never)]
+    fn next_opt_classified(&mut self, arg: A, begin: bool) -> Result<A, Option<Opt<A>>> {
+        match self.classify(&arg, begin) {
+            Kind::EndOfOpts => {
+                self.state = State::Start { ended_opts: true };
+                Ok(None)
+            }
+            Kind::Positional => Ok(self.positional(arg)),
+            Kind::Long => {
+                let (name, value) = Self::parsed(self.parse_long(arg));
+                self.long_opt(name, value).map(Some)
+            }
+            Kind::Numeric => {
+                let digits = Self::parsed(self.parse_short(arg).and_then(A::parse_numeric));
+                let opt = Opt::Numeric(digits);
+                self.state = State::EndOfOption(opt.clone());
+                Ok(Some(opt))
+            }
+            Kind::SingleDashLong => {
+                let (name, value) = Self::parsed(self.parse_short(arg)).split_on(b'=');
+                self.long_opt(name, value).map(Some)
+            }
+            Kind::Short => {
+                let cluster = Self::parsed(self.parse_short(arg));
+                Ok(Some(self.consume_short_opt(cluster, false)))
+            }
+            Kind::Slash => {
+                let (name, value) = Self::parsed(self.parse_slash_opt(arg));
+                self.long_opt(name, value).map(Some)
+            }
+            Kind::Plus => {
+                let cluster = Self::parsed(self.parse_plus_cluster(arg));
+                Ok(Some(self.consume_short_opt(cluster, true)))
+            }
+            Kind::LoneDash => {
+                self.state = State::Start { ended_opts: false };
+                Err(Error::LoneDash)
+            }
+            Kind::Bundled => {
+                let cluster = Self::parsed(arg.parse_bundled_cluster());
+                Ok(Some(self.consume_short_opt(cluster, false)))
+            }
+        }
+    }
+
+    /// Parses `arg` like [`Options::next_opt`] does with
+    /// [`ParseConfig::default`], trying each kind of argument on the
+    /// argument itself. This avoids classifying `arg` before parsing
+    /// it, which would take it apart twice.
+    #[inline(always)]
+    fn next_opt_fast(&mut self, arg: A) -> Option<Opt<A>> {
+        if arg.clone().ends_opts() {
+            self.state = State::Start { ended_opts: true };
+            None
+        } else if let Some((name, value)) = arg.clone().parse_long_opt() {
+            let opt = Opt::Long(name);
+
+            self.state = match value {
+                Some(value) => State::LongOptionWithValue(opt.clone(), value),
+                None => State::EndOfOption(opt.clone()),
+            };
+
+            Some(opt)
+        } else if let Some(cluster) = arg.clone().parse_short_cluster() {
+            Some(self.consume_short_opt(cluster, false))
+        } else {
+            self.state = State::Positional(arg);
+            None
+        }
+    }
+
+    /// Takes the current state, leaving [`State::Start`] in its place.
+    fn take_state(&mut self) -> State<A> {
+        core::mem::replace(&mut self.state, State::Start { ended_opts: false })
+    }
+
+    /// Returns the rest of the short option cluster that is currently
+    /// being parsed, if any.
+    pub(crate) fn cluster_rest(&self) -> Option<&A> {
+        match &self.state {
+            State::ShortOptionCluster(_, rest) => Some(rest),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying iterator, for wrappers that need to
+    /// inspect it between calls.
+    pub(crate) fn iter_mut(&mut self) -> &mut I {
+        &mut self.iter
+    }
+
+    /// Returns the next argument, taking peeked arguments first.
+    fn next_raw(&mut self) -> Option<A> {
+        let arg = match self.peeked[0] {
+            None => self.iter.next(),
+            Some(_) => self.next_peeked(),
+        };
+
+        if arg.is_some() {
+            self.index += 1;
+
+            if self.track_current {
+                self.current = arg.clone();
+            }
+        }
+
+        arg
+    }
+
+    /// Takes the first argument that was peeked, for
+    /// [`Options::next_raw`].
+    #[cold]
+    fn next_peeked(&mut self) -> Option<A> {
+        let arg = self.peeked[0].take();
+        self.peeked[0] = self.peeked[1].take();
+        arg
+    }
+
+    /// Takes a value for the option last returned, for
+    /// [`Options::values_while`]. A value attached to the option is
+    /// always taken, and the following argument is only taken if
+    /// `accept` returns `true` for it.
+    pub(crate) fn next_value_if(&mut self, accept: impl FnOnce(&Self, &A) -> bool) -> Option<A> {
+        match self.state {
+            State::ShortOptionCluster(..) | State::LongOptionWithValue(..) => {
+                return self.value_opt()
+            }
+            State::EndOfOption(_) | State::Start { ended_opts: false } => {}
+            _ => return None,
+        }
+
+        if self.peeked[0].is_none() {
+            self.peeked[0] = self.iter.next();
+        }
+
+        if !accept(self, self.peeked[0].as_ref()?) {
+            return None;
+        }
+
+        self.state = State::Start { ended_opts: false };
+        self.next_raw()
+    }
+
+    /// Calls `next` and then restores the state of this [`Options`],
+    /// so that `next` appears not to have consumed anything. The
+    /// arguments it will read from the iterator are buffered first.
+    fn peek<T>(&mut self, next: impl FnOnce(&mut Self) -> T) -> T {
+        if let State::Start { .. } | State::Begin | State::EndOfOption(_) = self.state {
+            if self.peeked[0].is_none() {
+                self.peeked[0] = self.iter.next();
+            }
+
+            // `--` is consumed along with the argument after it by
+            // `next_arg`.
+            let ends_opts = self.peeked[0]
+                .as_ref()
+                .is_some_and(|arg| match arg.byte_view() {
+                    Some(bytes) => self.ends_opts(bytes),
+                    None => self.ends_opts(arg.clone()),
+                });
+
+            if ends_opts && !self.raw && self.peeked[1].is_none() {
+                self.peeked[1] = self.iter.next();
+            }
+        }
+
+        let state = self.state.clone();
+        let peeked = self.peeked.clone();
+        let index = self.index;
+        let current = self.current.clone();
+        let result = next(self);
+        self.state = state;
+        self.peeked = peeked;
+        self.index = index;
+        self.current = current;
+        result
+    }
+
+    /// Returns bounds on the number of arguments left for
+    /// [`Options::next_positional`] to return, like
+    /// [`Iterator::size_hint`].
+    pub(crate) fn positionals_size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let extra = self.peeked.iter().flatten().count()
+            + matches!(self.state, State::Positional(_)) as usize;
+
+        match self.state {
+            State::End { .. } => (0, Some(0)),
+            _ => (
+                lower.saturating_add(extra),
+                upper.and_then(|upper| upper.checked_add(extra)),
+            ),
+        }
+    }
+
+    /// Works out how [`Options::next_opt`] should parse `arg`, looking
+    /// at its bytes if [`Argument::byte_view`] offers them, so that the
+    /// argument only has to be taken apart once. `begin` is whether
+    /// `arg` is the first argument and may be a bundled cluster.
+    pub(crate) fn classify(&self, arg: &A, begin: bool) -> Kind {
+        match arg.byte_view() {
+            Some(bytes) => self.kind(bytes, begin),
+            None => self.kind(arg.clone(), begin),
+        }
+    }
+
+    /// Returns the kind of `arg`, for [`Options::classify`]. This tries
+    /// each kind of option in turn, only cloning `arg` for those that
+    /// are enabled.
+    fn kind<B: Argument>(&self, arg: B, begin: bool) -> Kind {
+        if self.ends_opts(arg.clone()) {
+            Kind::EndOfOpts
+        } else if (self.config.terminator != Terminator::DoubleDash && arg.clone().ends_opts())
+            || self.is_negative_number(&arg)
+        {
+            // `--` is a positional argument if it doesn't end options.
+            Kind::Positional
+        } else if self.parse_long(arg.clone()).is_some() {
+            Kind::Long
+        } else if let Some(cluster) = self.parse_short(arg.clone()) {
+            self.cluster_kind(cluster)
+        } else if self.config.slash_opts && self.parse_slash_opt(arg.clone()).is_some() {
+            Kind::Slash
+        } else if self.config.plus_opts && self.parse_plus_cluster(arg.clone()).is_some() {
+            Kind::Plus
+        } else if self.config.reject_lone_dash && Self::is_lone_dash(arg.clone()) {
+            Kind::LoneDash
+        } else if begin && arg.parse_bundled_cluster().is_some() {
+            Kind::Bundled
+        } else {
+            Kind::Positional
+        }
+    }
+
+    /// Returns the kind of a short option cluster, which can also be a
+    /// numeric option or a long option with a single dash.
+    fn cluster_kind<B: Argument>(&self, cluster: B) -> Kind {
+        if self.config.numeric_opts && cluster.clone().parse_numeric().is_some() {
+            Kind::Numeric
+        } else if self.config.single_dash_long || self.parse_single_dash_name(cluster).is_some() {
+            Kind::SingleDashLong
+        } else {
+            Kind::Short
+        }
+    }
+
+    /// Unwraps the result of parsing an argument as the kind that
+    /// [`Options::classify`] found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument did not parse the same way as its
+    /// [`Argument::byte_view`].
+    fn parsed<T>(parsed: Option<T>) -> T {
+        parsed.expect("argument parsed differently from its byte view")
+    }
+
+    /// Parses `arg` as `/flag` or `/flag:value` if
+    /// [`ParseConfig::slash_opts`] is enabled. Arguments with more than
+    /// one slash before the value, like paths, are not options.
+    fn parse_slash_opt<B: Argument>(&self, arg: B) -> Option<(B, Option<B>)> {
+        if !self.config.slash_opts {
+            return None;
+        }
+
+        let (name, value) = arg.strip_ascii_prefix(b'/')?.split_on(b':');
+
+        if name.clone().split_on(b'/').1.is_some() {
+            return None;
+        }
+
+        Some((name, value))
+    }
+
+    /// Parses `arg` as a long option, using
+    /// [`ParseConfig::opt_prefix`] if it is not `-`.
+    fn parse_long<B: Argument>(&self, arg: B) -> Option<(B, Option<B>)> {
+        let prefix = self.config.opt_prefix;
+
+        if prefix == b'-' {
+            return arg.parse_long_opt();
+        }
+
+        let name = arg.strip_ascii_prefix(prefix)?.strip_ascii_prefix(prefix)?;
+        Some(name.split_on(b'='))
+    }
+
+    /// Parses `arg` as a short option cluster, using
+    /// [`ParseConfig::opt_prefix`] if it is not `-`.
+    fn parse_short<B: Argument>(&self, arg: B) -> Option<B> {
+        let prefix = self.config.opt_prefix;
+
+        if prefix == b'-' {
+            return arg.parse_short_cluster();
+        }
+
+        arg.strip_ascii_prefix(prefix)
+    }
+
+    /// Returns `true` if `arg` is a negative number like `-5` or
+    /// `-12.5` and [`ParseConfig::negative_numbers`] is enabled.
+    fn is_negative_number<B: Argument>(&self, arg: &B) -> bool {
+        if !self.config.negative_numbers {
+            return false;
+        }
+
+        let number = match self.parse_short(arg.clone()) {
+            Some(number) => number,
+            None => return false,
+        };
+
+        let (integer, fraction) = number.split_on(b'.');
+
+        integer.parse_numeric().is_some()
+            && fraction.map_or(true, |fraction| fraction.parse_numeric().is_some())
+    }
+
+    /// Parses `arg` as a cluster of `+` options if
+    /// [`ParseConfig::plus_opts`] is enabled.
+    fn parse_plus_cluster<B: Argument>(&self, arg: B) -> Option<B> {
+        if !self.config.plus_opts {
+            return None;
+        }
+
+        arg.strip_ascii_prefix(b'+')
+    }
+
+    /// Returns `true` if `arg` is a lone `-`.
+    fn is_lone_dash<B: Argument>(arg: B) -> bool {
+        Self::is_ascii_char(arg, b'-')
+    }
+
+    /// Returns `true` if `arg` is the ASCII character `ch` and nothing
+    /// else.
+    fn is_ascii_char<B: Argument>(arg: B, ch: u8) -> bool {
+        match arg.split_on(ch) {
+            (before, Some(after)) => before.is_empty_value() && after.is_empty_value(),
+            (_, None) => false,
+        }
+    }
+
+    /// Returns `true` if `arg` is the ASCII string `s` and nothing
+    /// else.
+    fn is_ascii_str<B: Argument>(arg: B, s: &str) -> bool {
+        let (&last, init) = match s.as_bytes().split_last() {
+            Some(split) if s.is_ascii() => split,
+            _ => return false,
+        };
+
+        let mut rest = arg;
+
+        for &byte in init {
+            rest = match rest.strip_ascii_prefix(byte) {
+                Some(rest) => rest,
+                None => return false,
+            };
+        }
+
+        Self::is_ascii_char(rest, last)
+    }
+
+    /// Parses `cluster` as a long option with a single dash if its name
+    /// is one of [`ParseConfig::single_dash_names`].
+    fn parse_single_dash_name<B: Argument>(&self, cluster: B) -> Option<(B, Option<B>)> {
+        let names = self.config.single_dash_names;
+
+        if names.is_empty() {
+            return None;
+        }
+
+        let (name, value) = cluster.split_on(b'=');
+
+        names
+            .iter()
+            .any(|&known| Self::is_ascii_str(name.clone(), known))
+            .then_some((name, value))
+    }
+
+    /// Returns `true` if `arg` ends options according to
+    /// [`ParseConfig::terminator`].
+    fn ends_opts<B: Argument>(&self, arg: B) -> bool {
+        match self.config.terminator {
+            Terminator::DoubleDash => arg.ends_opts(),
+            Terminator::Char(ch) => Self::is_ascii_char(arg, ch),
+            Terminator::Disabled => false,
+        }
+    }
+
+    /// Makes `arg` the current positional argument. If
+    /// [`ParseConfig::stop_at_positional`] is enabled, option parsing
+    /// stops here.
+    fn positional(&mut self, arg: A) -> Option<Opt<A>> {
+        self.state = State::Positional(arg);
+        self.raw |= self.config.stop_at_positional;
+        None
+    }
+
+    /// Removes the `=` from a value attached to a short option, like
+    /// `-o=file`, if [`ParseConfig::short_equals`] is enabled.
+    fn short_value(&self, value: A) -> A {
+        if self.config.short_equals {
+            if let (before, Some(after)) = value.clone().split_on(b'=') {
+                if before.is_empty_value() {
+                    return after;
+                }
+            }
+        }
+
+        value
+    }
+
+    /// Returns the long option `name` and updates the state to match,
+    /// keeping `value` if the option had one attached. Returns
+    /// [`Error::EmptyValue`] instead if `value` is empty and
+    /// [`ParseConfig::reject_empty_values`] is enabled.
+    fn long_opt(&mut self, name: A, value: Option<A>) -> Result<A, Opt<A>> {
+        let opt = Opt::Long(name);
+
+        match value {
+            Some(value) if self.config.reject_empty_values && value.is_empty_value() => {
+                self.state = State::Start { ended_opts: false };
+                return Err(Error::EmptyValue(opt));
+            }
+            Some(value) => self.state = State::LongOptionWithValue(opt.clone(), value),
+            None => self.state = State::EndOfOption(opt.clone()),
+        }
+
+        Ok(opt)
+    }
+
+    /// Consumes one short option from `cluster` and updates the state
+    /// to match. If `plus` is `true`, the option is returned as
+    /// [`Opt::ShortPlus`].
+    #[inline]
+    fn consume_short_opt(&mut self, cluster: A, plus: bool) -> Opt<A> {
+        let (opt, rest) = cluster.consume_short_opt();
+        let opt = if plus {
+            Opt::ShortPlus(opt)
+        } else {
+            Opt::Short(opt)
+        };
+
+        if let Some(rest) = rest {
+            self.state = State::ShortOptionCluster(opt.clone(), rest);
+        } else {
+            self.state = State::EndOfOption(opt.clone());
+        }
+
+        opt
+    }
+
+    /// Retrieves the next *argument*. An *argument* is represented by
+    /// [`Arg`] and defined as either an option ([`Opt`]) or a
+    /// positional argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Options::next_opt`] does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Arg, Opt, Options};
+    /// #
+    /// let args = ["--option", "VALUE", "arg", "arg2", "-vvv", "--option", "VALUE", "--", "arg3", "arg4", "--not-an-option"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// // Regular options and values work...
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("option"))));
+    /// assert_eq!(opts.value(), Ok("VALUE"));
+    ///
+    /// // Positional arguments afterwards also work...
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("arg"))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("arg2"))));
+    ///
+    /// // Options after positional arguments also work
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('v'))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('v'))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('v'))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("option"))));
+    /// assert_eq!(opts.value(), Ok("VALUE"));
+    ///
+    /// // `--` is recognized and handled correctly
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("arg3"))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("arg4"))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("--not-an-option"))));
+    /// assert_eq!(opts.next_arg(), Ok(None));
+    /// #
+    /// # assert_eq!(opts.is_empty(), true);
+    /// ```
+    pub fn next_arg(&'_ mut self) -> Result<A, Option<Arg<A>>> {
+        if !self.opts_ended() {
+            if let Some(opt) = self.next_opt()? {
+                return Ok(Some(opt.into()));
+            }
+        }
+
+        Ok(self.next_positional().map(Arg::Positional))
+    }
+
+    /// Returns what [`Options::next_opt`] would return, without
+    /// consuming it. Any number of calls to `peek_opt` can be made
+    /// before the next call to [`Options::next_opt`], which will then
+    /// return the same result.
+    ///
+    /// Arguments that have to be read from the iterator to find the
+    /// next option are buffered inside this [`Options`], so the
+    /// iterator does not need to be [`Clone`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["--level", "-v"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("level"))));
+    ///
+    /// // `--level` takes an optional numeric value, which is not given
+    /// assert_eq!(opts.peek_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.peek_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.peek_opt(), Ok(None));
+    /// ```
+    pub fn peek_opt(&'_ mut self) -> Result<A, Option<Opt<A>>> {
+        self.peek(Self::next_opt)
+    }
+
+    /// Returns what [`Options::next_arg`] would return, without
+    /// consuming it. See [`Options::peek_opt`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Arg, Options};
+    /// #
+    /// let args = ["--depth", "3", "file"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("depth"))));
+    ///
+    /// let depth = match opts.peek_arg()? {
+    ///     Some(Arg::Positional(value)) if value.parse::<u32>().is_ok() => opts.value()?,
+    ///     _ => "1",
+    /// };
+    ///
+    /// assert_eq!(depth, "3");
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("file"))));
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    pub fn peek_arg(&'_ mut self) -> Result<A, Option<Arg<A>>> {
+        self.peek(Self::next_arg)
+    }
+
+    /// Saves the current position of this [`Options`], so that it can
+    /// be restored with [`Options::restore`]. This allows speculative
+    /// parsing: if a guess about how to parse the following arguments
+    /// turns out to be wrong, parsing can go back and try again.
+    ///
+    /// This requires the iterator to be [`Clone`], since the saved
+    /// position includes a clone of it. Iterators over slices and
+    /// arrays are cheap to clone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["-x", "1", "2", "file"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('x'))));
+    ///
+    /// // Try to parse `-x` as taking three numbers
+    /// let checkpoint = opts.checkpoint();
+    /// let values = opts.value_group::<3>();
+    ///
+    /// assert!(values.unwrap()[2].parse::<i32>().is_err());
+    ///
+    /// // Not three numbers, so go back and take one value instead
+    /// opts.restore(checkpoint);
+    ///
+    /// assert_eq!(opts.value(), Ok("1"));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    /// assert_eq!(opts.next_positional(), Some("2"));
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint<A, I>
+    where
+        I: Clone,
+    {
+        Checkpoint { opts: self.clone() }
+    }
+
+    /// Restores a position saved by [`Options::checkpoint`]. Everything
+    /// parsed since then will be parsed again.
+    pub fn restore(&mut self, checkpoint: Checkpoint<A, I>) {
+        *self = checkpoint.opts;
+    }
+
+    /// Retrieves the value passed to the option last returned by
+    /// [`Options::next_opt`] or [`Options::next_arg`].
+    ///
+    /// This function returns an error if there is no value to return
+    /// because the end of the argument list has been reached.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if [`Options::next_opt`] or
+    /// [`Options::next_arg`] have not yet been called, if `value` is
+    /// called twice for the same option, or if the last call to
+    /// `next_*` did not return an option.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["-aay", "--bee=foo", "-c", "see", "bar"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    /// assert_eq!(opts.value(), Ok("ay"));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("bee"))));
+    /// assert_eq!(opts.value(), Ok("foo"));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('c'))));
+    /// assert_eq!(opts.value(), Ok("see"));
+    /// ```
+    pub fn value(&'_ mut self) -> Result<A, A> {
+        match self.take_state() {
+            State::Start { .. } | State::Begin | State::Positional(_) | State::End { .. } => {
+                panic!("called Options::value() with no previous option")
+            }
+
+            State::EndOfOption(opt) => {
+                if let Some(val) = self.next_raw() {
+                    Ok(val)
+                } else {
+                    self.state = State::End { ended_opts: false };
+                    Err(Error::RequiresValue(opt))
+                }
+            }
+
+            State::ShortOptionCluster(_, val) => Ok(self.short_value(val).consume_short_val()),
+
+            State::LongOptionWithValue(_, val) => Ok(val),
+        }
+    }
+
+    /// Retrieves an *optional* value for the option last returned by
+    /// [`Options::next_opt`] or [`Options::next_arg`]. Only explicit
+    /// values are accepted (`--flag=VALUE`, `-fVALUE`). Implicit values
+    /// (`--flag VALUE`, `-f VALUE`) will not be consumed, and they will
+    /// return `None`.
+    ///
+    /// The reasoning for this is that if implicit values were consumed
+    /// by this method, a subsequent flag could be mistaken as the
+    /// value. `--opt=` also needs to be distinct from `--opt` - the
+    /// former needs to have an empty value, whereas the latter needs to
+    /// have no value.
+    ///
+    /// Short options do not support empty values.
+    ///
+    /// This makes `value_opt` suitable for options whose value is
+    /// optional, like `--color[=WHEN]`: the value is only ever taken
+    /// from the option itself, so the argument after `--color` is left
+    /// alone to be parsed as an option or positional argument.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if [`Options::next_opt`] or
+    /// [`Options::next_arg`] have not yet been called, if `value_opt`
+    /// is called twice for the same option, or if the last call to
+    /// `next_*` did not return an option.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["--opt=value", "--other-flag", "--opt", "--opt=other", "-o", "-ovalue"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("opt"))));
+    /// assert_eq!(opts.value_opt(), Some("value"));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("other-flag"))));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("opt"))));
+    /// assert_eq!(opts.value_opt(), None); // does not return "--opt=other"
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("opt"))));
+    /// assert_eq!(opts.value_opt(), Some("other"));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    /// assert_eq!(opts.value_opt(), None); // short options can't have empty values
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    /// assert_eq!(opts.value_opt(), Some("value"));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    /// ```
+    pub fn value_opt(&'_ mut self) -> Option<A> {
+        match self.take_state() {
+            State::Start { .. } | State::Begin | State::Positional(_) | State::End { .. } => {
+                panic!("called Options::value_opt() with no previous option")
+            }
+
+            // If the option had no explicit `=value`, return None
+            state @ State::EndOfOption(_) => {
+                self.state = state;
+                None
+            }
+
+            State::ShortOptionCluster(_, val) => Some(self.short_value(val)),
+            State::LongOptionWithValue(_, val) => Some(val),
+        }
+    }
+
+    /// Retrieves an optional value for the option last returned by
+    /// [`Options::next_opt`] or [`Options::next_arg`] like
+    /// [`Options::value_opt`], returning `default` if the option has no
+    /// explicit value. The following argument is never consumed.
+    ///
+    /// This is a shorthand for options like `--color[=WHEN]`, which
+    /// have a default value when used on their own.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value_opt`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["--color", "--color=never", "file"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("color"))));
+    /// assert_eq!(opts.value_or("always"), "always");
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("color"))));
+    /// assert_eq!(opts.value_or("always"), "never");
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    /// assert_eq!(opts.next_positional(), Some("file"));
+    /// ```
+    pub fn value_or(&'_ mut self, default: A) -> A {
+        self.value_opt().unwrap_or(default)
+    }
+
+    /// Like [`Options::value_or`], but computes the default with
+    /// `default` only if the option has no explicit value.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value_opt`].
+    pub fn value_or_else(&'_ mut self, default: impl FnOnce() -> A) -> A {
+        self.value_opt().unwrap_or_else(default)
+    }
+
+    /// Like [`Options::value`], but also returns how the value was
+    /// supplied: attached with `=`, as the rest of a short option
+    /// cluster, or as the following argument.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options, ValueSource};
+    /// #
+    /// let args = ["--file=a", "-fb", "--file", "c"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("file"))));
+    /// assert_eq!(opts.value_with_source(), Ok(("a", ValueSource::Inline)));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('f'))));
+    /// assert_eq!(opts.value_with_source(), Ok(("b", ValueSource::Attached)));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("file"))));
+    /// assert_eq!(opts.value_with_source(), Ok(("c", ValueSource::Next)));
+    /// ```
+    pub fn value_with_source(&'_ mut self) -> Result<A, (A, ValueSource)> {
+        let source = match self.state {
+            State::EndOfOption(_) => ValueSource::Next,
+            State::ShortOptionCluster(..) => ValueSource::Attached,
+            State::LongOptionWithValue(..) => ValueSource::Inline,
+            _ => panic!("called Options::value_with_source() with no previous option"),
+        };
+
+        self.value().map(|value| (value, source))
+    }
+
+    /// Like [`Options::value_opt`], but also returns how the value was
+    /// supplied. Since only explicit values are accepted, the source is
+    /// never [`ValueSource::Next`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value_opt`].
+    pub fn value_opt_with_source(&'_ mut self) -> Option<(A, ValueSource)> {
+        let source = match self.state {
+            State::EndOfOption(_) => ValueSource::Next,
+            State::ShortOptionCluster(..) => ValueSource::Attached,
+            State::LongOptionWithValue(..) => ValueSource::Inline,
+            _ => panic!("called Options::value_opt_with_source() with no previous option"),
+        };
+
+        self.value_opt().map(|value| (value, source))
+    }
+
+    /// Retrieves a fixed-size group of `N` values for the option last
+    /// returned by [`Options::next_opt`] or [`Options::next_arg`], for
+    /// options like `--point X Y Z` that take more than one value.
+    ///
+    /// The first value is retrieved like [`Options::value`], so it may
+    /// be attached to the option (`--point=X Y Z`). The rest are always
+    /// taken from the following arguments, even if they look like
+    /// options.
+    ///
+    /// Returns [`Error::RequiresValues`] if the argument list ends
+    /// before the group is complete.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Error, Opt, Options};
+    /// #
+    /// let args = ["--point", "1", "2", "3", "--point=4", "5"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("point"))));
+    /// assert_eq!(opts.value_group(), Ok(["1", "2", "3"]));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("point"))));
+    /// assert_eq!(
+    ///     opts.value_group::<3>(),
+    ///     Err(Error::RequiresValues {
+    ///         opt: Opt::Long("point"),
+    ///         expected: 3,
+    ///         found: 2,
+    ///     })
+    /// );
+    /// ```
+    pub fn value_group<const N: usize>(&'_ mut self) -> Result<A, [A; N]> {
+        let opt = match &self.state {
+            State::EndOfOption(opt)
+            | State::ShortOptionCluster(opt, _)
+            | State::LongOptionWithValue(opt, _) => opt.clone(),
+            _ => panic!("called Options::value_group() with no previous option"),
+        };
+
+        let mut values: [Option<A>; N] = core::array::from_fn(|_| None);
+
+        for (found, slot) in values.iter_mut().enumerate() {
+            *slot = Some(self.group_value(&opt, found, N)?);
+        }
+
+        Ok(values.map(Option::unwrap))
+    }
+
+    /// Takes the value at index `found` of a group of `expected` values
+    /// of `opt`, for [`Options::value_group`] and [`OptSpec::arity`].
+    fn group_value(&mut self, opt: &Opt<A>, found: usize, expected: usize) -> Result<A, A> {
+        let value = if found == 0 {
+            self.value().ok()
+        } else {
+            self.next_raw()
+        };
+
+        value.ok_or_else(|| {
+            self.state = State::End { ended_opts: false };
+            Error::RequiresValues {
+                opt: opt.clone(),
+                expected,
+                found,
+            }
+        })
+    }
+
+    /// Takes the values of `opt` declared by `spec`, for
+    /// [`Options::next_spec`] and [`ParsedArgs`].
+    pub(crate) fn spec_values<S, L>(
+        &mut self,
+        opt: &Opt<A>,
+        spec: &OptSpec<S, L>,
+    ) -> Result<A, SpecValues<A>> {
+        let mut values = SpecValues::new();
+
+        match spec.value_count() {
+            0 => {}
+            1 => values.push(self.value()?),
+            count => {
+                for found in 0..count {
+                    values.push(self.group_value(opt, found, count)?);
+                }
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Retrieves the value of the option last returned by
+    /// [`Options::next_opt`] or [`Options::next_arg`] like
+    /// [`Options::value`], and parses it with [`FromStr`].
+    ///
+    /// If the value can't be parsed, [`ValueError::InvalidValue`] is
+    /// returned with the option, the value and the parse error, so it
+    /// can be reported without any extra context. For arguments that
+    /// aren't strings, like `&[u8]`, use [`Options::value_parsed_with`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["--jobs=4", "-j", "many"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("jobs"))));
+    /// assert_eq!(opts.value_parsed::<u32>(), Ok(4));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('j'))));
+    /// assert_eq!(
+    ///     opts.value_parsed::<u32>().unwrap_err().to_string(),
+    ///     "invalid value 'many' for -j: invalid digit found in string"
+    /// );
+    /// ```
+    pub fn value_parsed<T: FromStr>(&'_ mut self) -> core::result::Result<T, ValueError<A, T::Err>>
+    where
+        A: AsRef<str>,
+    {
+        self.value_parsed_with(|value| value.as_ref().parse())
+    }
+
+    /// Like [`Options::value_parsed`], but parses the value with
+    /// `parse` instead of [`FromStr`], so it can be used with any type
+    /// of argument.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options, ValueError};
+    /// #
+    /// fn parse_u8(value: &&[u8]) -> Result<u8, &'static str> {
+    ///     match value {
+    ///         [digit @ b'0'..=b'9'] => Ok(digit - b'0'),
+    ///         _ => Err("expected a single digit"),
+    ///     }
+    /// }
+    ///
+    /// let args: [&[u8]; 2] = [b"-l3", b"-l10"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'l'))));
+    /// assert_eq!(opts.value_parsed_with(parse_u8), Ok(3));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'l'))));
+    /// assert_eq!(
+    ///     opts.value_parsed_with(parse_u8),
+    ///     Err(ValueError::InvalidValue {
+    ///         opt: Opt::Short(b'l'),
+    ///         value: &b"10"[..],
+    ///         error: "expected a single digit",
+    ///     })
+    /// );
+    /// ```
+    pub fn value_parsed_with<T, E>(
+        &'_ mut self,
+        parse: impl FnOnce(&A) -> core::result::Result<T, E>,
+    ) -> core::result::Result<T, ValueError<A, E>> {
+        let opt = match &self.state {
+            State::EndOfOption(opt)
+            | State::ShortOptionCluster(opt, _)
+            | State::LongOptionWithValue(opt, _) => opt.clone(),
+            _ => panic!("called Options::value_parsed_with() with no previous option"),
+        };
+
+        let value = match self.value() {
+            Ok(value) => value,
+            Err(_) => return Err(ValueError::RequiresValue(opt)),
+        };
+
+        parse(&value).map_err(|error| ValueError::InvalidValue { opt, value, error })
+    }
+
+    /// Retrieves the value of the option last returned by
+    /// [`Options::next_opt`] or [`Options::next_arg`] like
+    /// [`Options::value`], and adds it to `values`, for options like
+    /// `-I DIR` that can be repeated without allocating.
+    ///
+    /// Returns [`Error::TooManyValues`] if `values` is already full, in
+    /// which case the value is still consumed.
+    ///
+    /// This method requires the `heapless` feature.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Error, Opt, Options};
+    /// #
+    /// let args = ["-Ia", "-Ib", "-Ic"];
+    /// let mut opts = Options::new(args.into_iter());
+    /// let mut includes = heapless::Vec::<_, 2>::new();
+    ///
+    /// opts.next_opt()?;
+    /// opts.value_into(&mut includes)?;
+    /// opts.next_opt()?;
+    /// opts.value_into(&mut includes)?;
+    /// opts.next_opt()?;
+    ///
+    /// assert_eq!(
+    ///     opts.value_into(&mut includes),
+    ///     Err(Error::TooManyValues { opt: Opt::Short('I'), capacity: 2 })
+    /// );
+    /// assert_eq!(includes, ["a", "b"]);
+    /// # Ok::<(), Error<&'static str>>(())
+    /// ```
+    #[cfg(feature = "heapless")]
+    pub fn value_into<const N: usize>(
+        &'_ mut self,
+        values: &mut heapless::Vec<A, N>,
+    ) -> Result<A, ()> {
+        let opt = match &self.state {
+            State::EndOfOption(opt)
+            | State::ShortOptionCluster(opt, _)
+            | State::LongOptionWithValue(opt, _) => opt.clone(),
+            _ => panic!("called Options::value_into() with no previous option"),
+        };
+
+        values
+            .push(self.value()?)
+            .map_err(|_| Error::TooManyValues { opt, capacity: N })
+    }
+
+    /// Retrieves the value of the option last returned by
+    /// [`Options::next_opt`] or [`Options::next_arg`] like
+    /// [`Options::value`], and splits it at the first `=` into a key
+    /// and an optional value, for options like `-D NAME=VALUE`.
+    ///
+    /// This is the same splitting that is done for `--option=value`,
+    /// using [`Argument::split_on`]. The value is `None` if there is no
+    /// `=`, and empty if nothing follows it.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["-D", "NAME=a=b", "--define=DEBUG", "-DEMPTY="];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('D'))));
+    /// assert_eq!(opts.value_pair(), Ok(("NAME", Some("a=b"))));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("define"))));
+    /// assert_eq!(opts.value_pair(), Ok(("DEBUG", None)));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('D'))));
+    /// assert_eq!(opts.value_pair(), Ok(("EMPTY", Some(""))));
+    /// ```
+    pub fn value_pair(&'_ mut self) -> Result<A, (A, Option<A>)> {
+        self.value().map(|value| value.split_on(b'='))
+    }
+
+    /// Retrieves the value of the option last returned by
+    /// [`Options::next_opt`] or [`Options::next_arg`] like
+    /// [`Options::value`], and returns an iterator over its parts
+    /// separated by the ASCII character `delimiter`, for options like
+    /// `--features a,b,c`.
+    ///
+    /// The value is split with [`Argument::split_on`], so this does not
+    /// allocate for `&str` or `&[u8]`. See [`Delimited`] for details.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["--features", "a,b,c", "-Fd"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("features"))));
+    /// assert!(opts.values_delimited(b',')?.eq(["a", "b", "c"]));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('F'))));
+    /// assert!(opts.values_delimited(b',')?.eq(["d"]));
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    pub fn values_delimited(&'_ mut self, delimiter: u8) -> Result<A, Delimited<A>> {
+        self.value().map(|value| Delimited::new(value, delimiter))
+    }
+
+    /// Returns an iterator over the values of the option last returned
+    /// by [`Options::next_opt`] or [`Options::next_arg`], for options
+    /// like `--include a b c` that take any number of values.
+    ///
+    /// A value attached to the option, like `--include=a`, is returned
+    /// first. After that, the following arguments are returned until
+    /// one would be parsed as an option or ends options, according to
+    /// the [`ParseConfig`] (so `-` itself and, with
+    /// [`ParseConfig::negative_numbers`], numbers like `-5` are still
+    /// values). That argument is left for [`Options::next_opt`]. Use
+    /// [`Options::values_while`] to stop somewhere else, or
+    /// [`Iterator::take`] to stop after a fixed number of values.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["--include", "a", "b", "-v", "--include=c", "d", "--", "e"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("include"))));
+    /// assert!(opts.values().eq(["a", "b"]));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("include"))));
+    /// assert!(opts.values().eq(["c", "d"]));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    /// assert_eq!(opts.next_positional(), Some("e"));
+    /// ```
+    pub fn values(&mut self) -> Values<'_, A, I, fn(&A) -> bool> {
+        let accept: fn(&A) -> bool = |_| true;
+        self.values_while(accept).until_opt()
+    }
+
+    /// Like [`Options::values`], but takes each following argument as a
+    /// value only while `accept` returns `true` for it. A value
+    /// attached to the option is always taken.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::value`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["--point", "1", "-2", "x"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("point"))));
+    /// let numbers = opts.values_while(|arg| arg.parse::<i32>().is_ok());
+    /// assert!(numbers.eq(["1", "-2"]));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    /// assert_eq!(opts.next_positional(), Some("x"));
+    /// ```
+    pub fn values_while<F: FnMut(&A) -> bool>(&mut self, accept: F) -> Values<'_, A, I, F> {
+        if let State::Start { .. } | State::Begin | State::Positional(_) | State::End { .. } =
+            self.state
+        {
+            panic!("called Options::values_while() with no previous option");
+        }
+
+        Values::new(self, accept)
+    }
+
+    /// Retrieves the next positional argument. This method must be
+    /// called after all the last option has been fully parsed. Usually,
+    /// this is when [`Options::next_opt`] returns `Ok(None)`, but it
+    /// can also be after calling [`Options::value_opt`].
+    ///
+    /// After this method is called, you can parse further options with
+    /// [`Options::next_opt`] or continue getting positional arguments
+    /// (which will not treat options specially).
+    ///
+    /// This method preserves the state of [`Options::opts_ended`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the option parsing is not yet complete;
+    /// that is, it can panic if [`Options::next_opt`] has not yet
+    /// returned `None` at least once. If [`Options::next_opt`] returns
+    /// `None`, then [`Options::next_positional`] will always be safe to
+    /// call.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["-a", "foo", "bar"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    /// assert_eq!(opts.next_positional(), Some("foo"));
+    /// assert_eq!(opts.next_positional(), Some("bar"));
+    /// assert_eq!(opts.next_positional(), None);
+    /// ```
+    #[inline]
+    pub fn next_positional(&'_ mut self) -> Option<A> {
+        match self.state {
+            State::Start { ended_opts } => self.next_raw().or_else(|| {
+                self.state = State::End { ended_opts };
+                None
+            }),
+
+            State::Begin => {
+                self.state = State::Start { ended_opts: false };
+                self.next_positional()
+            }
+
+            State::Positional(_) => match self.take_state() {
+                State::Positional(arg) => Some(arg),
+                _ => unreachable!(),
+            },
+
+            State::End { .. } => None,
+
+            _ => {
+                panic!("called Options::next_positional() while option parsing hasn't finished")
+            }
+        }
+    }
+
+    /// Retrieves the next positional argument as the name of a
+    /// subcommand, and returns it along with this [`Options`], ready to
+    /// parse the subcommand's options with [`Options::next_opt`].
+    ///
+    /// This is like [`Options::next_positional`], except that
+    /// [`Options::opts_ended`] is reset, so that a `--` before the
+    /// subcommand name does not stop its options from being parsed.
+    /// Returns `None` if there are no more arguments.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Options::next_positional`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["-v", "add", "-f", "file.txt"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    ///
+    /// match opts.subcommand() {
+    ///     Some(("add", opts)) => {
+    ///         assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('f'))));
+    ///         assert_eq!(opts.next_opt(), Ok(None));
+    ///         assert_eq!(opts.next_positional(), Some("file.txt"));
+    ///     }
+    ///
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn subcommand(&mut self) -> Option<(A, &mut Self)> {
+        let name = self.next_positional()?;
+        self.reset_opts_ended();
+        Some((name, self))
+    }
+
+    /// Returns an iterator over the positional arguments of this
+    /// [`Options`]. The returned iterator will forward
+    /// [`Iterator::next`] calls to [`Options::next_positional`].
+    ///
+    /// Every remaining argument is returned as-is, even if it looks
+    /// like an option. The iterator forwards [`Iterator::size_hint`] to
+    /// the underlying iterator, so collecting it allocates only once
+    /// for iterators that know their length.
+    ///
+    /// This method does not panic, but [`Iterator::next`] may panic
+    /// once it is called if option parsing has not finished
+    /// ([`Options::next_opt`] has not returned `Ok(None)`). The exact
+    /// requirements are the same as [`Options::next_positional`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["--flag", "one", "two"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("flag"))));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    ///
+    /// let mut args = opts.positionals();
+    ///
+    /// assert_eq!(args.next(), Some("one"));
+    /// assert_eq!(args.next(), Some("two"));
+    /// assert_eq!(args.next(), None);
+    /// ```
+    pub fn positionals(&mut self) -> Positionals<'_, A, I> {
+        Positionals::new(self)
+    }
+
+    /// Returns an iterator over the remaining arguments of this
+    /// [`Options`], both options and positional arguments. The returned
+    /// iterator will forward [`Iterator::next`] calls to
+    /// [`Options::next_arg`], so it yields `Result`s and can be used
+    /// with `for` loops and iterator adapters.
+    ///
+    /// Values of options can still be retrieved by calling
+    /// [`Options::value`] on this [`Options`], but not while the
+    /// iterator is borrowing it; use [`Options::next_arg`] directly if
+    /// options take values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Arg, Options};
+    /// #
+    /// let args = ["-ab", "file", "--verbose"];
+    /// let mut opts = Options::new(args.into_iter());
+    /// let mut flags = 0;
+    ///
+    /// for arg in opts.iter_args() {
+    ///     if let Arg::Short(_) | Arg::Long(_) = arg? {
+    ///         flags += 1;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(flags, 3);
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    pub fn iter_args(&mut self) -> Args<'_, A, I> {
+        Args::new(self)
+    }
+
+    /// Calls `f` with each remaining option and positional argument,
+    /// like [`Options::next_arg`] in a loop, and returns the final
+    /// accumulator. `f` is also given this [`Options`], so that it can
+    /// retrieve values.
+    ///
+    /// Parsing stops at the first error, which is returned. Errors from
+    /// parsing are converted with [`From`], so `f` can use its own
+    /// error type. Driving the loop internally like this lets the
+    /// optimizer keep the parsing state in registers across arguments,
+    /// which is faster than calling [`Options::next_arg`] when parsing
+    /// many command lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Arg, Options};
+    /// #
+    /// let args = ["-v", "--jobs", "4", "a", "-vv", "b"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// let (verbosity, files) = opts.try_fold_args((0, 0), |(v, files), opts, arg| {
+    ///     Ok::<_, getargs::Error<_>>(match arg {
+    ///         Arg::Short('v') => (v + 1, files),
+    ///         Arg::Long("jobs") => {
+    ///             opts.value()?;
+    ///             (v, files)
+    ///         }
+    ///         _ => (v, files + 1),
+    ///     })
+    /// })?;
+    ///
+    /// assert_eq!((verbosity, files), (3, 2));
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    pub fn try_fold_args<B, E: From<Error<A>>>(
+        &mut self,
+        init: B,
+        mut f: impl FnMut(B, &mut Self, Arg<A>) -> core::result::Result<B, E>,
+    ) -> core::result::Result<B, E> {
+        let mut acc = init;
+
+        while let Some(arg) = self.next_arg()? {
+            acc = f(acc, self, arg)?;
+        }
+
+        Ok(acc)
+    }
+
+    /// Like [`Options::try_fold_args`], but without an accumulator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Arg, Options};
+    /// #
+    /// let args = ["-o", "out", "in"];
+    /// let mut opts = Options::new(args.into_iter());
+    /// let mut positionals = Vec::new();
+    ///
+    /// opts.try_for_each_arg(|opts, arg| {
+    ///     match arg {
+    ///         Arg::Positional(positional) => positionals.push(positional),
+    ///         _ => drop(opts.value()?),
+    ///     }
+    ///
+    ///     Ok::<_, getargs::Error<_>>(())
+    /// })?;
+    ///
+    /// assert_eq!(positionals, ["in"]);
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    pub fn try_for_each_arg<E: From<Error<A>>>(
+        &mut self,
+        mut f: impl FnMut(&mut Self, Arg<A>) -> core::result::Result<(), E>,
+    ) -> core::result::Result<(), E> {
+        self.try_fold_args((), |(), opts, arg| f(opts, arg))
+    }
+
+    /// Parses all remaining arguments, calling `handler` for each option
+    /// and positional argument, until it returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// Returns [`ControlFlow::Continue`] if every argument was handled,
+    /// or the [`ControlFlow::Break`] returned by the handler. Parsing
+    /// stops at the first error, which is returned; parse errors are
+    /// converted with [`From`].
+    ///
+    /// See [`ParseHandler`] for an example.
+    pub fn parse_with<B, E: From<Error<A>>, H: ParseHandler<A, B, E> + ?Sized>(
+        &mut self,
+        handler: &mut H,
+    ) -> core::result::Result<ControlFlow<B>, E> {
+        while let Some(arg) = self.next_arg()? {
+            let flow = match arg {
+                Arg::Positional(positional) => handler.on_positional(positional)?,
+                arg => handler.on_opt(arg.opt().unwrap(), &mut ValueAccess::new(self))?,
+            };
+
+            if flow.is_break() {
+                return Ok(flow);
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Returns an iterator over the options of this [`Options`]. The
+    /// returned iterator will forward [`Iterator::next`] calls to
+    /// [`Options::next_opt`], so it ends at the first positional
+    /// argument, leaving this [`Options`] ready for
+    /// [`Options::positionals`].
+    ///
+    /// Like [`Options::iter_args`], the iterator borrows this
+    /// [`Options`], so it is best suited for options that don't take
+    /// values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["-v", "-v", "--quiet", "a.txt", "b.txt"];
+    /// let mut opts = Options::new(args.into_iter());
+    /// let mut verbosity = 0;
+    ///
+    /// for opt in opts.opts() {
+    ///     match opt? {
+    ///         Opt::Short('v') => verbosity += 1,
+    ///         Opt::Long("quiet") => verbosity -= 1,
+    ///         _ => {}
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(verbosity, 1);
+    /// assert_eq!(opts.positionals().collect::<Vec<_>>(), ["a.txt", "b.txt"]);
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    pub fn opts(&mut self) -> Opts<'_, A, I> {
+        Opts::new(self)
+    }
+
+    /// Returns an iterator over the positional arguments of this
+    /// [`Options`] as `key=value` operands, like those accepted by
+    /// `dd`. Each positional argument is split at the first `=` using
+    /// [`Argument::split_on`], the same way as the value of
+    /// `--key=value`. Operands without an `=` are returned with a
+    /// value of `None`.
+    ///
+    /// This method has the same requirements as
+    /// [`Options::positionals`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["if=/dev/zero", "of=out", "bs=1M", "sync"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    ///
+    /// let mut operands = opts.operands();
+    ///
+    /// assert_eq!(operands.next(), Some(("if", Some("/dev/zero"))));
+    /// assert_eq!(operands.next(), Some(("of", Some("out"))));
+    /// assert_eq!(operands.next(), Some(("bs", Some("1M"))));
+    /// assert_eq!(operands.next(), Some(("sync", None)));
+    /// assert_eq!(operands.next(), None);
+    /// ```
+    pub fn operands(&mut self) -> Operands<'_, A, I> {
+        Operands::new(self)
+    }
+
+    /// Consumes this [`Options`], returning an iterator over the rest
+    /// of the arguments. The returned iterator wraps the one originally
+    /// passed to [`Options::new`].
+    ///
+    /// # Panics
     ///
     /// Panics if an option is currently being parsed.
     pub fn into_positionals(self) -> IntoPositionals<A, I> {
         match self.state {
-            State::Start { .. } | State::EndOfOption(_) | State::End { .. } => {
-                IntoPositionals::new(None, self.iter)
+            State::Start { .. } | State::Begin | State::EndOfOption(_) | State::End { .. } => {
+                IntoPositionals::new(self.peeked, self.iter)
+            }
+            State::Positional(positional) => {
+                IntoPositionals::new([Some(positional), None], self.iter)
             }
-            State::Positional(positional) => IntoPositionals::new(Some(positional), self.iter),
             _ => {
                 panic!("called Options::into_positionals() while option parsing hasn't finished")
             }
         }
     }
 
+    /// Consumes this [`Options`], splitting the rest of the positional
+    /// arguments into a list of sources and a single destination, like
+    /// the operands of `cp`, `mv` or `install`.
+    ///
+    /// The sources are returned as an iterator over all but the last
+    /// positional argument, and the destination is the last one. The
+    /// iterator passed to [`Options::new`] must be [`Clone`] so that
+    /// the last argument can be found without allocating.
+    ///
+    /// Returns [`Error::TooFewPositionals`] if fewer than two
+    /// positional arguments remain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an option is currently being parsed, like
+    /// [`Options::into_positionals`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Error, Opt, Options};
+    /// #
+    /// let args = ["-v", "one", "two", "dest"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    ///
+    /// let (sources, dest) = opts.split_last_positional()?;
+    ///
+    /// assert_eq!(sources.collect::<Vec<_>>(), ["one", "two"]);
+    /// assert_eq!(dest, "dest");
+    /// # Ok::<(), Error<&'static str>>(())
+    /// ```
+    pub fn split_last_positional(self) -> Result<A, (Take<IntoPositionals<A, I>>, A)>
+    where
+        I: Clone,
+    {
+        let positionals = self.into_positionals();
+        let found = positionals.clone().count();
+
+        if found < 2 {
+            return Err(Error::TooFewPositionals { expected: 2, found });
+        }
+
+        let dest = positionals.clone().nth(found - 1).unwrap();
+        Ok((positionals.take(found - 1), dest))
+    }
+
+    /// Adds the rest of the positional arguments to `positionals`,
+    /// for programs without an allocator.
+    ///
+    /// Returns [`Error::TooManyPositionals`] as soon as a positional
+    /// argument does not fit. The positional arguments that did fit
+    /// are kept, and the rest remain in this [`Options`].
+    ///
+    /// This method requires the `heapless` feature.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Options::next_positional`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Error, Opt, Options};
+    /// #
+    /// let args = ["-v", "one", "two", "three"];
+    /// let mut opts = Options::new(args.into_iter());
+    /// let mut positionals = heapless::Vec::<_, 2>::new();
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    /// assert_eq!(
+    ///     opts.positionals_into(&mut positionals),
+    ///     Err(Error::TooManyPositionals { capacity: 2 })
+    /// );
+    /// assert_eq!(positionals, ["one", "two"]);
+    /// assert_eq!(opts.next_positional(), Some("three"));
+    /// ```
+    #[cfg(feature = "heapless")]
+    pub fn positionals_into<const N: usize>(
+        &'_ mut self,
+        positionals: &mut heapless::Vec<A, N>,
+    ) -> Result<A, ()> {
+        while !positionals.is_full() {
+            let Some(positional) = self.next_positional() else {
+                return Ok(());
+            };
+
+            // There is room, so this can't fail.
+            let _ = positionals.push(positional);
+        }
+
+        match self.peek(Self::next_positional) {
+            Some(_) => Err(Error::TooManyPositionals { capacity: N }),
+            None => Ok(()),
+        }
+    }
+
+    /// Scans the rest of the arguments for a help option, without
+    /// consuming anything, and returns the first option for which
+    /// `is_help` returns `true`. This needs a [`Clone`] iterator; see
+    /// [`Options::set_help_opt`] to report help while parsing instead.
+    ///
+    /// This lets `--help` take priority over everything else: call it
+    /// before your parsing loop and print help if it returns `Some`, so
+    /// that help works even when the rest of the command line is
+    /// invalid or incomplete. [`Opt::is_help`][Opt#method.is_help]
+    /// recognizes the usual `-h` and `--help`, but any other predicate
+    /// can be used.
+    ///
+    /// Since the scan does not know which options take values, every
+    /// argument that looks like an option is checked, even if it would
+    /// later be consumed as a value. The scan stops at `--`. Errors are
+    /// ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args = ["--output=x", "-vh", "--", "--help"];
+    /// let opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.help_requested(|opt| opt.is_help()), Some(Opt::Short('h')));
+    /// assert_eq!(opts.help_requested(|opt| opt == Opt::Short('?')), None);
+    /// ```
+    pub fn help_requested(&self, mut is_help: impl FnMut(Opt<A>) -> bool) -> Option<Opt<A>>
+    where
+        I: Clone,
+    {
+        let mut opts = self.clone();
+
+        loop {
+            match opts.next_opt() {
+                Ok(Some(opt)) => {
+                    if is_help(opt.clone()) {
+                        return Some(opt);
+                    }
+                }
+                Err(_) => {}
+                Ok(None) if opts.opts_ended() => return None,
+                Ok(None) => {
+                    opts.next_positional()?;
+                }
+            }
+        }
+    }
+
+    /// Wraps this [`Options`] so that every parsed option, value,
+    /// positional argument and error is reported to `observer`.
+    ///
+    /// See [`ParseObserver`] for an example.
+    pub fn observe<O: ParseObserver<A>>(self, observer: O) -> Observed<A, I, O> {
+        Observed::new(self, observer)
+    }
+
+    /// Wraps this [`Options`] so that every option, value and
+    /// positional argument is returned along with its [`Span`].
+    ///
+    /// See [`Spanned`] for an example.
+    pub fn spanned(self) -> Spanned<A, I>
+    where
+        A: AsRef<[u8]>,
+    {
+        Spanned::new(self)
+    }
+
+    /// Wraps this [`Options`] so that options are recognized using a
+    /// `getopt(3)` optstring like `"ab:c::"`, and returned the same way
+    /// `getopt` would return them.
+    ///
+    /// See [`Getopt`] for an example.
+    pub fn getopt(self, optstring: &str) -> Getopt<'_, A, I>
+    where
+        A::ShortOpt: Into<char>,
+    {
+        Getopt::new(self, optstring)
+    }
+
+    /// Like [`Options::getopt`], but also recognizes the long options
+    /// in `longopts`, like `getopt_long(3)`.
+    ///
+    /// See [`GetoptLong`] for an example.
+    pub fn getopt_long<'s>(
+        self,
+        optstring: &'s str,
+        longopts: &'s [LongOptSpec<'s>],
+    ) -> GetoptLong<'s, A, I>
+    where
+        A: AsRef<[u8]>,
+        A::ShortOpt: Into<char>,
+    {
+        GetoptLong::new(self, optstring, longopts)
+    }
+
+    /// Wraps this [`Options`] so that options that are not in `specs`
+    /// are reported as [`Error::UnknownOption`].
+    ///
+    /// See [`Validated`] for an example.
+    pub fn validate<L: Copy>(self, specs: &[OptSpec<A::ShortOpt, L>]) -> Validated<'_, A, I, L>
+    where
+        A: PartialEq<L>,
+    {
+        Validated::new(self, specs)
+    }
+
+    /// Wraps this [`Options`] so that options are accepted anywhere,
+    /// but positional arguments are only returned after all options,
+    /// like GNU `getopt`'s default argument permutation.
+    ///
+    /// See [`Permuting`] for an example.
+    #[cfg(feature = "alloc")]
+    pub fn permuting(self) -> Permuting<A, I> {
+        Permuting::new(self)
+    }
+
     /// Returns `true` if the last call to [`Options::next_opt`]
     /// encountered a `--`. In that case, it will have returned `None`,
     /// but without this method you wouldn't be able to tell whether
@@ -844,6 +2786,48 @@ impl<'arg, A: Argument + 'arg, I: Iterator<Item = A>> Options<A, I> {
         }
     }
 
+    /// Enables or disables raw mode. In raw mode, option parsing is
+    /// disabled entirely: [`Options::next_opt`] always returns
+    /// `Ok(None)` and [`Options::next_arg`] yields every remaining
+    /// argument as a positional, even if it looks like an option or is
+    /// `--`.
+    ///
+    /// This is useful after subcommands like `exec` or `run`, whose
+    /// trailing arguments must be passed through untouched without
+    /// requiring the user to type `--`. Raw mode can be toggled at any
+    /// point during parsing, and takes effect starting with the next
+    /// argument; the rest of a short option cluster that is currently
+    /// being parsed is still parsed as options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Arg, Options};
+    /// #
+    /// let args = ["-v", "exec", "ls", "-la", "--", "--color"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('v'))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("exec"))));
+    ///
+    /// opts.set_raw(true);
+    ///
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("ls"))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("-la"))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("--"))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("--color"))));
+    /// assert_eq!(opts.next_arg(), Ok(None));
+    /// ```
+    pub fn set_raw(&mut self, raw: bool) {
+        self.raw = raw;
+    }
+
+    /// Returns `true` if raw mode is enabled. See
+    /// [`Options::set_raw`].
+    pub fn is_raw(&self) -> bool {
+        self.raw
+    }
+
     /// Returns `true` if this [`Options`] has reached the end of its
     /// iterator, and all positional arguments have also been consumed.
     /// This method will always return `true` when
@@ -860,7 +2844,8 @@ impl<'arg, A: Argument + 'arg, I: Iterator<Item = A>> Options<A, I> {
     ///
     /// Most iterators, including iterators over arrays, never repeat
     /// their elements. This method isn't very useful unless you know
-    /// the exact behavior of the iterator you're using.
+    /// the exact behavior of the iterator you're using. Raw mode is
+    /// disabled, like with [`Options::rewind`].
     ///
     /// # Panics
     ///
@@ -869,7 +2854,10 @@ impl<'arg, A: Argument + 'arg, I: Iterator<Item = A>> Options<A, I> {
     pub fn restart(&'_ mut self) {
         match self.state {
             State::End { .. } => {
-                self.state = State::Start { ended_opts: false };
+                self.state = Self::initial_state(self.config);
+                self.index = 0;
+                self.current = None;
+                self.raw = false;
             }
             _ => {
                 panic!("called Options::restart() during an iteration")
@@ -877,3 +2865,174 @@ impl<'arg, A: Argument + 'arg, I: Iterator<Item = A>> Options<A, I> {
         }
     }
 }
+
+impl<A: Argument, I: Iterator<Item = A> + Clone> Options<A, Rewindable<I>> {
+    /// Starts parsing over from the first argument, as though this
+    /// [`Options`] had just been created with the same
+    /// [`ParseConfig`]. Raw mode is disabled. See [`Rewindable`] for
+    /// an example.
+    pub fn rewind(&mut self) {
+        self.iter.rewind();
+        self.peeked = [None, None];
+        self.state = Self::initial_state(self.config);
+        self.index = 0;
+        self.current = None;
+        self.raw = false;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: Argument, I: Iterator<Item = A>> Options<A, Injectable<A, I>> {
+    /// Inserts `args` in front of the remaining arguments, so that they
+    /// are parsed next. See [`Injectable`] for an example.
+    ///
+    /// The arguments are inserted after the current argument: if an
+    /// option was just returned from a short option cluster like
+    /// `-abc`, the rest of the cluster is parsed first, and if an
+    /// option with an attached value like `--foo=bar` was just
+    /// returned, the value can still be retrieved. Injecting arguments
+    /// after all arguments have been consumed makes them available
+    /// again, as though they had been at the end.
+    ///
+    /// This method requires the `alloc` feature.
+    pub fn inject<J: IntoIterator<Item = A>>(&mut self, args: J) {
+        self.iter.inject(args);
+
+        if let State::End { ended_opts } = self.state {
+            self.state = State::Start { ended_opts };
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Options<alloc::borrow::Cow<'a, str>, CmdlineWords<'a>> {
+    /// Creates a new [`Options`] over the words of `line`, which is
+    /// split like a POSIX shell would split it. This is convenient for
+    /// tests and for tools that read commands interactively. See
+    /// [`ShellWords`] for the quoting rules.
+    ///
+    /// Words are borrowed from `line` unless they have to be unquoted,
+    /// so arguments are [`Cow`][alloc::borrow::Cow]s. `line` should not
+    /// include the program name.
+    ///
+    /// This method requires the `alloc` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Arg, Options};
+    /// #
+    /// let mut opts = Options::from_cmdline_str("-a --bar=1 'baz qux'");
+    ///
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("bar".into()))));
+    /// assert_eq!(opts.value(), Ok("1".into()));
+    /// assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("baz qux".into()))));
+    /// assert_eq!(opts.next_arg(), Ok(None));
+    /// ```
+    pub fn from_cmdline_str(line: &'a str) -> Self {
+        Self::new(CmdlineWords::new(line))
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a [u8]>> Options<&'a [u8], I> {
+    /// Converts this [`Options`] into a [`Utf8Options`] that returns
+    /// the remaining options, values and positional arguments as
+    /// `&str`, keeping its place in the argument list. This can be used
+    /// to switch to string arguments partway through parsing, once it
+    /// is known that they should be text.
+    ///
+    /// Arguments that have not been parsed yet are converted as they
+    /// are reached, like in any [`Utf8Options`]. If the part of the
+    /// argument list that has already been read is not valid UTF-8, for
+    /// example because the rest of a short option cluster is not,
+    /// [`Error::InvalidUtf8`] is returned and this [`Options`] is
+    /// consumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, Options};
+    /// #
+    /// let args: [&[u8]; 4] = [b"--text", b"-n\xc3\xa9", b"x", b"\xff"];
+    /// let mut opts = Options::new(args.into_iter());
+    ///
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(&b"text"[..]))));
+    /// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'n'))));
+    ///
+    /// let mut opts = opts.map_to_str()?;
+    ///
+    /// assert_eq!(opts.value(), Ok("é"));
+    /// assert_eq!(opts.next_opt(), Ok(None));
+    /// assert_eq!(opts.next_positional(), Ok(Some("x")));
+    /// assert!(opts.next_positional().is_err());
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    pub fn map_to_str(self) -> Result<&'a str, Utf8Options<'a, I>> {
+        let index = self.index;
+
+        let to_str = |arg: &'a [u8], arg_index: usize| {
+            core::str::from_utf8(arg).map_err(|error| Error::InvalidUtf8 {
+                arg_index,
+                valid_up_to: error.valid_up_to(),
+            })
+        };
+
+        let short = |short: u8| {
+            if short.is_ascii() {
+                Ok(char::from(short))
+            } else {
+                Err(Error::InvalidUtf8 {
+                    arg_index: index,
+                    valid_up_to: 0,
+                })
+            }
+        };
+
+        let opt = |opt: Opt<&'a [u8]>| -> Result<&'a str, Opt<&'a str>> {
+            Ok(match opt {
+                Opt::Short(s) => Opt::Short(short(s)?),
+                Opt::Long(name) => Opt::Long(to_str(name, index)?),
+                Opt::ShortPlus(s) => Opt::ShortPlus(short(s)?),
+                Opt::Numeric(digits) => Opt::Numeric(to_str(digits, index)?),
+            })
+        };
+
+        let state = match self.state {
+            State::Start { ended_opts } => State::Start { ended_opts },
+            State::Begin => State::Begin,
+            State::Positional(arg) => State::Positional(to_str(arg, index)?),
+            State::EndOfOption(o) => State::EndOfOption(opt(o)?),
+            State::ShortOptionCluster(o, rest) => {
+                State::ShortOptionCluster(opt(o)?, to_str(rest, index)?)
+            }
+            State::LongOptionWithValue(o, value) => {
+                State::LongOptionWithValue(opt(o)?, to_str(value, index)?)
+            }
+            State::End { ended_opts } => State::End { ended_opts },
+        };
+
+        let mut peeked = [None; 2];
+        let mut read = index;
+
+        for (slot, arg) in peeked.iter_mut().zip(self.peeked) {
+            if let Some(arg) = arg {
+                read += 1;
+                *slot = Some(to_str(arg, read)?);
+            }
+        }
+
+        Ok(Utf8Options::from_options(Options {
+            iter: Utf8Args::with_index(self.iter, read),
+            peeked,
+            index,
+            current: self.current.and_then(|arg| core::str::from_utf8(arg).ok()),
+            track_current: self.track_current,
+            state,
+            raw: self.raw,
+            help: None,
+            fast: self.fast,
+            config: self.config,
+        }))
+    }
+}
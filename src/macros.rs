@@ -0,0 +1,226 @@
+use crate::{Argument, Opt};
+
+/// Declares an enum of options along with a table of [`OptSpec`]s.
+///
+/// This is a lightweight alternative to `#[derive(FromOptions)]` that
+/// does not need proc macros, allocation or `std`. Each variant is
+/// followed by the names of its option in parentheses: a short name
+/// like `-'v'`, a long name like `--"verbose"`, or both. Adding `value`
+/// at the end declares that the option takes a value.
+///
+/// The generated enum derives [`Copy`], [`Clone`], [`Eq`],
+/// [`PartialEq`], [`Debug`] and [`Hash`], and has:
+///
+/// - `SPECS`, the table of [`OptSpec`]s, in the same order as the
+///   variants.
+/// - `ALL`, all of the variants, in order.
+/// - `spec(self)`, which returns the [`OptSpec`] of a variant.
+/// - `from_opt(&opt)`, which returns the variant matching an [`Opt`].
+/// - `next_opt(&mut opts)`, which works like [`Options::next_spec`],
+///   but returns the variant instead of the [`Opt`], so that it can be
+///   matched on directly.
+///
+/// Only arguments whose short options are [`char`]s, like `&str`, are
+/// supported.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{opts, Options};
+/// #
+/// opts! {
+///     /// The options of this program.
+///     enum Cli {
+///         Verbose(-'v' --"verbose"),
+///         Output(-'o' --"output" value),
+///         DryRun(--"dry-run"),
+///     }
+/// }
+///
+/// let args = ["-vo", "out.txt", "--dry-run", "in.txt"];
+/// let mut opts = Options::new(args.into_iter());
+///
+/// while let Some((opt, value)) = Cli::next_opt(&mut opts)? {
+///     match opt {
+///         Cli::Verbose => {}
+///         Cli::Output => assert_eq!(value, Some("out.txt")),
+///         Cli::DryRun => {}
+///     }
+/// }
+///
+/// assert_eq!(Cli::Output.spec().long, Some("output"));
+/// assert_eq!(opts.next_positional(), Some("in.txt"));
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+///
+/// [`OptSpec`]: crate::OptSpec
+/// [`Opt`]: crate::Opt
+/// [`Options::next_spec`]: crate::Options::next_spec
+#[macro_export]
+macro_rules! opts {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident($($spec:tt)*)),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant,)*
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            /// The [`OptSpec`][$crate::OptSpec]s of all options, in
+            /// declaration order.
+            pub const SPECS: &'static [$crate::OptSpec] = &[$($crate::__opt_spec!($($spec)*),)*];
+
+            /// All options, in declaration order.
+            pub const ALL: &'static [Self] = &[$(Self::$variant,)*];
+
+            /// Returns the [`OptSpec`][$crate::OptSpec] of this option.
+            pub fn spec(self) -> &'static $crate::OptSpec {
+                &Self::SPECS[self as usize]
+            }
+
+            /// Returns the option that `opt` is one of the names of,
+            /// if any.
+            pub fn from_opt<A>(opt: &$crate::Opt<A>) -> ::core::option::Option<Self>
+            where
+                A: $crate::Argument<ShortOpt = char> + ::core::cmp::PartialEq<&'static str>,
+            {
+                Self::ALL.iter().copied().find(|this| this.spec().matches(opt))
+            }
+
+            /// Retrieves the next option and its value, if it takes
+            /// one. See [`Options::next_spec`][$crate::Options::next_spec].
+            pub fn next_opt<A, I>(
+                opts: &mut $crate::Options<A, I>,
+            ) -> $crate::Result<A, ::core::option::Option<(Self, ::core::option::Option<A>)>>
+            where
+                A: $crate::Argument<ShortOpt = char> + ::core::cmp::PartialEq<&'static str>,
+                I: ::core::iter::Iterator<Item = A>,
+            {
+                ::core::result::Result::Ok(opts.next_spec(Self::SPECS)?.map(|(opt, values)| {
+                    (Self::from_opt(&opt).unwrap(), values.into_first())
+                }))
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __opt_spec {
+    (- $short:tt -- $long:tt $($value:ident)?) => {
+        $crate::OptSpec::new()
+            .short($short)
+            .long($long)
+            .takes_value($crate::__opt_spec!(@value $($value)?))
+    };
+    (- $short:tt $($value:ident)?) => {
+        $crate::OptSpec::new()
+            .short($short)
+            .takes_value($crate::__opt_spec!(@value $($value)?))
+    };
+    (-- $long:tt $($value:ident)?) => {
+        $crate::OptSpec::new()
+            .long($long)
+            .takes_value($crate::__opt_spec!(@value $($value)?))
+    };
+    (@value) => {
+        false
+    };
+    (@value value) => {
+        true
+    };
+}
+
+/// Matches an [`Opt`] against arms of short and long names, like
+/// `('o' | "output") => ...`.
+///
+/// Each arm lists the names of one option in parentheses, separated by
+/// `|`. Short names are [`char`] or [`u8`] literals, like `'o'` or
+/// `b'o'`, and long names are string or byte string literals, like
+/// `"output"` or `b"output"`, so an arm can't get its short and long
+/// [`Opt`] patterns out of sync. An arm can have any number of names.
+///
+/// The macro evaluates to a [`Result`]: [`Ok`] with the value of the
+/// first arm that matches, or [`Error::UnknownOption`] if none do, so
+/// that every parsing loop reports unknown options the same way.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{opt_match, Error, Opt, Options};
+/// #
+/// let args = ["-v", "--output", "out.txt", "--colour"];
+/// let mut opts = Options::new(args.into_iter());
+/// let mut verbose = false;
+/// let mut output = None;
+///
+/// let result = (|| {
+///     while let Some(opt) = opts.next_opt()? {
+///         opt_match! { opt,
+///             ('v' | "verbose") => verbose = true,
+///             ('o' | "output") => output = Some(opts.value()?),
+///             ("dry-run") => {}
+///         }?;
+///     }
+///
+///     Ok(())
+/// })();
+///
+/// assert!(verbose);
+/// assert_eq!(output, Some("out.txt"));
+/// assert_eq!(result, Err(Error::UnknownOption(Opt::Long("colour"))));
+/// ```
+///
+/// [`Opt`]: crate::Opt
+/// [`Error::UnknownOption`]: crate::Error::UnknownOption
+#[macro_export]
+macro_rules! opt_match {
+    ($opt:expr, $(($($name:literal)|+) => $body:expr),* $(,)?) => {
+        match $opt {
+            opt => {
+                $(if $($crate::__OptName::__is_name_of(&$name, &opt))||+ {
+                    ::core::result::Result::Ok($body)
+                } else)* {
+                    ::core::result::Result::Err($crate::Error::UnknownOption(opt))
+                }
+            }
+        }
+    };
+}
+
+/// A literal that can be used as the name of an option in
+/// [`opt_match!`].
+#[doc(hidden)]
+pub trait __OptName<A: Argument> {
+    fn __is_name_of(&self, opt: &Opt<A>) -> bool;
+}
+
+impl<A: Argument<ShortOpt = char>> __OptName<A> for char {
+    fn __is_name_of(&self, opt: &Opt<A>) -> bool {
+        matches!(opt, Opt::Short(short) if short == self)
+    }
+}
+
+impl<A: Argument<ShortOpt = u8>> __OptName<A> for u8 {
+    fn __is_name_of(&self, opt: &Opt<A>) -> bool {
+        matches!(opt, Opt::Short(short) if short == self)
+    }
+}
+
+impl<A: Argument + for<'a> PartialEq<&'a str>> __OptName<A> for &'_ str {
+    fn __is_name_of(&self, opt: &Opt<A>) -> bool {
+        matches!(opt, Opt::Long(long) if *long == *self)
+    }
+}
+
+impl<A: Argument + for<'a> PartialEq<&'a [u8]>, const N: usize> __OptName<A> for &'_ [u8; N] {
+    fn __is_name_of(&self, opt: &Opt<A>) -> bool {
+        matches!(opt, Opt::Long(long) if *long == &self[..])
+    }
+}
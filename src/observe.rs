@@ -0,0 +1,499 @@
+use crate::{Arg, Argument, Deprecated, Error, Opt, OptSpec, Options, Result};
+#[cfg(feature = "std")]
+use core::fmt::{self, Display, Formatter, Write};
+
+/// A hook that is notified of every event produced by an [`Observed`]
+/// parser.
+///
+/// All methods have empty default implementations, so you only need to
+/// implement the ones you are interested in. This can be used to record
+/// usage metrics or audit logs for every invocation without adding
+/// bookkeeping to each arm of your parsing loop.
+///
+/// Observers receive events in the order they are produced. Values
+/// are reported together with the option they belong to.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, Options, ParseObserver};
+/// #
+/// #[derive(Default)]
+/// struct Count {
+///     opts: usize,
+///     positionals: usize,
+/// }
+///
+/// impl<'a> ParseObserver<&'a str> for Count {
+///     fn on_opt(&mut self, _opt: Opt<&'a str>) {
+///         self.opts += 1;
+///     }
+///
+///     fn on_positional(&mut self, _positional: &'a str) {
+///         self.positionals += 1;
+///     }
+/// }
+///
+/// let args = ["-a", "--bee", "foo", "-c"];
+/// let mut count = Count::default();
+/// let mut opts = Options::new(args.into_iter()).observe(&mut count);
+///
+/// while opts.next_arg()?.is_some() {}
+///
+/// assert_eq!(count.opts, 3);
+/// assert_eq!(count.positionals, 1);
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+pub trait ParseObserver<A: Argument> {
+    /// Called when an option is parsed.
+    fn on_opt(&mut self, opt: Opt<A>) {
+        let _ = opt;
+    }
+
+    /// Called when a value is retrieved for an option.
+    fn on_value(&mut self, opt: Opt<A>, value: A) {
+        let _ = (opt, value);
+    }
+
+    /// Called when a positional argument is retrieved.
+    fn on_positional(&mut self, positional: A) {
+        let _ = positional;
+    }
+
+    /// Called when parsing produces an error.
+    fn on_error(&mut self, error: &Error<A>) {
+        let _ = error;
+    }
+}
+
+impl<A: Argument, O: ParseObserver<A> + ?Sized> ParseObserver<A> for &'_ mut O {
+    fn on_opt(&mut self, opt: Opt<A>) {
+        (**self).on_opt(opt)
+    }
+
+    fn on_value(&mut self, opt: Opt<A>, value: A) {
+        (**self).on_value(opt, value)
+    }
+
+    fn on_positional(&mut self, positional: A) {
+        (**self).on_positional(positional)
+    }
+
+    fn on_error(&mut self, error: &Error<A>) {
+        (**self).on_error(error)
+    }
+}
+
+/// An [`Options`] that reports every parse event to a
+/// [`ParseObserver`].
+///
+/// This type can be obtained by calling [`Options::observe`]. It
+/// offers the same parsing methods as [`Options`], which behave
+/// identically except that their results are also passed to the
+/// observer.
+#[derive(Copy, Clone, Debug)]
+pub struct Observed<A: Argument, I: Iterator<Item = A>, O: ParseObserver<A>> {
+    opts: Options<A, I>,
+    observer: O,
+    last_opt: Option<Opt<A>>,
+}
+
+impl<A: Argument, I: Iterator<Item = A>, O: ParseObserver<A>> Observed<A, I, O> {
+    pub(crate) fn new(opts: Options<A, I>, observer: O) -> Self {
+        Self {
+            opts,
+            observer,
+            last_opt: None,
+        }
+    }
+
+    fn observe_error<T>(&mut self, result: Result<A, T>) -> Result<A, T> {
+        if let Err(error) = &result {
+            self.observer.on_error(error);
+        }
+
+        result
+    }
+
+    /// Like [`Options::next_opt`], but reports the option or error to
+    /// the observer.
+    pub fn next_opt(&mut self) -> Result<A, Option<Opt<A>>> {
+        let result = self.opts.next_opt();
+
+        if let Ok(Some(opt)) = &result {
+            self.last_opt = Some(opt.clone());
+            self.observer.on_opt(opt.clone());
+        }
+
+        self.observe_error(result)
+    }
+
+    /// Like [`Options::next_arg`], but reports the option, positional
+    /// argument or error to the observer.
+    pub fn next_arg(&mut self) -> Result<A, Option<Arg<A>>> {
+        let result = self.opts.next_arg();
+
+        match &result {
+            Ok(Some(Arg::Positional(positional))) => {
+                self.observer.on_positional(positional.clone())
+            }
+            Ok(Some(arg)) => {
+                let opt = arg.clone().opt().unwrap();
+                self.last_opt = Some(opt.clone());
+                self.observer.on_opt(opt);
+            }
+            _ => {}
+        }
+
+        self.observe_error(result)
+    }
+
+    /// Like [`Options::value`], but reports the value or error to the
+    /// observer.
+    pub fn value(&mut self) -> Result<A, A> {
+        let result = self.opts.value();
+
+        if let (Ok(value), Some(opt)) = (&result, &self.last_opt) {
+            self.observer.on_value(opt.clone(), value.clone());
+        }
+
+        self.observe_error(result)
+    }
+
+    /// Like [`Options::value_opt`], but reports the value (if any) to
+    /// the observer.
+    pub fn value_opt(&mut self) -> Option<A> {
+        let value = self.opts.value_opt();
+
+        if let (Some(value), Some(opt)) = (&value, &self.last_opt) {
+            self.observer.on_value(opt.clone(), value.clone());
+        }
+
+        value
+    }
+
+    /// Like [`Options::next_positional`], but reports the positional
+    /// argument to the observer.
+    pub fn next_positional(&mut self) -> Option<A> {
+        let positional = self.opts.next_positional();
+
+        if let Some(positional) = &positional {
+            self.observer.on_positional(positional.clone());
+        }
+
+        positional
+    }
+
+    /// Returns [`Options::opts_ended`] for the wrapped [`Options`].
+    pub fn opts_ended(&self) -> bool {
+        self.opts.opts_ended()
+    }
+
+    /// Returns [`Options::is_empty`] for the wrapped [`Options`].
+    pub fn is_empty(&self) -> bool {
+        self.opts.is_empty()
+    }
+
+    /// Returns a reference to the observer.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Returns a mutable reference to the observer.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Consumes this [`Observed`], returning the wrapped [`Options`]
+    /// and the observer.
+    pub fn into_inner(self) -> (Options<A, I>, O) {
+        (self.opts, self.observer)
+    }
+}
+
+/// A [`ParseObserver`] that counts how many times an option is used,
+/// for flags like `-vvv` that can be repeated.
+///
+/// Every name of the option counts, so `-vv --verbose` counts three
+/// times. The count so far can be retrieved while parsing through
+/// [`Observed::observer`], or afterwards with [`Occurrences::count`].
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Occurrences, OptSpec, Options};
+/// #
+/// const VERBOSE: OptSpec = OptSpec::new().short('v').long("verbose");
+///
+/// let args = ["-vv", "-q", "--verbose"];
+/// let mut opts = Options::new(args.into_iter()).observe(Occurrences::new(&VERBOSE));
+///
+/// while opts.next_opt()?.is_some() {
+///     if opts.observer().count() == 2 {
+///         // ...
+///     }
+/// }
+///
+/// assert_eq!(opts.observer().count(), 3);
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Occurrences<'s, S = char, L = &'static str> {
+    spec: &'s OptSpec<S, L>,
+    count: usize,
+}
+
+impl<'s, S, L> Occurrences<'s, S, L> {
+    /// Creates a new [`Occurrences`] that counts the option described
+    /// by `spec`.
+    pub const fn new(spec: &'s OptSpec<S, L>) -> Self {
+        Self { spec, count: 0 }
+    }
+
+    /// Returns the number of times the option has been used so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the [`OptSpec`] of the option being counted.
+    pub fn spec(&self) -> &'s OptSpec<S, L> {
+        self.spec
+    }
+}
+
+impl<S: Copy + Eq, L: Copy, A: Argument<ShortOpt = S> + PartialEq<L>> ParseObserver<A>
+    for Occurrences<'_, S, L>
+{
+    fn on_opt(&mut self, opt: Opt<A>) {
+        if self.spec.matches(&opt) {
+            self.count += 1;
+        }
+    }
+}
+
+/// A [`ParseObserver`] that reports every use of a
+/// [deprecated][OptSpec#structfield.deprecated] option in a table of
+/// [`OptSpec`]s by calling a function with a [`Deprecated`] warning.
+///
+/// This keeps deprecation warnings consistent across a program without
+/// checking for deprecated options in each arm of its parsing loops.
+/// Options that are not in the table are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Deprecated, Deprecations, OptSpec, Options};
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().long("color"),
+///     OptSpec::new().long("colour").deprecated(true).replaced_by("color"),
+///     OptSpec::new().short('q').deprecated(true),
+/// ];
+///
+/// let mut warnings = Vec::new();
+/// let deprecations = Deprecations::new(SPECS, |warning: Deprecated<&str>| {
+///     warnings.push(warning.to_string());
+/// });
+///
+/// let args = ["--colour", "--color", "-q"];
+/// let mut opts = Options::new(args.into_iter()).observe(deprecations);
+///
+/// while opts.next_opt()?.is_some() {}
+///
+/// assert_eq!(
+///     warnings,
+///     ["--colour is deprecated, use --color instead", "-q is deprecated"]
+/// );
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Deprecations<'s, S, L, F> {
+    specs: &'s [OptSpec<S, L>],
+    warn: F,
+}
+
+impl<'s, S, L, F> Deprecations<'s, S, L, F> {
+    /// Creates a new [`Deprecations`] that calls `warn` whenever a
+    /// deprecated option in `specs` is used.
+    pub const fn new(specs: &'s [OptSpec<S, L>], warn: F) -> Self {
+        Self { specs, warn }
+    }
+
+    /// Returns the table of [`OptSpec`]s that options are checked
+    /// against.
+    pub fn specs(&self) -> &'s [OptSpec<S, L>] {
+        self.specs
+    }
+
+    /// Consumes this [`Deprecations`], returning the function that
+    /// warnings are passed to.
+    pub fn into_inner(self) -> F {
+        self.warn
+    }
+}
+
+impl<'s, S, L, A, F> ParseObserver<A> for Deprecations<'s, S, L, F>
+where
+    S: Copy + Eq,
+    L: Copy,
+    A: Argument<ShortOpt = S> + PartialEq<L>,
+    F: FnMut(Deprecated<'s, A, S, L>),
+{
+    fn on_opt(&mut self, opt: Opt<A>) {
+        let spec = self.specs.iter().find(|spec| spec.matches(&opt));
+
+        if let Some(warning) = spec.and_then(|spec| spec.deprecation(opt)) {
+            (self.warn)(warning);
+        }
+    }
+}
+
+/// A [`ParseObserver`] that writes every parse event to a writer as a
+/// line of JSON.
+///
+/// This is useful for wrapper scripts and test harnesses that need to
+/// see exactly how a command line was interpreted. Each event is an
+/// object with an `"event"` field of `"opt"`, `"value"`,
+/// `"positional"` or `"error"`:
+///
+/// ```text
+/// {"event":"opt","opt":"-o"}
+/// {"event":"value","opt":"-o","value":"out.txt"}
+/// {"event":"positional","positional":"in.txt"}
+/// {"event":"error","error":"option requires a value: --file"}
+/// ```
+///
+/// Arguments are written using their [`Display`] implementations, so
+/// this observer can only be used with arguments like [`&str`] that
+/// implement it.
+///
+/// Since observers cannot return errors, the first I/O error is stored
+/// and can be retrieved with [`JsonLines::take_error`]. No further
+/// events are written after an error.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{JsonLines, Options};
+/// #
+/// let args = ["-o", "out.txt", "in.txt"];
+/// let mut opts = Options::new(args.into_iter()).observe(JsonLines::new(Vec::new()));
+///
+/// while let Some(arg) = opts.next_arg()? {
+///     if arg.opt().is_some() {
+///         opts.value()?;
+///     }
+/// }
+///
+/// let (_, json) = opts.into_inner();
+/// let output = String::from_utf8(json.into_inner()).unwrap();
+///
+/// assert_eq!(
+///     output,
+///     r#"{"event":"opt","opt":"-o"}
+/// {"event":"value","opt":"-o","value":"out.txt"}
+/// {"event":"positional","positional":"in.txt"}
+/// "#
+/// );
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct JsonLines<W: std::io::Write> {
+    writer: W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> JsonLines<W> {
+    /// Creates a new [`JsonLines`] that writes to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Returns the first I/O error that occurred while writing, if any.
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+
+    /// Consumes this [`JsonLines`], returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn emit(&mut self, args: fmt::Arguments) {
+        if self.error.is_none() {
+            if let Err(error) = self.writer.write_fmt(args) {
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Display, A: Argument<ShortOpt = S> + Display, W: std::io::Write> ParseObserver<A>
+    for JsonLines<W>
+{
+    fn on_opt(&mut self, opt: Opt<A>) {
+        self.emit(format_args!(
+            "{{\"event\":\"opt\",\"opt\":\"{}\"}}\n",
+            Escaped(opt)
+        ));
+    }
+
+    fn on_value(&mut self, opt: Opt<A>, value: A) {
+        self.emit(format_args!(
+            "{{\"event\":\"value\",\"opt\":\"{}\",\"value\":\"{}\"}}\n",
+            Escaped(opt),
+            Escaped(value)
+        ));
+    }
+
+    fn on_positional(&mut self, positional: A) {
+        self.emit(format_args!(
+            "{{\"event\":\"positional\",\"positional\":\"{}\"}}\n",
+            Escaped(positional)
+        ));
+    }
+
+    fn on_error(&mut self, error: &Error<A>) {
+        self.emit(format_args!(
+            "{{\"event\":\"error\",\"error\":\"{}\"}}\n",
+            Escaped(error)
+        ));
+    }
+}
+
+/// Displays the wrapped value as the contents of a JSON string.
+#[cfg(feature = "std")]
+struct Escaped<T>(T);
+
+#[cfg(feature = "std")]
+impl<T: Display> Display for Escaped<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        struct Escaper<'a, 'b>(&'a mut Formatter<'b>);
+
+        impl Write for Escaper<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for ch in s.chars() {
+                    match ch {
+                        '"' => self.0.write_str("\\\"")?,
+                        '\\' => self.0.write_str("\\\\")?,
+                        '\n' => self.0.write_str("\\n")?,
+                        '\r' => self.0.write_str("\\r")?,
+                        '\t' => self.0.write_str("\\t")?,
+                        ch if (ch as u32) < 0x20 => write!(self.0, "\\u{:04x}", ch as u32)?,
+                        ch => self.0.write_char(ch)?,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+
+        write!(Escaper(f), "{}", self.0)
+    }
+}
@@ -7,12 +7,87 @@ use crate::{Arg, Argument};
 /// This enum can be returned by calls to
 /// [`Options::next_opt`][crate::Options::next_opt] and represents a
 /// short or long command-line option name (but not value).
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+///
+/// [`Opt`] implements [`Hash`] and [`Ord`], so it can be used as a key
+/// in a `HashMap` or `BTreeMap`. Options are ordered by variant in the
+/// order they are declared, and then by name.
+///
+/// With the `serde` feature, [`Opt`], [`Arg`] and
+/// [`Error`][crate::Error] implement `Serialize` and `Deserialize`.
+///
+/// Options other than [`Opt::Short`] and [`Opt::Long`] are only returned
+/// when they are enabled in the [`ParseConfig`][crate::ParseConfig].
+/// This enum is `#[non_exhaustive]` so that more of them can be added
+/// that way, which means that a `match` on it needs a `_` arm.
+#[non_exhaustive]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Opt<A: Argument> {
     /// A short option, like `-f`. Does not include the leading `-`.
     Short(A::ShortOpt),
     /// A long option, like `--file`. Does not include the leading `--`.
     Long(A),
+    /// A short option with a leading `+` instead of `-`, like `+x`,
+    /// which is conventionally used to turn an option off. Does not
+    /// include the leading `+`. Only returned when
+    /// [`ParseConfig::plus_opts`][crate::ParseConfig#structfield.plus_opts]
+    /// is enabled.
+    ShortPlus(A::ShortOpt),
+    /// A numeric option, like `-5` in `head -5`. Contains the digits
+    /// without the leading `-`. Only returned when
+    /// [`ParseConfig::numeric_opts`][crate::ParseConfig#structfield.numeric_opts]
+    /// is enabled.
+    Numeric(A),
+}
+
+impl<A: Argument> Opt<A> {
+    /// Returns `true` if this option is the short option `short` or the
+    /// long option `long`, so that a short and long name for the same
+    /// option can be checked at once. [`Opt::ShortPlus`] and
+    /// [`Opt::Numeric`] never match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::Opt;
+    /// #
+    /// assert!(Opt::<&str>::Short('o').is('o', "output"));
+    /// assert!(Opt::Long("output").is('o', "output"));
+    /// assert!(!Opt::Long("o").is('o', "output"));
+    /// assert!(!Opt::<&str>::ShortPlus('o').is('o', "output"));
+    /// ```
+    pub fn is<L>(&self, short: A::ShortOpt, long: L) -> bool
+    where
+        A: PartialEq<L>,
+    {
+        match self {
+            Opt::Short(s) => *s == short,
+            Opt::Long(l) => *l == long,
+            _ => false,
+        }
+    }
+}
+
+impl Opt<&'_ str> {
+    /// Returns `true` if this option is `-h` or `--help`.
+    ///
+    /// This can be passed to
+    /// [`Options::help_requested`][crate::Options::help_requested] or
+    /// [`Options::set_help_opt`][crate::Options::set_help_opt].
+    pub fn is_help(self) -> bool {
+        matches!(self, Opt::Short('h') | Opt::Long("help"))
+    }
+}
+
+impl Opt<&'_ [u8]> {
+    /// Returns `true` if this option is `-h` or `--help`.
+    ///
+    /// This can be passed to
+    /// [`Options::help_requested`][crate::Options::help_requested] or
+    /// [`Options::set_help_opt`][crate::Options::set_help_opt].
+    pub fn is_help(self) -> bool {
+        matches!(self, Opt::Short(b'h') | Opt::Long(b"help"))
+    }
 }
 
 impl<A: Argument> TryFrom<Arg<A>> for Opt<A> {
@@ -22,6 +97,8 @@ impl<A: Argument> TryFrom<Arg<A>> for Opt<A> {
         match value {
             Arg::Short(short) => Ok(Self::Short(short)),
             Arg::Long(long) => Ok(Self::Long(long)),
+            Arg::ShortPlus(short) => Ok(Self::ShortPlus(short)),
+            Arg::Numeric(digits) => Ok(Self::Numeric(digits)),
             _ => Err(()),
         }
     }
@@ -32,6 +109,40 @@ impl<S: Display, A: Argument<ShortOpt = S> + Display> Display for Opt<A> {
         match self {
             Opt::Short(c) => write!(f, "-{}", c),
             Opt::Long(s) => write!(f, "--{}", s),
+            Opt::ShortPlus(c) => write!(f, "+{}", c),
+            Opt::Numeric(s) => write!(f, "-{}", s),
         }
     }
 }
+
+/// Formats an [`Opt`] like its [`Display`] implementation. This
+/// requires the `defmt` feature.
+#[cfg(feature = "defmt")]
+impl<S: defmt::Format, A: Argument<ShortOpt = S> + defmt::Format> defmt::Format for Opt<A> {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Opt::Short(c) => defmt::write!(f, "-{}", c),
+            Opt::Long(s) => defmt::write!(f, "--{}", s),
+            Opt::ShortPlus(c) => defmt::write!(f, "+{}", c),
+            Opt::Numeric(s) => defmt::write!(f, "-{}", s),
+        }
+    }
+}
+
+/// Generates any kind of [`Opt`] with an arbitrary name. This requires
+/// the `arbitrary` feature.
+#[cfg(feature = "arbitrary")]
+impl<'a, S, A> arbitrary::Arbitrary<'a> for Opt<A>
+where
+    S: arbitrary::Arbitrary<'a>,
+    A: Argument<ShortOpt = S> + arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=3)? {
+            0 => Opt::Short(u.arbitrary()?),
+            1 => Opt::Long(u.arbitrary()?),
+            2 => Opt::ShortPlus(u.arbitrary()?),
+            _ => Opt::Numeric(u.arbitrary()?),
+        })
+    }
+}
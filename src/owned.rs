@@ -0,0 +1,237 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::{Arg, Argument, Error, Opt};
+
+/// Arguments that can be converted into an owned, `'static` form, so
+/// that they can outlive the buffer they were parsed from.
+///
+/// This is implemented for [`&str`], [`String`] and [`Cow<str>`], which
+/// become [`String`], and for [`&[u8]`][slice] and [`Vec<u8>`], which
+/// become [`Vec<u8>`]. It requires the `alloc` feature.
+pub trait IntoOwnedArgument: Argument {
+    /// The owned form of this argument.
+    type Owned: Argument<ShortOpt = Self::ShortOpt> + Send + Sync + 'static;
+
+    /// Converts this argument into its owned form.
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl IntoOwnedArgument for &'_ str {
+    type Owned = String;
+
+    fn into_owned(self) -> String {
+        self.into()
+    }
+}
+
+impl IntoOwnedArgument for String {
+    type Owned = String;
+
+    fn into_owned(self) -> String {
+        self
+    }
+}
+
+impl IntoOwnedArgument for Cow<'_, str> {
+    type Owned = String;
+
+    fn into_owned(self) -> String {
+        Cow::into_owned(self)
+    }
+}
+
+impl IntoOwnedArgument for &'_ [u8] {
+    type Owned = Vec<u8>;
+
+    fn into_owned(self) -> Vec<u8> {
+        self.into()
+    }
+}
+
+impl IntoOwnedArgument for Vec<u8> {
+    type Owned = Vec<u8>;
+
+    fn into_owned(self) -> Vec<u8> {
+        self
+    }
+}
+
+impl<A: IntoOwnedArgument> Opt<A> {
+    /// Converts this option into one that owns its argument, so that it
+    /// can outlive the arguments being parsed. Requires the `alloc`
+    /// feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::Opt;
+    /// #
+    /// let opt = Opt::Long("verbose").into_owned();
+    ///
+    /// assert_eq!(opt, Opt::Long(String::from("verbose")));
+    /// ```
+    pub fn into_owned(self) -> Opt<A::Owned> {
+        map_opt(self, A::into_owned, |short| short)
+    }
+}
+
+impl<A: IntoOwnedArgument> Arg<A> {
+    /// Converts this argument into one that owns its contents, so that
+    /// it can outlive the arguments being parsed. Requires the `alloc`
+    /// feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::Arg;
+    /// #
+    /// let arg = Arg::Positional(&b"file.txt"[..]).into_owned();
+    ///
+    /// assert_eq!(arg, Arg::Positional(b"file.txt".to_vec()));
+    /// ```
+    pub fn into_owned(self) -> Arg<A::Owned> {
+        match self {
+            Arg::Positional(arg) => Arg::Positional(arg.into_owned()),
+            arg => arg.opt().unwrap().into_owned().into(),
+        }
+    }
+}
+
+/// An [`Error`] that owns its arguments, so that it can be stored or
+/// returned from functions that outlive the arguments being parsed.
+///
+/// This type can be obtained by calling [`Error::into_owned`], or by
+/// converting an [`Error`] with [`From`]. [`ErrorOwned<String>`] and
+/// [`ErrorOwned<Vec<u8>>`] implement [`Display`] and
+/// [`core::error::Error`]; byte arguments are displayed lossily. It
+/// requires the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{ErrorOwned, Opt, Options};
+/// #
+/// fn parse(args: &[String]) -> Result<bool, ErrorOwned<String>> {
+///     let mut opts = Options::new(args.iter().map(String::as_str));
+///     let mut verbose = false;
+///
+///     while let Some(opt) = opts.next_opt()? {
+///         match opt {
+///             Opt::Short('v') => verbose = true,
+///             _ => return Err(getargs::Error::UnknownOption(opt).into()),
+///         }
+///     }
+///
+///     Ok(verbose)
+/// }
+///
+/// let error = parse(&["-x".into()]).unwrap_err();
+///
+/// assert_eq!(error.to_string(), "unknown option: -x");
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ErrorOwned<O: Argument>(pub Error<O>);
+
+impl<A: IntoOwnedArgument> Error<A> {
+    /// Converts this error into an [`ErrorOwned`], which does not
+    /// borrow from the arguments. Requires the `alloc` feature.
+    pub fn into_owned(self) -> ErrorOwned<A::Owned> {
+        ErrorOwned(map_error(self, A::into_owned, |short| short))
+    }
+}
+
+impl<A: IntoOwnedArgument> From<Error<A>> for ErrorOwned<A::Owned> {
+    fn from(error: Error<A>) -> Self {
+        error.into_owned()
+    }
+}
+
+impl<O: Argument> ErrorOwned<O> {
+    /// Returns the conventional [`sysexits`][crate::sysexits] exit code
+    /// for this error. See [`Error::exit_code`].
+    pub fn exit_code(&self) -> u8 {
+        self.0.exit_code()
+    }
+}
+
+impl Display for ErrorOwned<String> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Display for ErrorOwned<Vec<u8>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let lossy = map_error(
+            self.0.clone(),
+            |bytes| String::from_utf8_lossy(&bytes).into_owned(),
+            char::from,
+        );
+
+        lossy.fmt(f)
+    }
+}
+
+impl core::error::Error for ErrorOwned<String> {}
+
+impl core::error::Error for ErrorOwned<Vec<u8>> {}
+
+/// Converts the arguments and short options in `opt` with `arg` and
+/// `short`.
+fn map_opt<A: Argument, B: Argument>(
+    opt: Opt<A>,
+    arg: impl FnOnce(A) -> B,
+    short: impl FnOnce(A::ShortOpt) -> B::ShortOpt,
+) -> Opt<B> {
+    match opt {
+        Opt::Short(s) => Opt::Short(short(s)),
+        Opt::Long(long) => Opt::Long(arg(long)),
+        Opt::ShortPlus(s) => Opt::ShortPlus(short(s)),
+        Opt::Numeric(digits) => Opt::Numeric(arg(digits)),
+    }
+}
+
+/// Converts the arguments and short options in `error` with `arg` and
+/// `short`.
+fn map_error<A: Argument, B: Argument>(
+    error: Error<A>,
+    arg: impl FnOnce(A) -> B,
+    short: impl FnOnce(A::ShortOpt) -> B::ShortOpt,
+) -> Error<B> {
+    match error {
+        Error::RequiresValue(opt) => Error::RequiresValue(map_opt(opt, arg, short)),
+        Error::DoesNotRequireValue(opt) => Error::DoesNotRequireValue(map_opt(opt, arg, short)),
+        Error::EmptyValue(opt) => Error::EmptyValue(map_opt(opt, arg, short)),
+        Error::LoneDash => Error::LoneDash,
+        Error::RequiresValues {
+            opt,
+            expected,
+            found,
+        } => Error::RequiresValues {
+            opt: map_opt(opt, arg, short),
+            expected,
+            found,
+        },
+        Error::TooFewPositionals { expected, found } => {
+            Error::TooFewPositionals { expected, found }
+        }
+        Error::TooManyPositionals { capacity } => Error::TooManyPositionals { capacity },
+        Error::TooManyValues { opt, capacity } => Error::TooManyValues {
+            opt: map_opt(opt, arg, short),
+            capacity,
+        },
+        Error::UnknownOption(opt) => Error::UnknownOption(map_opt(opt, arg, short)),
+        Error::UnknownSubcommand(name) => Error::UnknownSubcommand(arg(name)),
+        Error::InvalidUtf8 {
+            arg_index,
+            valid_up_to,
+        } => Error::InvalidUtf8 {
+            arg_index,
+            valid_up_to,
+        },
+        Error::HelpRequested(opt) => Error::HelpRequested(map_opt(opt, arg, short)),
+    }
+}
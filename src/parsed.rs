@@ -0,0 +1,405 @@
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::{Arg, Argument, Error, Opt, OptSpec, OptValue, Options, Result, SpecTable, ValueError};
+
+/// All of the options and positional arguments of a command line,
+/// collected according to a table of [`OptSpec`]s.
+///
+/// [`ParsedArgs::parse`] runs the whole parsing loop up front, for
+/// programs that would rather look options up by name afterwards than
+/// handle them one at a time. Options are looked up by their
+/// [long name][OptSpec#structfield.long] or
+/// [short name][OptSpec#structfield.short], either of which refers to
+/// any spelling of that option. [Negatable][OptSpec#structfield.negatable]
+/// options can be turned off with `--no-`, and
+/// [default values][OptSpec#structfield.default_value] are used for
+/// options that were not given. Options with an
+/// [arity][OptSpec#structfield.arity] of more than 1 consume all of
+/// their values, which can be looked up together with
+/// [`ParsedArgs::group`]. The streaming API of [`Options`] is
+/// unaffected.
+///
+/// This type requires the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{OptSpec, OptValue, Options, ParsedArgs};
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().short('v').long("verbose"),
+///     OptSpec::new().short('I').long("include").takes_value(true),
+///     OptSpec::new().long("color").negatable(true),
+///     OptSpec::new().short('j').takes_value(true).default_value("1"),
+///     OptSpec::new().long("dry-run"),
+/// ];
+///
+/// let args = ["-v", "-Isrc", "main.c", "--include", "lib", "--no-color", "util.c"];
+/// let parsed = ParsedArgs::parse(Options::new(args.into_iter()), SPECS)?;
+///
+/// assert!(parsed.flag("verbose"));
+/// assert!(parsed.flag('v'));
+/// assert!(!parsed.flag("dry-run"));
+/// assert_eq!(parsed.enabled("color"), Some(false));
+/// assert_eq!(parsed.values("include").collect::<Vec<_>>(), [&"src", &"lib"]);
+/// assert_eq!(parsed.value('j'), Some(OptValue::Default("1")));
+/// assert_eq!(parsed.positionals(), ["main.c", "util.c"]);
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParsedArgs<'s, A: Argument, L = &'static str> {
+    specs: &'s [OptSpec<A::ShortOpt, L>],
+    /// Every option that was given, as the index of its spec, the
+    /// option as it was spelled, whether it was turned on rather than
+    /// off with `--no-`, and its values.
+    opts: Vec<(usize, Opt<A>, bool, Vec<A>)>,
+    positionals: Vec<A>,
+}
+
+/// A name that options can be looked up by in a [`ParsedArgs`]: a long
+/// name, like `"verbose"`, or a short name, like `'v'`.
+///
+/// This is implemented for the long and short option types of [`&str`]
+/// and [`&[u8]`][slice] arguments.
+pub trait OptName<S, L> {
+    /// Returns `true` if this is one of the names of `spec`.
+    fn names(&self, spec: &OptSpec<S, L>) -> bool;
+}
+
+impl<S, L: PartialEq<Self>> OptName<S, L> for &str {
+    fn names(&self, spec: &OptSpec<S, L>) -> bool {
+        spec.long.as_ref().is_some_and(|long| long == self)
+    }
+}
+
+impl<S, L: PartialEq<Self>> OptName<S, L> for &[u8] {
+    fn names(&self, spec: &OptSpec<S, L>) -> bool {
+        spec.long.as_ref().is_some_and(|long| long == self)
+    }
+}
+
+impl<S, L: AsRef<[u8]>, const N: usize> OptName<S, L> for &[u8; N] {
+    fn names(&self, spec: &OptSpec<S, L>) -> bool {
+        spec.long
+            .as_ref()
+            .is_some_and(|long| long.as_ref() == *self)
+    }
+}
+
+impl<L> OptName<char, L> for char {
+    fn names(&self, spec: &OptSpec<char, L>) -> bool {
+        spec.short == Some(*self)
+    }
+}
+
+impl<L> OptName<u8, L> for u8 {
+    fn names(&self, spec: &OptSpec<u8, L>) -> bool {
+        spec.short == Some(*self)
+    }
+}
+
+impl<'s, A, L> ParsedArgs<'s, A, L>
+where
+    A: Argument + AsRef<[u8]>,
+    L: Copy + AsRef<[u8]>,
+{
+    /// Parses all of the arguments of `opts`, checking each option
+    /// against `specs` with [`SpecTable::find_flag`] and retrieving the
+    /// values of the options that take any, as many as their
+    /// [arity][OptSpec#structfield.arity]. Options that are turned off
+    /// with `--no-` don't take a value.
+    ///
+    /// Positional arguments are collected in order, including those
+    /// that appear between options. Returns the first error, such as
+    /// [`Error::UnknownOption`][crate::Error::UnknownOption] for an
+    /// option that is not in `specs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `specs` has more than [`SpecTable::MAX_SPECS`]
+    /// options.
+    pub fn parse<I: Iterator<Item = A>>(
+        mut opts: Options<A, I>,
+        specs: &'s [OptSpec<A::ShortOpt, L>],
+    ) -> Result<A, Self> {
+        let table = SpecTable::new(specs);
+        let mut parsed = Self {
+            specs,
+            opts: Vec::new(),
+            positionals: Vec::new(),
+        };
+
+        while let Some(arg) = opts.next_arg()? {
+            let opt = match arg {
+                Arg::Positional(positional) => {
+                    parsed.positionals.push(positional);
+                    continue;
+                }
+                arg => arg.opt().unwrap(),
+            };
+
+            // Only abbreviations can be ambiguous, and they are off.
+            let Ok(Some((spec, enabled))) = table.find_flag(&opt) else {
+                return Err(Error::UnknownOption(opt));
+            };
+
+            let values = match enabled {
+                true => opts.spec_values(&opt, spec)?.into_iter().collect(),
+                false => Vec::new(),
+            };
+
+            let index = specs.iter().position(|other| core::ptr::eq(other, spec));
+            parsed.opts.push((index.unwrap(), opt, enabled, values));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Returns `true` if the option named `name` was given, and its
+    /// last occurrence did not turn it off with `--no-`.
+    pub fn flag(&self, name: impl OptName<A::ShortOpt, L>) -> bool {
+        self.enabled(name) == Some(true)
+    }
+
+    /// Returns whether the last occurrence of the option named `name`
+    /// turned it on, or turned it off with `--no-` if it is
+    /// [negatable][OptSpec#structfield.negatable]. Returns [`None`] if
+    /// the option was not given at all, so that programs can fall back
+    /// to their own default.
+    pub fn enabled(&self, name: impl OptName<A::ShortOpt, L>) -> Option<bool> {
+        self.occurrences(name).last().map(|(_, enabled, _)| enabled)
+    }
+
+    /// Returns the number of times the option named `name` was given
+    /// without being turned off, for flags like `-vvv` that can be
+    /// repeated.
+    pub fn count(&self, name: impl OptName<A::ShortOpt, L>) -> usize {
+        self.occurrences(name)
+            .filter(|&(_, enabled, _)| enabled)
+            .count()
+    }
+
+    /// Returns the value of the last occurrence of the option named
+    /// `name`, or its [default value][OptSpec#structfield.default_value]
+    /// if it was not given. For an option with an
+    /// [arity][OptSpec#structfield.arity] of more than 1, this is the
+    /// first of its values.
+    ///
+    /// To also fall back to the option's
+    /// [environment variable][OptSpec#structfield.env], use
+    /// [`ParsedArgs::value_or_env`].
+    pub fn value(&self, name: impl OptName<A::ShortOpt, L>) -> Option<OptValue<&A, L>> {
+        let spec = self.spec(&name)?;
+        spec.value_or_default(self.last_value(name).map(|(_, value)| value))
+    }
+
+    /// Like [`ParsedArgs::value`], but if the option was not given, the
+    /// value of its [environment variable][OptSpec#structfield.env] is
+    /// used before falling back to its default value, like
+    /// [`OptSpec::value_or_env`].
+    ///
+    /// This method requires the `std` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::ffi::OsString;
+    /// # use getargs::{OptSpec, OptValue, Options, ParsedArgs};
+    /// #
+    /// const SPECS: &[OptSpec] = &[OptSpec::new()
+    ///     .long("cache-dir")
+    ///     .takes_value(true)
+    ///     .env("MYAPP_PARSED_CACHE_DIR")
+    ///     .default_value("/tmp/cache")];
+    ///
+    /// let parsed = ParsedArgs::parse(Options::new(std::iter::empty::<&str>()), SPECS)?;
+    ///
+    /// std::env::remove_var("MYAPP_PARSED_CACHE_DIR");
+    /// assert_eq!(parsed.value_or_env("cache-dir"), Some(OptValue::Default("/tmp/cache")));
+    ///
+    /// std::env::set_var("MYAPP_PARSED_CACHE_DIR", "/var/cache");
+    /// assert_eq!(
+    ///     parsed.value_or_env("cache-dir"),
+    ///     Some(OptValue::Env(OsString::from("/var/cache")))
+    /// );
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn value_or_env(
+        &self,
+        name: impl OptName<A::ShortOpt, L>,
+    ) -> Option<OptValue<&A, L, std::ffi::OsString>> {
+        let spec = self.spec(&name)?;
+        spec.value_or_env(self.last_value(name).map(|(_, value)| value))
+    }
+
+    /// Returns the values of every occurrence of the option named
+    /// `name`, in order.
+    pub fn values(&self, name: impl OptName<A::ShortOpt, L>) -> impl Iterator<Item = &A> + '_ {
+        self.occurrences(name).flat_map(|(_, _, values)| values)
+    }
+
+    /// Returns all of the values of the last occurrence of the option
+    /// named `name`, for options with an
+    /// [arity][OptSpec#structfield.arity] of more than 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{OptSpec, Options, ParsedArgs};
+    /// #
+    /// const SPECS: &[OptSpec] = &[
+    ///     OptSpec::new().long("point").arity(2),
+    /// ];
+    ///
+    /// let args = ["--point", "1", "2", "--point=3", "4"];
+    /// let parsed = ParsedArgs::parse(Options::new(args.into_iter()), SPECS)?;
+    ///
+    /// assert_eq!(parsed.group("point"), Some(&["3", "4"][..]));
+    /// assert_eq!(parsed.values("point").count(), 4);
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    pub fn group(&self, name: impl OptName<A::ShortOpt, L>) -> Option<&[A]> {
+        self.occurrences(name)
+            .filter(|(_, _, values)| !values.is_empty())
+            .last()
+            .map(|(_, _, values)| values)
+    }
+
+    /// Parses the value of the option named `name` with [`FromStr`],
+    /// like [`ParsedArgs::value`], returning `Ok(None)` if it was not
+    /// given and has no [default value][OptSpec#structfield.default_value].
+    ///
+    /// If the value can't be parsed, [`ValueError::InvalidValue`] is
+    /// returned with the option as it was spelled, the value and the
+    /// parse error, like [`Options::value_parsed`]. For arguments that
+    /// aren't strings, like `&[u8]`, use [`ParsedArgs::get_with`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the option was not given and its default value can't
+    /// be parsed, since that is a mistake in the table of [`OptSpec`]s
+    /// rather than in the arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{OptSpec, Options, ParsedArgs};
+    /// #
+    /// const SPECS: &[OptSpec] = &[
+    ///     OptSpec::new().short('p').long("port").takes_value(true),
+    ///     OptSpec::new().long("jobs").takes_value(true),
+    ///     OptSpec::new().long("host").takes_value(true),
+    ///     OptSpec::new().long("retries").takes_value(true).default_value("3"),
+    /// ];
+    ///
+    /// let args = ["--port=8080", "--jobs", "many"];
+    /// let parsed = ParsedArgs::parse(Options::new(args.into_iter()), SPECS)?;
+    ///
+    /// assert_eq!(parsed.get::<u16>("port"), Ok(Some(8080)));
+    /// assert_eq!(parsed.get::<String>("host"), Ok(None));
+    /// assert_eq!(parsed.get::<u8>("retries"), Ok(Some(3)));
+    /// assert_eq!(
+    ///     parsed.get::<u32>("jobs").unwrap_err().to_string(),
+    ///     "invalid value 'many' for --jobs: invalid digit found in string"
+    /// );
+    /// # Ok::<(), getargs::Error<&'static str>>(())
+    /// ```
+    pub fn get<T: FromStr>(
+        &self,
+        name: impl OptName<A::ShortOpt, L>,
+    ) -> core::result::Result<Option<T>, ValueError<A, T::Err>>
+    where
+        A: AsRef<str>,
+        L: AsRef<str>,
+    {
+        self.parse_value(name, |value| AsRef::<str>::as_ref(&value).parse())
+    }
+
+    /// Like [`ParsedArgs::get`], but parses the bytes of the value with
+    /// `parse` instead of using [`FromStr`], so it can be used with any
+    /// type of argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`ParsedArgs::get`].
+    pub fn get_with<T, E>(
+        &self,
+        name: impl OptName<A::ShortOpt, L>,
+        parse: impl FnOnce(&[u8]) -> core::result::Result<T, E>,
+    ) -> core::result::Result<Option<T>, ValueError<A, E>> {
+        self.parse_value(name, |value| parse(value.as_ref()))
+    }
+
+    /// Returns the positional arguments, in order.
+    pub fn positionals(&self) -> &[A] {
+        &self.positionals
+    }
+
+    /// Returns the table of [`OptSpec`]s that options were checked
+    /// against.
+    pub fn specs(&self) -> &'s [OptSpec<A::ShortOpt, L>] {
+        self.specs
+    }
+
+    /// Parses the value of the option named `name` with `parse`, for
+    /// [`ParsedArgs::get`] and [`ParsedArgs::get_with`].
+    fn parse_value<T, E>(
+        &self,
+        name: impl OptName<A::ShortOpt, L>,
+        parse: impl FnOnce(OptValue<&A, L>) -> core::result::Result<T, E>,
+    ) -> core::result::Result<Option<T>, ValueError<A, E>> {
+        let Some(spec) = self.spec(&name) else {
+            return Ok(None);
+        };
+
+        let Some((opt, value)) = self.last_value(name) else {
+            return match spec.default_value {
+                Some(default) => match parse(OptValue::Default(default)) {
+                    Ok(value) => Ok(Some(value)),
+                    Err(_) => panic!("invalid default value in getargs::OptSpec"),
+                },
+                None => Ok(None),
+            };
+        };
+
+        match parse(OptValue::Given(value)) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) => Err(ValueError::InvalidValue {
+                opt: opt.clone(),
+                value: value.clone(),
+                error,
+            }),
+        }
+    }
+
+    /// Returns the [`OptSpec`] of the option named `name`, if it is in
+    /// the table.
+    fn spec(&self, name: &impl OptName<A::ShortOpt, L>) -> Option<&'s OptSpec<A::ShortOpt, L>> {
+        self.specs.iter().find(|spec| name.names(spec))
+    }
+
+    /// Returns the last occurrence of the option named `name` that has
+    /// a value, along with the value.
+    fn last_value(&self, name: impl OptName<A::ShortOpt, L>) -> Option<(&Opt<A>, &A)> {
+        self.occurrences(name)
+            .filter_map(|(opt, _, values)| Some((opt, values.first()?)))
+            .last()
+    }
+
+    /// Returns every occurrence of the option named `name`, as the
+    /// option as it was spelled, whether it turned the option on, and
+    /// its values.
+    fn occurrences(
+        &self,
+        name: impl OptName<A::ShortOpt, L>,
+    ) -> impl Iterator<Item = (&Opt<A>, bool, &[A])> + '_ {
+        let index = self.specs.iter().position(|spec| name.names(spec));
+
+        self.opts
+            .iter()
+            .filter(move |(i, _, _, _)| Some(*i) == index)
+            .map(|(_, opt, enabled, values)| (opt, *enabled, values.as_slice()))
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{Argument, IntoPositionals, Opt, Options, Result};
+use alloc::collections::{vec_deque, VecDeque};
+use core::iter::Chain;
+
+/// An [`Options`] that parses options anywhere in the argument list,
+/// but returns positional arguments only after all options, like GNU
+/// `getopt` does by default.
+///
+/// Positional arguments found in between options are buffered, and
+/// [`Permuting::next_opt`] keeps returning options until `--` or the
+/// end of the arguments is reached. Afterwards, the buffered positional
+/// arguments are returned first, followed by the rest of the arguments.
+///
+/// This type can be obtained by calling [`Options::permuting`], and
+/// requires the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, Options};
+/// #
+/// let args = ["one", "-a", "two", "--bee", "x", "--", "-c"];
+/// let mut opts = Options::new(args.into_iter()).permuting();
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("bee"))));
+/// assert_eq!(opts.value(), Ok("x"));
+/// assert_eq!(opts.next_opt(), Ok(None));
+///
+/// let positionals = opts.into_positionals().collect::<Vec<_>>();
+/// assert_eq!(positionals, ["one", "two", "-c"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Permuting<A: Argument, I: Iterator<Item = A>> {
+    opts: Options<A, I>,
+    positionals: VecDeque<A>,
+}
+
+impl<A: Argument, I: Iterator<Item = A>> Permuting<A, I> {
+    pub(crate) fn new(opts: Options<A, I>) -> Self {
+        Self {
+            opts,
+            positionals: VecDeque::new(),
+        }
+    }
+
+    /// Retrieves the next option, buffering any positional arguments
+    /// that come before it.
+    ///
+    /// Returns `Ok(None)` once `--` or the end of the arguments has
+    /// been reached. See [`Options::next_opt`].
+    pub fn next_opt(&mut self) -> Result<A, Option<Opt<A>>> {
+        loop {
+            if let Some(opt) = self.opts.next_opt()? {
+                return Ok(Some(opt));
+            }
+
+            if self.opts.opts_ended() {
+                return Ok(None);
+            }
+
+            match self.opts.next_positional() {
+                Some(positional) => self.positionals.push_back(positional),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Retrieves the value of the last option. See [`Options::value`].
+    pub fn value(&mut self) -> Result<A, A> {
+        self.opts.value()
+    }
+
+    /// Retrieves the optional value of the last option. See
+    /// [`Options::value_opt`].
+    pub fn value_opt(&mut self) -> Option<A> {
+        self.opts.value_opt()
+    }
+
+    /// Retrieves the next positional argument, starting with the ones
+    /// that were buffered by [`Permuting::next_opt`]. See
+    /// [`Options::next_positional`].
+    pub fn next_positional(&mut self) -> Option<A> {
+        self.positionals
+            .pop_front()
+            .or_else(|| self.opts.next_positional())
+    }
+
+    /// Returns `true` if `--` was encountered. See
+    /// [`Options::opts_ended`].
+    pub fn opts_ended(&self) -> bool {
+        self.opts.opts_ended()
+    }
+
+    /// Consumes this [`Permuting`], returning an iterator over the
+    /// buffered positional arguments followed by the rest of the
+    /// arguments. See [`Options::into_positionals`].
+    pub fn into_positionals(self) -> Chain<vec_deque::IntoIter<A>, IntoPositionals<A, I>> {
+        self.positionals
+            .into_iter()
+            .chain(self.opts.into_positionals())
+    }
+}
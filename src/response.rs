@@ -0,0 +1,124 @@
+use std::{fs, io, vec};
+
+/// An iterator adapter that expands `@file` arguments into the contents
+/// of the file, like gcc, MSVC and javac do.
+///
+/// Response files contain arguments separated by whitespace, including
+/// newlines. Arguments can be quoted with `'` or `"` to include
+/// whitespace, and a backslash escapes the next character, except
+/// inside single quotes. Response files can refer to other response
+/// files, up to [`ResponseFiles::max_depth`] levels deep, which also
+/// stops files that refer to themselves.
+///
+/// Arguments are returned as [`io::Result`]s, since reading a response
+/// file can fail, and
+/// [`sysexits::for_io_error`][crate::sysexits::for_io_error] returns
+/// the exit code for an error. A lone `@` is not expanded. Expansion
+/// happens before [`Options`][crate::Options] sees the arguments, so
+/// `@file` is expanded even after `--`.
+///
+/// This type requires the `std` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// # use getargs::{Options, ResponseFiles};
+/// #
+/// let args = ResponseFiles::new(std::env::args().skip(1)).collect::<std::io::Result<Vec<_>>>()?;
+/// let mut opts = Options::new(args.iter().map(String::as_str));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct ResponseFiles<I: Iterator<Item = String>> {
+    iter: I,
+    stack: Vec<vec::IntoIter<String>>,
+    max_depth: usize,
+}
+
+impl<I: Iterator<Item = String>> ResponseFiles<I> {
+    /// The default value of [`ResponseFiles::max_depth`].
+    pub const DEFAULT_MAX_DEPTH: usize = 16;
+
+    /// Creates a new [`ResponseFiles`] that expands the response files
+    /// in `iter`.
+    pub fn new(iter: I) -> Self {
+        Self {
+            iter,
+            stack: Vec::new(),
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Sets how deeply response files can be nested. A response file
+    /// past this depth is reported as an error of kind
+    /// [`io::ErrorKind::InvalidInput`]. A depth of `0` disables
+    /// expansion.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl<I: Iterator<Item = String>> Iterator for ResponseFiles<I> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let arg = match self.stack.last_mut() {
+                Some(args) => match args.next() {
+                    Some(arg) => arg,
+                    None => {
+                        self.stack.pop();
+                        continue;
+                    }
+                },
+                None => self.iter.next()?,
+            };
+
+            let path = match arg.strip_prefix('@') {
+                Some(path) if !path.is_empty() && self.max_depth > 0 => path,
+                _ => return Some(Ok(arg)),
+            };
+
+            if self.stack.len() >= self.max_depth {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("response file {} is nested too deeply", path),
+                )));
+            }
+
+            match fs::read_to_string(path) {
+                Ok(contents) => self.stack.push(split(&contents).into_iter()),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// Splits the contents of a response file into arguments.
+fn split(contents: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = None::<String>;
+    let mut quote = None;
+    let mut chars = contents.chars();
+
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('\''), _) => arg.get_or_insert_with(String::new).push(ch),
+            (_, '\\') => {
+                let arg = arg.get_or_insert_with(String::new);
+                arg.extend(chars.next());
+            }
+            (None, '\'' | '"') => {
+                arg.get_or_insert_with(String::new);
+                quote = Some(ch);
+            }
+            (None, _) if ch.is_whitespace() => args.extend(arg.take()),
+            _ => arg.get_or_insert_with(String::new).push(ch),
+        }
+    }
+
+    args.extend(arg);
+    args
+}
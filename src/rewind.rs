@@ -0,0 +1,68 @@
+/// An iterator adapter that keeps a copy of the iterator it wraps, so
+/// that it can be rewound to the beginning.
+///
+/// [`Options`] over a [`Rewindable`] gain an [`Options::rewind`]
+/// method, which starts parsing over from the first argument. This
+/// allows two-pass parsing, for example to find `--config` before
+/// parsing everything else. Unlike [`Options::restart`], this works at
+/// any point, and with iterators that don't repeat.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, Options, Rewindable};
+/// #
+/// let args = ["-v", "--config", "app.toml", "file"];
+/// let mut opts = Options::new(Rewindable::new(args.into_iter()));
+/// let mut config = None;
+///
+/// while let Some(opt) = opts.next_opt()? {
+///     if opt == Opt::Long("config") {
+///         config = Some(opts.value()?);
+///     }
+/// }
+///
+/// assert_eq!(config, Some("app.toml"));
+///
+/// opts.rewind();
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+///
+/// [`Options`]: crate::Options
+/// [`Options::rewind`]: crate::Options::rewind
+/// [`Options::restart`]: crate::Options::restart
+#[derive(Copy, Clone, Debug)]
+pub struct Rewindable<I: Iterator + Clone> {
+    start: I,
+    iter: I,
+}
+
+impl<I: Iterator + Clone> Rewindable<I> {
+    /// Creates a new [`Rewindable`] that can be rewound to the current
+    /// position of `iter`.
+    pub fn new(iter: I) -> Self {
+        Self {
+            start: iter.clone(),
+            iter,
+        }
+    }
+
+    /// Goes back to the position this [`Rewindable`] was created at.
+    pub fn rewind(&mut self) {
+        self.iter = self.start.clone();
+    }
+}
+
+impl<I: Iterator + Clone> Iterator for Rewindable<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// An iterator that splits a string into words like a POSIX shell,
+/// for parsing command strings from configuration files or interactive
+/// prompts.
+///
+/// Words are separated by spaces, tabs and newlines. Quoting follows
+/// the shell:
+///
+/// * Everything between single quotes is literal.
+/// * Inside double quotes, a backslash only escapes `$`, `` ` ``, `"`,
+///   `\` and newlines, and is literal otherwise.
+/// * Outside of quotes, a backslash escapes any character.
+/// * A backslash followed by a newline is removed entirely, continuing
+///   the line.
+/// * A `#` at the start of a word comments out the rest of the line.
+///
+/// Quotes are removed, so `'a b'c` is the single word `a bc`, and `''`
+/// is an empty word. A quote that is never closed extends to the end of
+/// the string. Nothing else, like variables or globs, is expanded.
+///
+/// The string is unescaped in place, so each word is borrowed from it
+/// and can be passed straight to [`Options::new`][crate::Options::new]
+/// without allocating. Words that are not quoted are left where they
+/// are.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, Options, ShellWords};
+/// #
+/// let mut line = *br#"-o 'my file.txt' "\$HOME" a\ b # comment"#;
+/// let mut opts = Options::new(ShellWords::new(&mut line));
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'o'))));
+/// assert_eq!(opts.value(), Ok(&b"my file.txt"[..]));
+/// assert_eq!(opts.next_positional(), Some(&b"$HOME"[..]));
+/// assert_eq!(opts.next_positional(), Some(&b"a b"[..]));
+/// assert_eq!(opts.next_positional(), None);
+/// ```
+#[derive(Debug)]
+pub struct ShellWords<'a> {
+    rest: &'a mut [u8],
+}
+
+impl<'a> ShellWords<'a> {
+    /// Creates a new [`ShellWords`] over the string `line`.
+    pub fn new(line: &'a mut [u8]) -> Self {
+        Self { rest: line }
+    }
+}
+
+impl<'a> Iterator for ShellWords<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = word_start(self.rest)?;
+        let rest = &mut core::mem::take(&mut self.rest)[start..];
+
+        let mut unquote = Unquote::new();
+        let mut write = 0;
+
+        while let Some(byte) = unquote.next(rest) {
+            rest[write] = byte;
+            write += 1;
+        }
+
+        let (word, tail) = rest.split_at_mut(unquote.read);
+        self.rest = tail;
+        Some(&word[..write])
+    }
+}
+
+/// An iterator that splits a `&str` into words like a POSIX shell,
+/// borrowing each word from the string unless it has to be unquoted.
+///
+/// This is the iterator of the [`Options`][crate::Options] returned by
+/// [`Options::from_cmdline_str`][crate::Options::from_cmdline_str]. It
+/// follows the same rules as [`ShellWords`], but doesn't need a mutable
+/// copy of the string: words without quotes or backslashes are
+/// returned as [`Cow::Borrowed`], and only the others are copied.
+///
+/// This type requires the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::CmdlineWords;
+/// # use std::borrow::Cow;
+/// #
+/// let mut words = CmdlineWords::new("a 'b c'");
+///
+/// assert_eq!(words.next(), Some(Cow::Borrowed("a")));
+/// assert_eq!(words.next(), Some(Cow::Owned("b c".into())));
+/// assert_eq!(words.next(), None);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug)]
+pub struct CmdlineWords<'a> {
+    rest: &'a str,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> CmdlineWords<'a> {
+    /// Creates a new [`CmdlineWords`] over the string `line`.
+    pub fn new(line: &'a str) -> Self {
+        Self { rest: line }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Iterator for CmdlineWords<'a> {
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = word_start(self.rest.as_bytes())?;
+        let rest = &self.rest[start..];
+
+        let mut unquote = Unquote::new();
+        let mut len = 0;
+
+        while unquote.next(rest.as_bytes()).is_some() {
+            len += 1;
+        }
+
+        // Words always end before an ASCII character, so this can't
+        // split a character in half.
+        let (word, tail) = rest.split_at(unquote.read);
+        self.rest = tail;
+
+        // Unquoting only ever removes bytes, so a word is unchanged if
+        // it has the same length.
+        if len == word.len() {
+            return Some(Cow::Borrowed(word));
+        }
+
+        let mut unquote = Unquote::new();
+        let mut bytes = Vec::with_capacity(len);
+        bytes.extend(core::iter::from_fn(|| unquote.next(word.as_bytes())));
+
+        // Only ASCII quotes and backslashes are removed, so the word is
+        // still valid UTF-8.
+        Some(Cow::Owned(String::from_utf8(bytes).unwrap()))
+    }
+}
+
+/// Returns the index of the next word in `input`, skipping whitespace,
+/// line continuations and comments.
+fn word_start(input: &[u8]) -> Option<usize> {
+    let mut pos = 0;
+
+    loop {
+        match input.get(pos)? {
+            b' ' | b'\t' | b'\n' => pos += 1,
+            b'\\' if input.get(pos + 1) == Some(&b'\n') => pos += 2,
+            b'#' => pos += input[pos..].iter().position(|&byte| byte == b'\n')?,
+            _ => return Some(pos),
+        }
+    }
+}
+
+/// Removes the quoting from a single word, one byte at a time.
+struct Unquote {
+    /// How many bytes of the word have been read.
+    read: usize,
+    quote: Option<u8>,
+}
+
+impl Unquote {
+    fn new() -> Self {
+        Self {
+            read: 0,
+            quote: None,
+        }
+    }
+
+    /// Returns the next unquoted byte of the word at the start of
+    /// `input`, or [`None`] at the end of the word.
+    fn next(&mut self, input: &[u8]) -> Option<u8> {
+        loop {
+            let byte = *input.get(self.read)?;
+            self.read += 1;
+
+            match (self.quote, byte) {
+                (None, b' ' | b'\t' | b'\n') => {
+                    self.read -= 1;
+                    return None;
+                }
+                (None, b'\'' | b'"') => self.quote = Some(byte),
+                (Some(quote), _) if byte == quote => self.quote = None,
+                (None, b'\\') | (Some(b'"'), b'\\') => match input.get(self.read) {
+                    Some(b'\n') => self.read += 1,
+                    Some(&next) if self.quote.is_none() || b"$`\"\\".contains(&next) => {
+                        self.read += 1;
+                        return Some(next);
+                    }
+                    _ => return Some(byte),
+                },
+                _ => return Some(byte),
+            }
+        }
+    }
+}
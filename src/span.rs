@@ -0,0 +1,207 @@
+use core::ops::Range;
+
+use crate::{Arg, Argument, Opt, Options, Result};
+
+/// The location of an option, value or positional argument on the
+/// command line: the index of the argument it came from, as returned
+/// by [`Options::arg_index`], and its byte range within that argument.
+///
+/// For example, in `prog -o --depth=x`, the value `x` is at index 2,
+/// bytes `8..9`.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct Span {
+    /// The 1-based index of the argument.
+    pub index: usize,
+    /// The byte range within the argument.
+    pub range: Range<usize>,
+}
+
+/// An [`Options`] that returns a [`Span`] along with every option,
+/// value and positional argument, for pointing diagnostics at exactly
+/// the part of an argument that was wrong.
+///
+/// This type can be obtained by calling [`Options::spanned`]. It
+/// offers the same parsing methods as [`Options`], which behave
+/// identically except that their results are paired with spans.
+/// Arguments must implement [`AsRef<[u8]>`][AsRef], like `&str`,
+/// `&[u8]`, `String` and `Vec<u8>` do.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, Options, Span};
+/// #
+/// let args = ["-vo", "out.txt", "--depth=x"];
+/// let mut opts = Options::new(args.into_iter()).spanned();
+///
+/// assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('v'), Span { index: 1, range: 1..2 }))));
+/// assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('o'), Span { index: 1, range: 2..3 }))));
+/// assert_eq!(opts.value(), Ok(("out.txt", Span { index: 2, range: 0..7 })));
+/// assert_eq!(opts.next_opt(), Ok(Some((Opt::Long("depth"), Span { index: 3, range: 2..7 }))));
+///
+/// let (depth, span) = opts.value()?;
+///
+/// assert!(depth.parse::<u32>().is_err());
+/// assert_eq!(span, Span { index: 3, range: 8..9 });
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Spanned<A: Argument, I: Iterator<Item = A>> {
+    opts: Options<A, I>,
+    /// The index of the argument containing the last short option, and
+    /// the end of that option within it.
+    short_end: Option<(usize, usize)>,
+}
+
+impl<A: Argument + AsRef<[u8]>, I: Iterator<Item = A>> Spanned<A, I> {
+    pub(crate) fn new(mut opts: Options<A, I>) -> Self {
+        opts.set_track_current_arg(true);
+
+        Self {
+            opts,
+            short_end: None,
+        }
+    }
+
+    /// Returns the current argument as bytes.
+    fn whole(&self) -> &[u8] {
+        self.opts.current().map_or(&[], AsRef::as_ref)
+    }
+
+    /// Returns the span of the name of a long or numeric option that
+    /// was just parsed, which starts right after its prefix: two of
+    /// [`ParseConfig::opt_prefix`][crate::ParseConfig::opt_prefix] for
+    /// long options, and one character otherwise.
+    fn locate_name(&self, name: &A) -> Span {
+        let prefix = self.opts.config().opt_prefix;
+
+        let start = match self.whole() {
+            [] => 0,
+            [first, second, ..] if *first == prefix && *second == prefix => 2,
+            _ => 1,
+        };
+
+        Span {
+            index: self.opts.arg_index(),
+            range: start..start + name.as_ref().len(),
+        }
+    }
+
+    /// Returns the span of a value or positional argument that was just
+    /// parsed. These are always the end of the current argument, either
+    /// after an option and its `=`, or the whole argument.
+    fn locate_value(&self, value: &A) -> Span {
+        let end = self.whole().len();
+
+        Span {
+            index: self.opts.arg_index(),
+            range: end.saturating_sub(value.as_ref().len())..end,
+        }
+    }
+
+    /// Returns the span of a short option that was just parsed. `plus`
+    /// is whether it was a `+` option.
+    fn locate_short(&mut self, plus: bool) -> Span {
+        let index = self.opts.arg_index();
+        let whole = self.whole();
+        let prefix = if plus {
+            b'+'
+        } else {
+            self.opts.config().opt_prefix
+        };
+
+        let start = match self.short_end {
+            Some((short_index, end)) if short_index == index => end,
+            // Bundled clusters in the first argument have no prefix.
+            _ => whole.first().map_or(0, |&first| (first == prefix) as usize),
+        };
+
+        let end = whole.len()
+            - self
+                .opts
+                .cluster_rest()
+                .map_or(0, |rest| rest.as_ref().len());
+        self.short_end = Some((index, end));
+
+        Span {
+            index,
+            range: start..end,
+        }
+    }
+
+    fn span_opt(&mut self, opt: &Opt<A>) -> Span {
+        match opt {
+            Opt::Short(_) => self.locate_short(false),
+            Opt::ShortPlus(_) => self.locate_short(true),
+            Opt::Long(name) | Opt::Numeric(name) => self.locate_name(name),
+        }
+    }
+
+    /// Like [`Options::next_opt`], but also returns the span of the
+    /// option.
+    pub fn next_opt(&mut self) -> Result<A, Option<(Opt<A>, Span)>> {
+        let Some(opt) = self.opts.next_opt()? else {
+            return Ok(None);
+        };
+
+        let span = self.span_opt(&opt);
+        Ok(Some((opt, span)))
+    }
+
+    /// Like [`Options::next_arg`], but also returns the span of the
+    /// option or positional argument.
+    pub fn next_arg(&mut self) -> Result<A, Option<(Arg<A>, Span)>> {
+        let span = match self.opts.next_arg()? {
+            Some(Arg::Positional(positional)) => {
+                let span = self.locate_value(&positional);
+                (Arg::Positional(positional), span)
+            }
+            Some(arg) => {
+                let opt = arg.opt().unwrap();
+                let span = self.span_opt(&opt);
+                (opt.into(), span)
+            }
+            None => return Ok(None),
+        };
+
+        Ok(Some(span))
+    }
+
+    /// Like [`Options::value`], but also returns the span of the value.
+    pub fn value(&mut self) -> Result<A, (A, Span)> {
+        let value = self.opts.value()?;
+        let span = self.locate_value(&value);
+        Ok((value, span))
+    }
+
+    /// Like [`Options::value_opt`], but also returns the span of the
+    /// value.
+    pub fn value_opt(&mut self) -> Option<(A, Span)> {
+        let value = self.opts.value_opt()?;
+        let span = self.locate_value(&value);
+        Some((value, span))
+    }
+
+    /// Like [`Options::next_positional`], but also returns the span of
+    /// the positional argument.
+    pub fn next_positional(&mut self) -> Option<(A, Span)> {
+        let positional = self.opts.next_positional()?;
+        let span = self.locate_value(&positional);
+        Some((positional, span))
+    }
+
+    /// Returns [`Options::opts_ended`] for the wrapped [`Options`].
+    pub fn opts_ended(&self) -> bool {
+        self.opts.opts_ended()
+    }
+
+    /// Returns [`Options::is_empty`] for the wrapped [`Options`].
+    pub fn is_empty(&self) -> bool {
+        self.opts.is_empty()
+    }
+
+    /// Consumes this [`Spanned`], returning the wrapped [`Options`].
+    pub fn into_inner(self) -> Options<A, I> {
+        self.opts
+    }
+}
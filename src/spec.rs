@@ -0,0 +1,1078 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "unicode")]
+use core::str::from_utf8;
+
+use crate::{sysexits, Argument, Opt, OptValue};
+
+/// A declaration of one option accepted by a program.
+///
+/// A table of [`OptSpec`]s can be passed to [`Options::next_spec`],
+/// which parses the next option, checks it against the table, and
+/// consumes its value if the option takes one. Options that are not in
+/// the table are reported as
+/// [`Error::UnknownOption`][crate::Error::UnknownOption].
+///
+/// `S` is the short option type and `L` is the long option type; they
+/// default to those of [`&str`] arguments. For [`&[u8]`][slice]
+/// arguments, use `OptSpec<u8, &[u8]>`.
+///
+/// Like [`ParseConfig`][crate::ParseConfig], each field can be set
+/// using the builder method of the same name, so tables can be
+/// declared as constants:
+///
+/// ```
+/// # use getargs::OptSpec;
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().short('v').long("verbose"),
+///     OptSpec::new().short('o').long("output").takes_value(true),
+///     OptSpec::new().long("help"),
+/// ];
+///
+/// assert_eq!(SPECS[1].long, Some("output"));
+/// ```
+///
+/// [`Options::next_spec`]: crate::Options::next_spec
+#[non_exhaustive]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct OptSpec<S = char, L = &'static str> {
+    /// The short name of the option, like `o` for `-o`.
+    pub short: Option<S>,
+    /// The long name of the option, like `output` for `--output`.
+    pub long: Option<L>,
+    /// Whether the option takes a value, which will be consumed with
+    /// [`Options::value`][crate::Options::value]. This is always
+    /// [`arity`][Self#structfield.arity] `> 0` when set through
+    /// [`OptSpec::takes_value`] or [`OptSpec::arity`].
+    pub takes_value: bool,
+    /// The number of values the option takes, like 3 for
+    /// `--point X Y Z`. The first value is taken like
+    /// [`Options::value`][crate::Options::value], and the rest are the
+    /// arguments after it. Defaults to 0, is set to 1 by
+    /// [`OptSpec::takes_value`], and can be at most
+    /// [`OptSpec::MAX_ARITY`].
+    pub arity: u8,
+    /// The name of the value in help text, like `FILE` in
+    /// `--output FILE`. Defaults to `VALUE` when rendering.
+    pub value_name: Option<&'static str>,
+    /// A description of the option for help text.
+    pub help: Option<&'static str>,
+    /// Whether the option must be present. This is checked by
+    /// [`Tracker::check_required`].
+    pub required: bool,
+    /// The group of mutually exclusive options this option belongs to,
+    /// if any. Two different options in the same group are reported as
+    /// a [`Conflict`] by [`Tracker::record`].
+    pub group: Option<u8>,
+    /// The value to use when the option is not given. This is applied
+    /// by [`OptSpec::value_or_default`].
+    pub default_value: Option<L>,
+    /// The environment variable to read when the option is not given,
+    /// like `MYAPP_CACHE_DIR`. This is applied by
+    /// [`OptSpec::value_or_env`].
+    pub env: Option<&'static str>,
+    /// Whether the option is a flag that can also be turned off by
+    /// prefixing its long name with `no-`, like `--no-color` for
+    /// `--color`. This is recognized by [`SpecTable::find_flag`].
+    pub negatable: bool,
+    /// Whether the option is deprecated. Uses of deprecated options
+    /// can be reported with [`OptSpec::deprecation`] or a
+    /// [`Deprecations`][crate::Deprecations] observer.
+    pub deprecated: bool,
+    /// The long name of the option that should be used instead of this
+    /// one, if it is [deprecated][Self#structfield.deprecated].
+    pub replaced_by: Option<L>,
+}
+
+impl<S: Copy, L: Copy> OptSpec<S, L> {
+    /// Creates an [`OptSpec`] with no names that takes no value.
+    pub const fn new() -> Self {
+        Self {
+            short: None,
+            long: None,
+            takes_value: false,
+            arity: 0,
+            value_name: None,
+            help: None,
+            required: false,
+            group: None,
+            default_value: None,
+            env: None,
+            negatable: false,
+            deprecated: false,
+            replaced_by: None,
+        }
+    }
+
+    /// Sets [`OptSpec::short`][Self#structfield.short].
+    pub const fn short(mut self, short: S) -> Self {
+        self.short = Some(short);
+        self
+    }
+
+    /// Sets [`OptSpec::long`][Self#structfield.long].
+    pub const fn long(mut self, long: L) -> Self {
+        self.long = Some(long);
+        self
+    }
+
+    /// Sets [`OptSpec::takes_value`][Self#structfield.takes_value],
+    /// and [`OptSpec::arity`][Self#structfield.arity] to 1 if it was 0
+    /// and `takes_value` is `true`, or to 0 if `takes_value` is
+    /// `false`.
+    pub const fn takes_value(mut self, takes_value: bool) -> Self {
+        self.takes_value = takes_value;
+        self.arity = match (takes_value, self.arity) {
+            (false, _) => 0,
+            (true, 0) => 1,
+            (true, arity) => arity,
+        };
+        self
+    }
+
+    /// Sets [`OptSpec::arity`][Self#structfield.arity], and
+    /// [`OptSpec::takes_value`][Self#structfield.takes_value] to
+    /// whether `arity` is more than 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `arity` is more than [`OptSpec::MAX_ARITY`], which is
+    /// a compile-time error when declaring a constant table.
+    pub const fn arity(mut self, arity: u8) -> Self {
+        assert!(
+            arity as usize <= Self::MAX_ARITY,
+            "arity is more than OptSpec::MAX_ARITY"
+        );
+
+        self.arity = arity;
+        self.takes_value = arity > 0;
+        self
+    }
+
+    /// Sets [`OptSpec::value_name`][Self#structfield.value_name].
+    pub const fn value_name(mut self, value_name: &'static str) -> Self {
+        self.value_name = Some(value_name);
+        self
+    }
+
+    /// Sets [`OptSpec::help`][Self#structfield.help].
+    pub const fn help(mut self, help: &'static str) -> Self {
+        self.help = Some(help);
+        self
+    }
+
+    /// Sets [`OptSpec::required`][Self#structfield.required].
+    pub const fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Sets [`OptSpec::group`][Self#structfield.group].
+    pub const fn group(mut self, group: u8) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Sets [`OptSpec::default_value`][Self#structfield.default_value].
+    pub const fn default_value(mut self, default_value: L) -> Self {
+        self.default_value = Some(default_value);
+        self
+    }
+
+    /// Sets [`OptSpec::env`][Self#structfield.env].
+    pub const fn env(mut self, env: &'static str) -> Self {
+        self.env = Some(env);
+        self
+    }
+
+    /// Sets [`OptSpec::negatable`][Self#structfield.negatable].
+    pub const fn negatable(mut self, negatable: bool) -> Self {
+        self.negatable = negatable;
+        self
+    }
+
+    /// Sets [`OptSpec::deprecated`][Self#structfield.deprecated].
+    pub const fn deprecated(mut self, deprecated: bool) -> Self {
+        self.deprecated = deprecated;
+        self
+    }
+
+    /// Sets [`OptSpec::replaced_by`][Self#structfield.replaced_by].
+    pub const fn replaced_by(mut self, replaced_by: L) -> Self {
+        self.replaced_by = Some(replaced_by);
+        self
+    }
+
+    /// Returns `value`, which should be the value given for this option
+    /// if it was present, or else this option's
+    /// [default value][Self#structfield.default_value], if it has one.
+    /// The returned [`OptValue`] tells which of the two was used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{OptSpec, OptValue};
+    /// #
+    /// let spec: OptSpec = OptSpec::new()
+    ///     .long("color")
+    ///     .takes_value(true)
+    ///     .default_value("auto");
+    ///
+    /// let given = spec.value_or_default(Some("never"));
+    /// let default = spec.value_or_default(None::<&str>);
+    ///
+    /// assert_eq!(given, Some(OptValue::Given("never")));
+    /// assert_eq!(default, Some(OptValue::Default("auto")));
+    /// assert!(default.unwrap().is_default());
+    /// assert_eq!(default.unwrap().to_string(), "auto");
+    /// ```
+    pub fn value_or_default<A>(&self, value: Option<A>) -> Option<OptValue<A, L>> {
+        match value {
+            Some(value) => Some(OptValue::Given(value)),
+            None => self.default_value.map(OptValue::Default),
+        }
+    }
+
+    /// Like [`OptSpec::value_or_default`], but if the option was not
+    /// given, the value of its
+    /// [environment variable][Self#structfield.env] is used before
+    /// falling back to the default value. Values given on the command
+    /// line always take precedence over the environment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::ffi::OsString;
+    /// # use getargs::{OptSpec, OptValue};
+    /// #
+    /// let spec: OptSpec = OptSpec::new()
+    ///     .long("cache-dir")
+    ///     .takes_value(true)
+    ///     .env("MYAPP_CACHE_DIR")
+    ///     .default_value("/tmp/cache");
+    ///
+    /// std::env::remove_var("MYAPP_CACHE_DIR");
+    /// assert_eq!(spec.value_or_env(None::<&str>), Some(OptValue::Default("/tmp/cache")));
+    ///
+    /// std::env::set_var("MYAPP_CACHE_DIR", "/var/cache");
+    /// assert_eq!(
+    ///     spec.value_or_env(None::<&str>),
+    ///     Some(OptValue::Env(OsString::from("/var/cache")))
+    /// );
+    /// assert_eq!(spec.value_or_env(Some("cache")), Some(OptValue::Given("cache")));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn value_or_env<A>(&self, value: Option<A>) -> Option<OptValue<A, L, std::ffi::OsString>> {
+        match value {
+            Some(value) => Some(OptValue::Given(value)),
+            None => self
+                .env
+                .and_then(std::env::var_os)
+                .map(OptValue::Env)
+                .or_else(|| self.default_value.map(OptValue::Default)),
+        }
+    }
+
+    /// Returns `true` if `opt` is one of the names of this option.
+    /// [`Opt::ShortPlus`] matches the short name as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, OptSpec};
+    /// #
+    /// let spec: OptSpec = OptSpec::new().short('o').long("output");
+    ///
+    /// assert!(spec.matches(&Opt::<&str>::Short('o')));
+    /// assert!(spec.matches(&Opt::Long("output")));
+    /// assert!(!spec.matches(&Opt::Long("o")));
+    /// ```
+    pub fn matches<A>(&self, opt: &Opt<A>) -> bool
+    where
+        S: Eq,
+        A: Argument<ShortOpt = S> + PartialEq<L>,
+    {
+        match opt {
+            Opt::Short(short) | Opt::ShortPlus(short) => self.short == Some(*short),
+            Opt::Long(long) => self.long.is_some_and(|name| *long == name),
+            Opt::Numeric(_) => false,
+        }
+    }
+}
+
+impl<S, L> OptSpec<S, L> {
+    /// The maximum [arity][Self#structfield.arity] of an option, which
+    /// is the capacity of [`SpecValues`].
+    pub const MAX_ARITY: usize = 8;
+
+    /// Returns the number of values the option takes, which is its
+    /// [arity][Self#structfield.arity] if it
+    /// [takes a value][Self#structfield.takes_value] and 0 otherwise.
+    pub const fn value_count(&self) -> usize {
+        if self.takes_value {
+            self.arity as usize
+        } else {
+            0
+        }
+    }
+
+    /// Returns a [`Deprecated`] warning for `opt`, which should be a
+    /// use of this option, if this option is
+    /// [deprecated][Self#structfield.deprecated].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, OptSpec};
+    /// #
+    /// let spec: OptSpec = OptSpec::new()
+    ///     .long("colour")
+    ///     .deprecated(true)
+    ///     .replaced_by("color");
+    ///
+    /// let warning = spec.deprecation(Opt::Long("colour")).unwrap();
+    ///
+    /// assert_eq!(warning.to_string(), "--colour is deprecated, use --color instead");
+    /// ```
+    pub fn deprecation<A: Argument>(&self, opt: Opt<A>) -> Option<Deprecated<'_, A, S, L>> {
+        self.deprecated.then_some(Deprecated { opt, spec: self })
+    }
+}
+
+impl<S: Copy, L: Copy> Default for OptSpec<S, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The values of an option returned by
+/// [`Options::next_spec`][crate::Options::next_spec], as many as the
+/// [arity][OptSpec#structfield.arity] of the option, or none if it does
+/// not take a value.
+///
+/// [`SpecValues`] does not allocate, so it can hold at most
+/// [`OptSpec::MAX_ARITY`] values. It can be compared with an array of
+/// values, and iterated over by reference or by value.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, OptSpec, Options};
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().short('v'),
+///     OptSpec::new().long("point").arity(3),
+/// ];
+///
+/// let args = ["--point", "1", "2", "3", "-v"];
+/// let mut opts = Options::new(args.into_iter());
+///
+/// let (opt, values) = opts.next_spec(SPECS)?.unwrap();
+/// assert_eq!(opt, Opt::Long("point"));
+/// assert_eq!(values, ["1", "2", "3"]);
+///
+/// let (opt, values) = opts.next_spec(SPECS)?.unwrap();
+/// assert_eq!(opt, Opt::Short('v'));
+/// assert!(values.is_empty());
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct SpecValues<A> {
+    values: [Option<A>; OptSpec::<(), ()>::MAX_ARITY],
+}
+
+impl<A> SpecValues<A> {
+    /// Creates an empty [`SpecValues`].
+    pub fn new() -> Self {
+        Self {
+            values: Default::default(),
+        }
+    }
+
+    /// Appends `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are already [`OptSpec::MAX_ARITY`] values.
+    pub fn push(&mut self, value: A) {
+        let slot = self.values.iter_mut().find(|slot| slot.is_none());
+        *slot.expect("too many values for getargs::SpecValues") = Some(value);
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns `true` if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.values[0].is_none()
+    }
+
+    /// Returns the value at `index`, if there is one.
+    pub fn get(&self, index: usize) -> Option<&A> {
+        self.values.get(index)?.as_ref()
+    }
+
+    /// Returns the first value, which is the only value of an option
+    /// with an [arity][OptSpec#structfield.arity] of 1.
+    pub fn first(&self) -> Option<&A> {
+        self.get(0)
+    }
+
+    /// Returns the first value like [`SpecValues::first`], consuming
+    /// this [`SpecValues`].
+    pub fn into_first(self) -> Option<A> {
+        self.into_iter().next()
+    }
+
+    /// Returns an iterator over the values.
+    pub fn iter(&self) -> impl Iterator<Item = &A> + '_ {
+        self.values.iter().map_while(Option::as_ref)
+    }
+}
+
+impl<A> Default for SpecValues<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> IntoIterator for SpecValues<A> {
+    type Item = A;
+    type IntoIter =
+        core::iter::Flatten<core::array::IntoIter<Option<A>, { OptSpec::<(), ()>::MAX_ARITY }>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter().flatten()
+    }
+}
+
+impl<A: PartialEq, const N: usize> PartialEq<[A; N]> for SpecValues<A> {
+    fn eq(&self, other: &[A; N]) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+/// Tracks which options of a table of [`OptSpec`]s have been seen
+/// while parsing, so that constraints between them can be checked.
+///
+/// Options are recorded with [`Tracker::record`], usually right after
+/// they are returned by [`Options::next_spec`]. Once parsing has
+/// finished, [`Tracker::check_required`] reports every
+/// [required][OptSpec#structfield.required] option that was never
+/// seen, all at once. Options in the same
+/// [group][OptSpec#structfield.group] are mutually exclusive, which
+/// is checked as they are recorded.
+///
+/// A [`Tracker`] does not allocate; it stores one bit per option, so
+/// tables can have at most [`Tracker::MAX_SPECS`] options.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{OptSpec, Options, Tracker};
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().short('v'),
+///     OptSpec::new().long("input").takes_value(true).required(true),
+///     OptSpec::new().long("output").takes_value(true).required(true),
+/// ];
+///
+/// let args = ["-v", "--output", "out.txt"];
+/// let mut opts = Options::new(args.into_iter());
+/// let mut tracker = Tracker::new(SPECS);
+///
+/// while let Some((opt, _values)) = opts.next_spec(SPECS)? {
+///     tracker.record(&opt).unwrap();
+/// }
+///
+/// let missing = tracker.check_required().unwrap_err();
+///
+/// assert_eq!(missing.to_string(), "missing required options: --input");
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+///
+/// [`Options::next_spec`]: crate::Options::next_spec
+#[derive(Copy, Clone, Debug)]
+pub struct Tracker<'s, S = char, L = &'static str> {
+    specs: &'s [OptSpec<S, L>],
+    seen: u128,
+}
+
+impl<'s, S: Copy + Eq, L: Copy> Tracker<'s, S, L> {
+    /// The maximum number of options a [`Tracker`] can track.
+    pub const MAX_SPECS: usize = 128;
+
+    /// Creates a new [`Tracker`] for `specs`, which have not been seen
+    /// yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `specs` has more than [`Tracker::MAX_SPECS`] options.
+    pub const fn new(specs: &'s [OptSpec<S, L>]) -> Self {
+        assert!(
+            specs.len() <= Self::MAX_SPECS,
+            "too many options for getargs::Tracker"
+        );
+
+        Self { specs, seen: 0 }
+    }
+
+    /// Records that `opt` was seen. Options that are not in the table
+    /// are ignored.
+    ///
+    /// Returns a [`Conflict`] if a different option in the same
+    /// [group][OptSpec#structfield.group] has already been seen, in
+    /// which case `opt` is not recorded. Repeating the same option is
+    /// not a conflict.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, OptSpec, Tracker};
+    /// #
+    /// const SPECS: &[OptSpec] = &[
+    ///     OptSpec::new().long("json").group(0),
+    ///     OptSpec::new().long("yaml").group(0),
+    /// ];
+    ///
+    /// let mut tracker = Tracker::new(SPECS);
+    ///
+    /// assert!(tracker.record(&Opt::<&str>::Long("json")).is_ok());
+    /// assert!(tracker.record(&Opt::<&str>::Long("json")).is_ok());
+    ///
+    /// let conflict = tracker.record(&Opt::<&str>::Long("yaml")).unwrap_err();
+    ///
+    /// assert_eq!(conflict.to_string(), "--yaml cannot be used with --json");
+    /// ```
+    pub fn record<A>(&mut self, opt: &Opt<A>) -> core::result::Result<(), Conflict<'s, S, L>>
+    where
+        A: Argument<ShortOpt = S> + PartialEq<L>,
+    {
+        let Some(index) = self.specs.iter().position(|spec| spec.matches(opt)) else {
+            return Ok(());
+        };
+
+        let spec = &self.specs[index];
+
+        if let Some(group) = spec.group {
+            let earlier =
+                self.specs.iter().enumerate().find(|&(i, other)| {
+                    i != index && other.group == Some(group) && self.is_seen(i)
+                });
+
+            if let Some((_, earlier)) = earlier {
+                return Err(Conflict {
+                    earlier,
+                    later: spec,
+                });
+            }
+        }
+
+        self.seen |= 1 << index;
+        Ok(())
+    }
+
+    /// Returns `true` if the option at `index` in the table has been
+    /// seen.
+    pub fn is_seen(&self, index: usize) -> bool {
+        index < self.specs.len() && self.seen & (1 << index) != 0
+    }
+
+    /// Checks that every [required][OptSpec#structfield.required]
+    /// option has been seen, returning all of the ones that have not.
+    pub fn check_required(&self) -> core::result::Result<(), MissingRequired<'s, S, L>> {
+        let missing = self
+            .specs
+            .iter()
+            .enumerate()
+            .filter(|&(index, spec)| spec.required && !self.is_seen(index))
+            .fold(0, |missing, (index, _)| missing | 1 << index);
+
+        if missing == 0 {
+            Ok(())
+        } else {
+            Err(MissingRequired {
+                specs: self.specs,
+                missing,
+            })
+        }
+    }
+}
+
+/// The error returned by [`Tracker::check_required`], listing every
+/// required option that was not present.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct MissingRequired<'s, S = char, L = &'static str> {
+    specs: &'s [OptSpec<S, L>],
+    missing: u128,
+}
+
+impl<'s, S, L> MissingRequired<'s, S, L> {
+    /// Returns an iterator over the [`OptSpec`]s of the missing
+    /// options, in the order they appear in the table.
+    pub fn iter(&self) -> impl Iterator<Item = &'s OptSpec<S, L>> + '_ {
+        self.specs
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| self.missing & (1 << index) != 0)
+            .map(|(_, spec)| spec)
+    }
+
+    /// Returns the conventional [`sysexits`] exit code for this error,
+    /// which is [`EX_USAGE`][sysexits::EX_USAGE].
+    pub fn exit_code(&self) -> u8 {
+        sysexits::EX_USAGE
+    }
+}
+
+impl<S: Display, L: Display> Display for MissingRequired<'_, S, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("missing required options:")?;
+
+        for (i, spec) in self.iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { ", " })?;
+            write_name(spec, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S: Display + fmt::Debug, L: Display + fmt::Debug> core::error::Error
+    for MissingRequired<'_, S, L>
+{
+}
+
+/// The error returned by [`Tracker::record`] when two options in the
+/// same [group][OptSpec#structfield.group] are used together.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Conflict<'s, S = char, L = &'static str> {
+    /// The option that was seen first.
+    pub earlier: &'s OptSpec<S, L>,
+    /// The option that conflicts with [`Conflict::earlier`].
+    pub later: &'s OptSpec<S, L>,
+}
+
+impl<S, L> Conflict<'_, S, L> {
+    /// Returns the conventional [`sysexits`] exit code for this error,
+    /// which is [`EX_USAGE`][sysexits::EX_USAGE].
+    pub fn exit_code(&self) -> u8 {
+        sysexits::EX_USAGE
+    }
+}
+
+impl<S: Display, L: Display> Display for Conflict<'_, S, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_name(self.later, f)?;
+        f.write_str(" cannot be used with ")?;
+        write_name(self.earlier, f)
+    }
+}
+
+impl<S: Display + fmt::Debug, L: Display + fmt::Debug> core::error::Error for Conflict<'_, S, L> {}
+
+/// A warning that a [deprecated][OptSpec#structfield.deprecated]
+/// option was used, returned by [`OptSpec::deprecation`] and passed to
+/// [`Deprecations`][crate::Deprecations] observers.
+///
+/// It displays as a message like `--colour is deprecated, use --color
+/// instead`, which can be printed as is or logged.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Deprecated<'s, A: Argument, S = char, L = &'static str> {
+    /// The option as it was given.
+    pub opt: Opt<A>,
+    /// The [`OptSpec`] of the deprecated option.
+    pub spec: &'s OptSpec<S, L>,
+}
+
+impl<A: Argument, S, L: Copy> Deprecated<'_, A, S, L> {
+    /// Returns the long name of the option that should be used instead,
+    /// if there is one.
+    pub fn replaced_by(&self) -> Option<L> {
+        self.spec.replaced_by
+    }
+}
+
+impl<A: Argument + Display, S, L: Display> Display for Deprecated<'_, A, S, L>
+where
+    A::ShortOpt: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is deprecated", self.opt)?;
+
+        if let Some(replaced_by) = &self.spec.replaced_by {
+            write!(f, ", use --{} instead", replaced_by)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A table of [`OptSpec`]s that options can be looked up in, with
+/// more lenient matching than [`OptSpec::matches`].
+///
+/// With [`SpecTable::abbreviations`] enabled, long options can be
+/// abbreviated like in GNU programs: `--verb` finds `--verbose` as long
+/// as no other long option starts with `verb`. Exact matches always
+/// win, so `--version` can still be given if `--version-info` also
+/// exists. A prefix of several options is reported as [`Ambiguous`].
+///
+/// With [`SpecTable::ignore_case`] enabled, long options are also
+/// matched regardless of ASCII case, so `--Output` finds `--output`, as
+/// many programs ported from Windows expect. This does not allocate.
+///
+/// With the `unicode` feature, `SpecTable::normalize` also matches
+/// long options that are canonically equivalent, like `café` spelled
+/// with a combining accent rather than a precomposed `é`.
+///
+/// Since the option returned by [`Options::next_opt`] is still spelled
+/// the way it was given, [`SpecTable::find`] returns the matching
+/// [`OptSpec`], which should be used to tell options apart instead.
+///
+/// Long names are compared as bytes, so arguments and long names must
+/// implement [`AsRef<[u8]>`][AsRef], which [`&str`], [`&[u8]`][slice]
+/// and [`String`] do. Like a [`Tracker`], a [`SpecTable`] can hold at
+/// most [`SpecTable::MAX_SPECS`] options.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, OptSpec, Options, SpecTable};
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().short('v').long("verbose"),
+///     OptSpec::new().long("version"),
+///     OptSpec::new().long("output").takes_value(true),
+/// ];
+///
+/// let table = SpecTable::new(SPECS).abbreviations(true);
+///
+/// let args = ["--out", "out.txt", "--verb", "--vers", "--ver"];
+/// let mut opts = Options::new(args.into_iter());
+///
+/// let opt = opts.next_opt()?.unwrap();
+/// let spec = table.find(&opt).unwrap().unwrap();
+/// assert_eq!(spec.long, Some("output"));
+/// assert_eq!(opts.value(), Ok("out.txt"));
+///
+/// let opt = opts.next_opt()?.unwrap();
+/// assert_eq!(table.find(&opt).unwrap(), Some(&SPECS[0]));
+///
+/// let opt = opts.next_opt()?.unwrap();
+/// assert_eq!(table.find(&opt).unwrap(), Some(&SPECS[1]));
+///
+/// let opt = opts.next_opt()?.unwrap();
+/// let ambiguous = table.find(&opt).unwrap_err();
+/// assert_eq!(
+///     ambiguous.to_string(),
+///     "ambiguous option, could be: --verbose, --version"
+/// );
+/// # Ok::<(), getargs::Error<&'static str>>(())
+/// ```
+///
+/// [`Options::next_opt`]: crate::Options::next_opt
+#[derive(Copy, Clone, Debug)]
+pub struct SpecTable<'s, S = char, L = &'static str> {
+    specs: &'s [OptSpec<S, L>],
+    abbreviations: bool,
+    ignore_case: bool,
+    #[cfg(feature = "unicode")]
+    normalize: bool,
+}
+
+impl<'s, S: Copy + Eq, L: Copy + AsRef<[u8]>> SpecTable<'s, S, L> {
+    /// The maximum number of options a [`SpecTable`] can hold.
+    pub const MAX_SPECS: usize = 128;
+
+    /// Creates a new [`SpecTable`] for `specs`, which only matches
+    /// options exactly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `specs` has more than [`SpecTable::MAX_SPECS`]
+    /// options.
+    pub const fn new(specs: &'s [OptSpec<S, L>]) -> Self {
+        assert!(
+            specs.len() <= Self::MAX_SPECS,
+            "too many options for getargs::SpecTable"
+        );
+
+        Self {
+            specs,
+            abbreviations: false,
+            ignore_case: false,
+            #[cfg(feature = "unicode")]
+            normalize: false,
+        }
+    }
+
+    /// Sets whether long options can be abbreviated to any prefix that
+    /// only one long option starts with. This is `false` by default.
+    pub const fn abbreviations(mut self, abbreviations: bool) -> Self {
+        self.abbreviations = abbreviations;
+        self
+    }
+
+    /// Sets whether long options are matched regardless of ASCII case.
+    /// This is `false` by default. An option whose long name matches
+    /// exactly is still preferred over one that only differs in case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, OptSpec, SpecTable};
+    /// #
+    /// const SPECS: &[OptSpec] = &[OptSpec::new().long("output")];
+    ///
+    /// let table = SpecTable::new(SPECS).ignore_case(true);
+    ///
+    /// assert_eq!(table.find(&Opt::Long("Output")), Ok(Some(&SPECS[0])));
+    /// assert_eq!(table.find(&Opt::Long("OUTPUT")), Ok(Some(&SPECS[0])));
+    /// ```
+    pub const fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Sets whether long options are matched if they are canonically
+    /// equivalent, meaning that they have the same [NFC]
+    /// normalization. This is `false` by default. An option whose long
+    /// name matches exactly is still preferred.
+    ///
+    /// Names are compared without allocating or using the full Unicode
+    /// data tables: only precomposed Latin, Greek and Cyrillic letters
+    /// are decomposed, and combining marks are expected to be in
+    /// canonical order. Names that are not valid UTF-8 are compared as
+    /// bytes.
+    ///
+    /// This method requires the `unicode` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, OptSpec, SpecTable};
+    /// #
+    /// const SPECS: &[OptSpec] = &[OptSpec::new().long("caf\u{e9}")];
+    ///
+    /// let table = SpecTable::new(SPECS).normalize(true);
+    ///
+    /// assert_eq!(table.find(&Opt::Long("cafe\u{301}")), Ok(Some(&SPECS[0])));
+    /// assert_eq!(table.find(&Opt::Long("cafe")), Ok(None));
+    /// ```
+    ///
+    /// [NFC]: https://www.unicode.org/reports/tr15/
+    #[cfg(feature = "unicode")]
+    pub const fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Returns the table of [`OptSpec`]s that options are looked up in.
+    pub fn specs(&self) -> &'s [OptSpec<S, L>] {
+        self.specs
+    }
+
+    /// Returns the [`OptSpec`] that `opt` refers to, or [`None`] if it
+    /// is not in the table.
+    ///
+    /// Returns an [`Ambiguous`] error if `opt` is an abbreviation of
+    /// more than one long option.
+    pub fn find<A>(
+        &self,
+        opt: &Opt<A>,
+    ) -> core::result::Result<Option<&'s OptSpec<S, L>>, Ambiguous<'s, S, L>>
+    where
+        A: Argument<ShortOpt = S> + AsRef<[u8]>,
+    {
+        let name = match opt {
+            Opt::Short(short) | Opt::ShortPlus(short) => {
+                return Ok(self.specs.iter().find(|spec| spec.short == Some(*short)));
+            }
+            Opt::Long(name) => name.as_ref(),
+            Opt::Numeric(_) => return Ok(None),
+        };
+
+        self.find_long(name, |_| true)
+    }
+
+    /// Like [`SpecTable::find`], but also finds
+    /// [negatable][OptSpec#structfield.negatable] options that were
+    /// turned off with `--no-`, returning `false` along with them.
+    /// Every other option that is found is returned with `true`.
+    ///
+    /// An option whose name really starts with `no-` takes precedence
+    /// over negating another option.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use getargs::{Opt, OptSpec, SpecTable};
+    /// #
+    /// const SPECS: &[OptSpec] = &[
+    ///     OptSpec::new().long("color").negatable(true),
+    ///     OptSpec::new().long("verbose"),
+    /// ];
+    ///
+    /// let table = SpecTable::new(SPECS);
+    ///
+    /// assert_eq!(table.find_flag(&Opt::Long("color")), Ok(Some((&SPECS[0], true))));
+    /// assert_eq!(table.find_flag(&Opt::Long("no-color")), Ok(Some((&SPECS[0], false))));
+    /// assert_eq!(table.find_flag(&Opt::Long("no-verbose")), Ok(None));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn find_flag<A>(
+        &self,
+        opt: &Opt<A>,
+    ) -> core::result::Result<Option<(&'s OptSpec<S, L>, bool)>, Ambiguous<'s, S, L>>
+    where
+        A: Argument<ShortOpt = S> + AsRef<[u8]>,
+    {
+        if let Some(spec) = self.find(opt)? {
+            return Ok(Some((spec, true)));
+        }
+
+        let Opt::Long(name) = opt else {
+            return Ok(None);
+        };
+
+        let name = name.as_ref();
+
+        match name.split_at_checked(3) {
+            Some((no, rest)) if self.eq(no, b"no-", true) => Ok(self
+                .find_long(rest, |spec| spec.negatable)?
+                .map(|spec| (spec, false))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the option in the table with the long name `name`, only
+    /// considering options that are accepted by `accept`.
+    fn find_long(
+        &self,
+        name: &[u8],
+        accept: impl Fn(&OptSpec<S, L>) -> bool,
+    ) -> core::result::Result<Option<&'s OptSpec<S, L>>, Ambiguous<'s, S, L>> {
+        let exact = |lenient| {
+            self.specs.iter().find(|spec| {
+                accept(spec)
+                    && spec
+                        .long
+                        .is_some_and(|long| self.eq(long.as_ref(), name, lenient))
+            })
+        };
+
+        if let Some(spec) = exact(false).or_else(|| exact(true)) {
+            return Ok(Some(spec));
+        }
+
+        if !self.abbreviations || name.is_empty() {
+            return Ok(None);
+        }
+
+        let candidates = self
+            .specs
+            .iter()
+            .enumerate()
+            .filter(|(_, spec)| {
+                accept(spec)
+                    && spec
+                        .long
+                        .is_some_and(|long| self.is_prefix(name, long.as_ref()))
+            })
+            .fold(0u128, |candidates, (index, _)| candidates | 1 << index);
+
+        match candidates.count_ones() {
+            0 => Ok(None),
+            1 => Ok(Some(&self.specs[candidates.trailing_zeros() as usize])),
+            _ => Err(Ambiguous {
+                specs: self.specs,
+                candidates,
+            }),
+        }
+    }
+
+    /// Returns `true` if `a` and `b` are equal or, if `lenient` is
+    /// `true`, equivalent under the settings of this table.
+    fn eq(&self, a: &[u8], b: &[u8], lenient: bool) -> bool {
+        if !lenient {
+            return a == b;
+        }
+
+        #[cfg(feature = "unicode")]
+        if let (true, Ok(a), Ok(b)) = (self.normalize, from_utf8(a), from_utf8(b)) {
+            return crate::normalize::eq(a, b, self.ignore_case);
+        }
+
+        if self.ignore_case {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    }
+
+    /// Returns `true` if `prefix` is a prefix of `name`, under the
+    /// settings of this table.
+    fn is_prefix(&self, prefix: &[u8], name: &[u8]) -> bool {
+        #[cfg(feature = "unicode")]
+        if let (true, Ok(prefix), Ok(name)) = (self.normalize, from_utf8(prefix), from_utf8(name)) {
+            return crate::normalize::is_prefix(prefix, name, self.ignore_case);
+        }
+
+        name.get(..prefix.len())
+            .is_some_and(|start| self.eq(start, prefix, true))
+    }
+}
+
+/// The error returned by [`SpecTable::find`] when an abbreviated long
+/// option could refer to more than one option.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Ambiguous<'s, S = char, L = &'static str> {
+    specs: &'s [OptSpec<S, L>],
+    candidates: u128,
+}
+
+impl<'s, S, L> Ambiguous<'s, S, L> {
+    /// Returns an iterator over the [`OptSpec`]s of the options that
+    /// the abbreviation could refer to, in the order they appear in the
+    /// table.
+    pub fn iter(&self) -> impl Iterator<Item = &'s OptSpec<S, L>> + '_ {
+        self.specs
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| self.candidates & (1 << index) != 0)
+            .map(|(_, spec)| spec)
+    }
+
+    /// Returns the conventional [`sysexits`] exit code for this error,
+    /// which is [`EX_USAGE`][sysexits::EX_USAGE].
+    pub fn exit_code(&self) -> u8 {
+        sysexits::EX_USAGE
+    }
+}
+
+impl<S: Display, L: Display> Display for Ambiguous<'_, S, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("ambiguous option, could be:")?;
+
+        for (i, spec) in self.iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { ", " })?;
+            write_name(spec, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S: Display + fmt::Debug, L: Display + fmt::Debug> core::error::Error for Ambiguous<'_, S, L> {}
+
+/// Writes the most descriptive name of `spec`: its long name if it has
+/// one, or its short name otherwise.
+fn write_name<S: Display, L: Display>(spec: &OptSpec<S, L>, f: &mut Formatter<'_>) -> fmt::Result {
+    match (&spec.short, &spec.long) {
+        (_, Some(long)) => write!(f, "--{}", long),
+        (Some(short), None) => write!(f, "-{}", short),
+        (None, None) => Ok(()),
+    }
+}
@@ -0,0 +1,89 @@
+//! "Did you mean" suggestions for unknown options.
+//!
+//! [`closest`] finds the known option name that is most similar to an
+//! unknown one, so that error messages can point users at what they
+//! probably meant:
+//!
+//! ```
+//! # use getargs::suggest;
+//! #
+//! let known = ["color", "columns", "verbose"];
+//!
+//! assert_eq!(suggest::closest("colr", &known), Some("color"));
+//! assert_eq!(suggest::closest("xyz", &known), None);
+//! ```
+//!
+//! Similarity is measured by [edit distance][edit_distance]. Everything
+//! in this module works without `std` or allocation, using a fixed
+//! amount of stack space; names longer than [`MAX_LEN`] characters are
+//! never suggested.
+
+/// The maximum length, in characters, of names that can be compared.
+pub const MAX_LEN: usize = 64;
+
+/// Returns the Levenshtein edit distance between `a` and `b`: the
+/// minimum number of characters that must be inserted, removed or
+/// replaced to turn one into the other. Returns `None` if `b` is longer
+/// than [`MAX_LEN`] characters.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::suggest::edit_distance;
+/// #
+/// assert_eq!(edit_distance("colr", "color"), Some(1));
+/// assert_eq!(edit_distance("verbose", "verbose"), Some(0));
+/// assert_eq!(edit_distance("kitten", "sitting"), Some(3));
+/// ```
+pub fn edit_distance(a: &str, b: &str) -> Option<usize> {
+    let len = b.chars().count();
+
+    if len > MAX_LEN {
+        return None;
+    }
+
+    let mut row = [0; MAX_LEN + 1];
+
+    for (j, cell) in row.iter_mut().enumerate().take(len + 1) {
+        *cell = j;
+    }
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.chars().enumerate() {
+            let above = row[j + 1];
+            let cost = if ca == cb { 0 } else { 1 };
+            row[j + 1] = (diagonal + cost).min(above + 1).min(row[j] + 1);
+            diagonal = above;
+        }
+    }
+
+    Some(row[len])
+}
+
+/// Returns the name in `candidates` that is closest to `name`, if any
+/// is close enough to be a plausible typo.
+///
+/// A candidate is close enough if its [edit distance][edit_distance]
+/// from `name` is at most a third of the length of `name`, rounded up.
+/// If several candidates are equally close, the first one is returned.
+pub fn closest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    closest_in(name, candidates.iter().copied())
+}
+
+/// Like [`closest`], but takes the candidates from an iterator.
+pub(crate) fn closest_in<'a, I: IntoIterator<Item = &'a str>>(
+    name: &str,
+    candidates: I,
+) -> Option<&'a str> {
+    let threshold = name.chars().count().div_ceil(3);
+
+    candidates
+        .into_iter()
+        .filter_map(|candidate| Some((edit_distance(name, candidate)?, candidate)))
+        .filter(|&(distance, _)| distance <= threshold)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
@@ -0,0 +1,39 @@
+//! Conventional exit codes from BSD's `sysexits.h`.
+//!
+//! These are returned by [`Error::exit_code`][crate::Error::exit_code]
+//! and [`for_io_error`] for tools that need to follow the exit code
+//! conventions used by mailers and daemons. Only the codes that
+//! argument parsing can produce are included.
+
+/// The command completed successfully, e.g. after printing help.
+pub const EX_OK: u8 = 0;
+
+/// The command was used incorrectly, e.g. with the wrong number of
+/// arguments, a bad flag, or bad syntax in a parameter.
+pub const EX_USAGE: u8 = 64;
+
+/// The input data was incorrect in some way. This should only be used
+/// for user data, not system files.
+pub const EX_DATAERR: u8 = 65;
+
+/// An input file (not a system file) did not exist or was not
+/// readable.
+pub const EX_NOINPUT: u8 = 66;
+
+/// Returns the conventional exit code for an error returned by
+/// [`ResponseFiles`][crate::ResponseFiles].
+///
+/// Errors of kind [`InvalidInput`][std::io::ErrorKind::InvalidInput],
+/// like response files that are nested too deeply, and
+/// [`InvalidData`][std::io::ErrorKind::InvalidData], like response
+/// files that are not valid UTF-8, are [`EX_DATAERR`]. Any other error
+/// means the file could not be read, which is [`EX_NOINPUT`].
+///
+/// This function requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn for_io_error(error: &std::io::Error) -> u8 {
+    match error.kind() {
+        std::io::ErrorKind::InvalidInput | std::io::ErrorKind::InvalidData => EX_DATAERR,
+        _ => EX_NOINPUT,
+    }
+}
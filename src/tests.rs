@@ -1,5 +1,13 @@
 use super::*;
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
 #[test]
 fn no_options() {
     let args = ["foo", "bar"];
@@ -437,6 +445,38 @@ fn bytes() {
     assert!(opts.is_empty());
 }
 
+#[test]
+fn bytes_split_on() {
+    let mut haystack = [b'a'; 40];
+
+    for len in 0..haystack.len() {
+        assert_eq!(haystack[..len].split_on(b'='), (&haystack[..len], None));
+
+        for at in 0..len {
+            haystack[at] = b'=';
+            // A later match and bytes that differ from `=` only in their
+            // high bit must not be mistaken for the first match.
+            haystack[len - 1] = if at == len - 1 { b'=' } else { b'=' | 0x80 };
+
+            let (name, value) = haystack[..len].split_on(b'=');
+            assert_eq!(name, &haystack[..at]);
+            assert_eq!(value, Some(&haystack[at + 1..len]));
+
+            haystack[at] = b'a';
+            haystack[len - 1] = b'a';
+        }
+    }
+
+    let arg = b"--a-long-option-name=with=equals".as_slice();
+    assert_eq!(
+        arg.parse_long_opt(),
+        Some((
+            b"a-long-option-name".as_slice(),
+            Some(b"with=equals".as_slice())
+        ))
+    );
+}
+
 #[test]
 fn alternating() {
     let args = [
@@ -574,3 +614,3571 @@ fn repeating_iterator() {
     assert_eq!(opts.next_positional(), None);
     assert!(opts.is_empty());
 }
+
+#[test]
+fn split_last_positional() {
+    let args = ["-f", "a", "b", "c"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('f'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    let (mut sources, dest) = opts.split_last_positional().unwrap();
+    assert_eq!(dest, "c");
+    assert_eq!(sources.next(), Some("a"));
+    assert_eq!(sources.next(), Some("b"));
+    assert_eq!(sources.next(), None);
+}
+
+#[test]
+fn split_last_positional_too_few() {
+    let args = ["-f", "a"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('f'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(
+        opts.split_last_positional().map(|(_, dest)| dest),
+        Err(Error::TooFewPositionals {
+            expected: 2,
+            found: 1
+        })
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> ParseObserver<&'a str> for Recorder {
+    fn on_opt(&mut self, opt: Opt<&'a str>) {
+        self.events.push(format!("opt {}", opt));
+    }
+
+    fn on_value(&mut self, opt: Opt<&'a str>, value: &'a str) {
+        self.events.push(format!("value {} {}", opt, value));
+    }
+
+    fn on_positional(&mut self, positional: &'a str) {
+        self.events.push(format!("positional {}", positional));
+    }
+
+    fn on_error(&mut self, error: &Error<&'a str>) {
+        self.events.push(format!("error {}", error));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn observe() {
+    let args = ["-a", "1", "--bee=2", "--see=3", "foo"];
+    let mut opts = Options::new(args.into_iter()).observe(Recorder::default());
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+    assert_eq!(opts.value(), Ok("1"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("bee"))));
+    assert_eq!(opts.value_opt(), Some("2"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("see"))));
+    assert_eq!(
+        opts.next_arg(),
+        Err(Error::DoesNotRequireValue(Opt::Long("see")))
+    );
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("foo"))));
+    assert_eq!(opts.next_arg(), Ok(None));
+    assert!(opts.is_empty());
+
+    let (_, recorder) = opts.into_inner();
+    assert_eq!(
+        recorder.events,
+        [
+            "opt -a",
+            "value -a 1",
+            "opt --bee",
+            "value --bee 2",
+            "opt --see",
+            "error option does not require a value: --see",
+            "positional foo",
+        ]
+    );
+}
+
+#[test]
+fn value_group() {
+    let args = ["-p1", "2", "--point", "3", "4", "-x", "--point=5"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('p'))));
+    assert_eq!(opts.value_group(), Ok(["1", "2"]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("point"))));
+    assert_eq!(opts.value_group(), Ok(["3", "4", "-x"]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("point"))));
+    assert_eq!(
+        opts.value_group::<2>(),
+        Err(Error::RequiresValues {
+            opt: Opt::Long("point"),
+            expected: 2,
+            found: 1
+        })
+    );
+    assert!(opts.is_empty());
+}
+
+#[test]
+fn exit_code() {
+    assert_eq!(
+        Error::<&str>::RequiresValue(Opt::Short('a')).exit_code(),
+        sysexits::EX_USAGE
+    );
+    assert_eq!(
+        Error::<&str>::TooFewPositionals {
+            expected: 2,
+            found: 0
+        }
+        .exit_code(),
+        sysexits::EX_USAGE
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn json_lines() {
+    let args = ["--na\"me=a\\b", "-x", "tab\there"];
+    let mut opts = Options::new(args.into_iter()).observe(JsonLines::new(Vec::new()));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("na\"me"))));
+    assert_eq!(opts.value(), Ok("a\\b"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('x'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("tab\there"))));
+    let (_, mut json) = opts.into_inner();
+    assert!(json.take_error().is_none());
+    assert_eq!(
+        String::from_utf8(json.into_inner()).unwrap(),
+        concat!(
+            "{\"event\":\"opt\",\"opt\":\"--na\\\"me\"}\n",
+            "{\"event\":\"value\",\"opt\":\"--na\\\"me\",\"value\":\"a\\\\b\"}\n",
+            "{\"event\":\"opt\",\"opt\":\"-x\"}\n",
+            "{\"event\":\"positional\",\"positional\":\"tab\\there\"}\n",
+        )
+    );
+}
+
+#[test]
+fn value_sources() {
+    let args = ["-ab", "--see=1", "--dee", "-e", "2"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(
+        opts.value_opt_with_source(),
+        Some(("b", ValueSource::Attached))
+    );
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("see"))));
+    assert_eq!(
+        opts.value_opt_with_source(),
+        Some(("1", ValueSource::Inline))
+    );
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("dee"))));
+    assert_eq!(opts.value_opt_with_source(), None);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('e'))));
+    assert_eq!(opts.value_with_source(), Ok(("2", ValueSource::Next)));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.is_empty());
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+struct Chars<'a>(&'a [char]);
+
+impl SplitArgument for Chars<'_> {
+    type ShortOpt = char;
+
+    fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn strip_prefix(self, prefix: &str) -> Option<Self> {
+        let len = prefix.len();
+        let matches =
+            self.0.len() >= len && self.0.iter().zip(prefix.chars()).all(|(a, b)| *a == b);
+        matches.then(|| Chars(&self.0[len..]))
+    }
+
+    fn find(self, ch: u8) -> Option<usize> {
+        self.0.iter().position(|c| *c == ch as char)
+    }
+
+    fn split_at(self, position: usize) -> (Self, Self) {
+        let (left, right) = self.0.split_at(position);
+        (Chars(left), Chars(right))
+    }
+
+    fn split_short_opt(self) -> Option<(Self::ShortOpt, Self)> {
+        self.0.split_first().map(|(c, rest)| (*c, Chars(rest)))
+    }
+}
+
+#[test]
+fn split_argument() {
+    let args = [
+        Chars(&['-', 'a', 'b']),
+        Chars(&['-', '-', 'c', '=', 'd']),
+        Chars(&['-', '-', 'e']),
+        Chars(&['-', '-']),
+        Chars(&['-', 'f']),
+    ];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.value(), Ok(Chars(&['b'])));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(Chars(&['c'])))));
+    assert_eq!(opts.value(), Ok(Chars(&['d'])));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(Chars(&['e'])))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some(Chars(&['-', 'f'])));
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[test]
+fn split_argument_numeric() {
+    let args = [
+        Chars(&['-', '1', '2']),
+        Chars(&['-', '3', 'a']),
+        Chars(&['-', '-']),
+    ];
+    let config = ParseConfig::new().numeric_opts(true);
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Numeric(Chars(&['1', '2'])))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('3'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+
+    assert_eq!(Chars(&['4', '2']).parse_numeric(), Some(Chars(&['4', '2'])));
+    assert_eq!(Chars(&['4', 'x']).parse_numeric(), None);
+    assert_eq!(Chars(&[]).parse_numeric(), None);
+}
+
+#[test]
+fn help_requested() {
+    let args = ["-a", "foo", "--bee=1", "--help", "-c"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(
+        opts.help_requested(|opt| opt.is_help()),
+        Some(Opt::Long("help"))
+    );
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(
+        opts.help_requested(|opt| opt.is_help()),
+        Some(Opt::Long("help"))
+    );
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("foo"));
+
+    let args = [b"-x".as_slice(), b"--", b"-h"];
+    let opts = Options::new(args.into_iter());
+    assert_eq!(opts.help_requested(|opt| opt.is_help()), None);
+}
+
+#[test]
+fn help_opt() {
+    let mut args = ["-a", "--bee=1", "-xh", "--help", "foo"].into_iter();
+    let mut opts = Options::new(args.by_ref());
+    opts.set_help_opt(|opt| opt.is_help());
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("bee"))));
+    assert_eq!(opts.value(), Ok("1"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('x'))));
+    assert_eq!(opts.next_arg(), Err(Error::HelpRequested(Opt::Short('h'))));
+    assert_eq!(
+        opts.next_arg(),
+        Err(Error::HelpRequested(Opt::Long("help")))
+    );
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("foo"))));
+    assert_eq!(opts.next_arg(), Ok(None));
+
+    let error: Error<&str> = Error::HelpRequested(Opt::Long("help"));
+    assert_eq!(error.exit_code(), sysexits::EX_OK);
+
+    let args = [b"-?".as_slice(), b"--", b"-h"];
+    let mut opts = Options::new(args.into_iter());
+    opts.set_help_opt(|opt| opt.is_help());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'?'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some(b"-h".as_slice()));
+}
+
+#[test]
+fn raw_mode() {
+    let args = ["-ab", "-c", "--", "-d", "--ee"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    opts.set_raw(true);
+    assert!(opts.is_raw());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("-c"));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("--"));
+    opts.set_raw(false);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('d'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("ee"))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.is_empty());
+
+    let args = ["-a"];
+    let mut opts = Options::new(args.iter().copied().cycle().take(2));
+    opts.set_raw(true);
+    assert_eq!(opts.next_positional(), Some("-a"));
+    assert_eq!(opts.next_positional(), Some("-a"));
+    assert_eq!(opts.next_positional(), None);
+    opts.restart();
+    assert!(!opts.is_raw());
+}
+
+#[cfg(all(unix, feature = "std"))]
+#[test]
+fn os_str() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let args = [
+        OsStr::new("-ab\u{e9}"),
+        OsStr::new("--see=\u{e9}"),
+        OsStr::from_bytes(b"-\xff"),
+        OsStr::new("--"),
+        OsStr::from_bytes(b"\xfe"),
+    ];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'a'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'b'))));
+    assert_eq!(opts.value(), Ok(OsStr::new("\u{e9}")));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(OsStr::new("see")))));
+    assert_eq!(opts.value(), Ok(OsStr::new("\u{e9}")));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(0xff))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some(OsStr::from_bytes(b"\xfe")));
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[cfg(all(windows, feature = "std"))]
+#[test]
+fn os_str() {
+    use std::ffi::{OsStr, OsString};
+    use std::os::windows::ffi::OsStringExt;
+
+    let surrogate = OsString::from_wide(&[b'-' as u16, 0xD800]);
+    let args = [
+        OsStr::new("-ab\u{e9}"),
+        OsStr::new("--see=\u{e9}"),
+        surrogate.as_os_str(),
+        OsStr::new("--"),
+        OsStr::new("-x"),
+    ];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.value(), Ok(OsStr::new("\u{e9}")));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(OsStr::new("see")))));
+    assert_eq!(opts.value(), Ok(OsStr::new("\u{e9}")));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some(surrogate.as_os_str()));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some(OsStr::new("-x")));
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[test]
+fn wide() {
+    const A: u16 = b'a' as u16;
+    const B: u16 = b'b' as u16;
+    const D: u16 = b'-' as u16;
+    const E: u16 = b'=' as u16;
+    let args: [&[u16]; 5] = [
+        &[D, A, 0xe9],
+        &[D, D, B, E, 0xd83d, 0xde00],
+        &[D, D, B],
+        &[D, D],
+        &[D, A],
+    ];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(A))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(0xe9))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(&[B][..]))));
+    assert_eq!(opts.value(), Ok(&[0xd83d, 0xde00][..]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(&[B][..]))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some(&[D, A][..]));
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn owned() {
+    let args = ["-ab", "--see=1", "--", "-d"].map(String::from);
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.value(), Ok(String::from("b")));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(String::from("see")))));
+    assert_eq!(opts.value_opt(), Some(String::from("1")));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some(String::from("-d")));
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn owned_config() {
+    fn parse<A: Argument>(args: impl Iterator<Item = A>, config: ParseConfig) -> Vec<String> {
+        let mut opts = Options::with_config(args, config);
+        let mut parsed = Vec::new();
+
+        while let Some(arg) = opts.next_arg().transpose() {
+            parsed.push(format!("{arg:?}"));
+        }
+
+        parsed
+    }
+
+    let args = [
+        "xv", "-5", "-1.5", "+p", "/w", "-name", "-", "--no", "-ab", "%", "-12", "@@x", "@y",
+    ];
+    let configs = [
+        ParseConfig::new()
+            .bundled_first(true)
+            .slash_opts(true)
+            .plus_opts(true)
+            .negative_numbers(true)
+            .single_dash_names(&["name"])
+            .reject_lone_dash(true),
+        ParseConfig::new()
+            .numeric_opts(true)
+            .single_dash_long(true)
+            .terminator(Terminator::Char(b'%')),
+        ParseConfig::new().opt_prefix(b'@'),
+    ];
+
+    for config in configs {
+        assert_eq!(
+            parse(args.map(String::from).into_iter(), config),
+            parse(args.into_iter(), config),
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn owned_bytes() {
+    let args = [b"-ab".to_vec(), b"--see".to_vec(), b"foo".to_vec()];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short(b'a'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short(b'b'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long(b"see".to_vec()))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional(b"foo".to_vec()))));
+    assert_eq!(opts.next_arg(), Ok(None));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn cow() {
+    use std::borrow::Cow;
+
+    let args = [
+        Cow::Borrowed("-ab"),
+        Cow::Owned(String::from("--see=1")),
+        Cow::Borrowed("--dee=2"),
+        Cow::Owned(String::from("foo")),
+    ];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert!(matches!(opts.value(), Ok(Cow::Borrowed("b"))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(Cow::Borrowed("see")))));
+    assert!(matches!(opts.value(), Ok(Cow::Owned(value)) if value == "1"));
+    assert!(matches!(
+        opts.next_opt(),
+        Ok(Some(Opt::Long(Cow::Borrowed("dee"))))
+    ));
+    assert!(matches!(opts.value(), Ok(Cow::Borrowed("2"))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some(Cow::Borrowed("foo")));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn permuting() {
+    let args = ["one", "-ab", "two", "--see", "three", "-d", "four"];
+    let mut opts = Options::new(args.into_iter()).permuting();
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("see"))));
+    assert_eq!(opts.value(), Ok("three"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('d'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(!opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some("one"));
+    assert_eq!(opts.next_positional(), Some("two"));
+    assert_eq!(opts.next_positional(), Some("four"));
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[test]
+fn stop_at_positional() {
+    let args = ["-a", "cmd", "-b", "--", "arg"];
+    let config = ParseConfig::new().stop_at_positional(true);
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.config(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("cmd"));
+    assert!(opts.is_raw());
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("-b"))));
+    opts.set_raw(false);
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some("arg"));
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[test]
+fn single_dash_long() {
+    let args = ["-name", "*.rs", "-geometry=80x24", "--type", "f", "-", "x"];
+    let config = ParseConfig::new().single_dash_long(true);
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("name"))));
+    assert_eq!(opts.value(), Ok("*.rs"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("geometry"))));
+    assert_eq!(opts.value_with_source(), Ok(("80x24", ValueSource::Inline)));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("type"))));
+    assert_eq!(opts.value(), Ok("f"));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("-"));
+    assert_eq!(opts.next_positional(), Some("x"));
+
+    let args = [b"-geometry=80x24".as_slice(), b"-e"];
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(b"geometry".as_slice()))));
+    assert_eq!(
+        opts.next_opt(),
+        Err(Error::DoesNotRequireValue(Opt::Long(
+            b"geometry".as_slice()
+        )))
+    );
+}
+
+#[test]
+fn slash_opts() {
+    let args = ["/v", "/out:C:/dir/file", "-q", "/", "/path/to/file", "/x"];
+    let config = ParseConfig::new().slash_opts(true);
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("v"))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("out"))));
+    assert_eq!(opts.value(), Ok("C:/dir/file"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('q'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("/"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("/path/to/file"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("x"))));
+
+    let args = [b"/out:file".as_slice()];
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(b"out".as_slice()))));
+    assert_eq!(opts.value(), Ok(b"file".as_slice()));
+
+    let mut opts = Options::new(["/v"].into_iter());
+    assert_eq!(opts.next_opt(), Ok(None));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn plus_opts() {
+    let args = ["-x", "+xv", "+o", "vi", "+", "--", "+x"];
+    let config = ParseConfig::new().plus_opts(true);
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('x'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::ShortPlus('x'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::ShortPlus('v'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::ShortPlus('o'))));
+    assert_eq!(opts.value(), Ok("vi"));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("+"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("+x"))));
+    assert_eq!(Opt::<&str>::ShortPlus('x').to_string(), "+x");
+
+    let mut opts = Options::new(["+x"].into_iter());
+    assert_eq!(opts.next_opt(), Ok(None));
+}
+
+#[test]
+fn bundled_first() {
+    let args = ["xvf", "file.tar", "cf", "-z"];
+    let config = ParseConfig::new().bundled_first(true);
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('x'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('f'))));
+    assert_eq!(opts.value(), Ok("file.tar"));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("cf"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('z'))));
+    assert_eq!(opts.next_arg(), Ok(None));
+
+    let args = ["-xf", "file.tar"];
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('x'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('f'))));
+    assert_eq!(opts.value(), Ok("file.tar"));
+
+    let args = ["", "x"];
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some(""));
+
+    let args = ["x"];
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_positional(), Some("x"));
+    assert_eq!(opts.next_positional(), None);
+    opts.restart();
+    assert_eq!(opts.next_opt(), Ok(None));
+}
+
+#[test]
+fn operands() {
+    let args = ["--", "if=a=b", "=x", "of=", "-v"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(None));
+    let mut operands = opts.operands();
+    assert_eq!(operands.next(), Some(("if", Some("a=b"))));
+    assert_eq!(operands.next(), Some(("", Some("x"))));
+    assert_eq!(operands.next(), Some(("of", Some(""))));
+    assert_eq!(operands.next(), Some(("-v", None)));
+    assert_eq!(operands.next(), None);
+
+    let args = [b"bs=1M".as_slice()];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(
+        opts.operands().next(),
+        Some((b"bs".as_slice(), Some(b"1M".as_slice())))
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn numeric_opts() {
+    let args = ["-5", "-42", "-5v", "-n", "-3", "--", "-1"];
+    let config = ParseConfig::new().numeric_opts(true);
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Numeric("5"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Numeric("42"))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('5'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('n'))));
+    assert_eq!(opts.value(), Ok("-3"));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("-1"));
+    assert_eq!(Opt::<&str>::Numeric("42").to_string(), "-42");
+
+    let args = [b"-10".as_slice()];
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Numeric(b"10".as_slice()))));
+
+    let mut opts = Options::new(["-5"].into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('5'))));
+}
+
+#[test]
+fn optional_value() {
+    let args = [
+        "--color",
+        "file",
+        "--color=never",
+        "-c",
+        "-cauto",
+        "--color",
+        "-v",
+    ];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("color"))));
+    assert_eq!(opts.value_opt(), None);
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("file"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("color"))));
+    assert_eq!(opts.value_opt(), Some("never"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('c'))));
+    assert_eq!(opts.value_opt(), None);
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('c'))));
+    assert_eq!(opts.value_opt(), Some("auto"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("color"))));
+    assert_eq!(opts.value_opt(), None);
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('v'))));
+    assert_eq!(opts.next_arg(), Ok(None));
+}
+
+/// Returns the first value of an option returned by
+/// [`Options::next_spec`], to compare it with an [`Option`].
+#[cfg(feature = "alloc")]
+#[allow(clippy::type_complexity)]
+fn first_value<A: Argument>(
+    next: Result<A, Option<(Opt<A>, SpecValues<A>)>>,
+) -> Result<A, Option<(Opt<A>, Option<A>)>> {
+    next.map(|next| next.map(|(opt, values)| (opt, values.into_first())))
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn next_spec() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('v').long("verbose"),
+        OptSpec::new().short('o').long("output").takes_value(true),
+        OptSpec::new().long("dry-run"),
+    ];
+
+    let args = ["-vofile", "--output=x", "--dry-run=yes", "-o"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(
+        first_value(opts.next_spec(SPECS)),
+        Ok(Some((Opt::Short('v'), None)))
+    );
+    assert_eq!(
+        first_value(opts.next_spec(SPECS)),
+        Ok(Some((Opt::Short('o'), Some("file"))))
+    );
+    assert_eq!(
+        first_value(opts.next_spec(SPECS)),
+        Ok(Some((Opt::Long("output"), Some("x"))))
+    );
+    assert_eq!(
+        first_value(opts.next_spec(SPECS)),
+        Ok(Some((Opt::Long("dry-run"), None)))
+    );
+    assert_eq!(
+        first_value(opts.next_spec(SPECS)),
+        Err(Error::DoesNotRequireValue(Opt::Long("dry-run")))
+    );
+    assert_eq!(
+        first_value(opts.next_spec(SPECS)),
+        Err(Error::RequiresValue(Opt::Short('o')))
+    );
+    assert_eq!(first_value(opts.next_spec(SPECS)), Ok(None));
+
+    let args = ["--bogus", "-z"];
+    let mut opts = Options::new(args.into_iter());
+    let error = first_value(opts.next_spec(SPECS)).unwrap_err();
+    assert_eq!(error, Error::UnknownOption(Opt::Long("bogus")));
+    assert_eq!(error.to_string(), "unknown option: --bogus");
+    assert_eq!(error.exit_code(), sysexits::EX_USAGE);
+
+    let specs: &[OptSpec<u8, &[u8]>] = &[OptSpec::new().short(b'n').takes_value(true)];
+    let args = [b"-n3".as_slice()];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(
+        first_value(opts.next_spec(specs)),
+        Ok(Some((Opt::Short(b'n'), Some(b"3".as_slice()))))
+    );
+}
+
+#[test]
+fn next_spec_arity() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('p').long("point").arity(3),
+        OptSpec::new().short('v'),
+        OptSpec::new().long("none").arity(0),
+    ];
+
+    let args = ["--point", "1", "2", "3", "-vp4", "-5", "6", "--none"];
+    let mut opts = Options::new(args.into_iter());
+    let (opt, values) = opts.next_spec(SPECS).unwrap().unwrap();
+    assert_eq!(opt, Opt::Long("point"));
+    assert_eq!(values, ["1", "2", "3"]);
+    assert_eq!(values.len(), 3);
+    assert_eq!(values.get(2), Some(&"3"));
+    assert_eq!(values.get(3), None);
+    let (opt, values) = opts.next_spec(SPECS).unwrap().unwrap();
+    assert_eq!(opt, Opt::Short('v'));
+    assert!(values.is_empty());
+    let (opt, values) = opts.next_spec(SPECS).unwrap().unwrap();
+    assert_eq!(opt, Opt::Short('p'));
+    assert_eq!(values, ["4", "-5", "6"]);
+    let (opt, values) = opts.next_spec(SPECS).unwrap().unwrap();
+    assert_eq!(opt, Opt::Long("none"));
+    assert_eq!(values.into_iter().count(), 0);
+    assert_eq!(opts.next_spec(SPECS), Ok(None));
+
+    let args = ["--point=1", "2"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(
+        opts.next_spec(SPECS),
+        Err(Error::RequiresValues {
+            opt: Opt::Long("point"),
+            expected: 3,
+            found: 2,
+        })
+    );
+    assert_eq!(opts.next_spec(SPECS), Ok(None));
+
+    let args = ["-p"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(
+        opts.next_spec(SPECS),
+        Err(Error::RequiresValues {
+            opt: Opt::Short('p'),
+            expected: 3,
+            found: 0,
+        })
+    );
+
+    assert_eq!(SPECS[0].value_count(), 3);
+    assert!(SPECS[0].takes_value);
+    assert_eq!(SPECS[1].value_count(), 0);
+    assert_eq!(SPECS[2].value_count(), 0);
+    assert!(!SPECS[2].takes_value);
+    assert_eq!(OptSpec::<char>::new().takes_value(true).value_count(), 1);
+
+    let spec: OptSpec = OptSpec::new().arity(0).takes_value(true);
+    assert_eq!((spec.takes_value, spec.arity), (true, 1));
+    let spec: OptSpec = OptSpec::new().arity(3).takes_value(true);
+    assert_eq!((spec.takes_value, spec.arity), (true, 3));
+    let spec: OptSpec = OptSpec::new().arity(3).takes_value(false);
+    assert_eq!((spec.takes_value, spec.arity), (false, 0));
+}
+
+#[test]
+#[should_panic(expected = "arity is more than OptSpec::MAX_ARITY")]
+fn arity_too_large() {
+    let _: OptSpec = OptSpec::new().arity(OptSpec::<char>::MAX_ARITY as u8 + 1);
+}
+
+#[test]
+fn opts_macro() {
+    opts! {
+        enum Cli {
+            Verbose(-'v' --"verbose"),
+            Output(-'o' --"output" value),
+            DryRun(--"dry-run"),
+            Number(-'n' value),
+            Quiet(-'q'),
+        }
+    }
+
+    assert_eq!(Cli::SPECS.len(), 5);
+    assert_eq!(Cli::ALL[2], Cli::DryRun);
+    assert_eq!(
+        *Cli::Output.spec(),
+        OptSpec::new().short('o').long("output").takes_value(true)
+    );
+    assert_eq!(*Cli::Quiet.spec(), OptSpec::new().short('q'));
+    assert_eq!(Cli::from_opt(&Opt::Long("dry-run")), Some(Cli::DryRun));
+    assert_eq!(Cli::from_opt(&Opt::<&str>::Short('x')), None);
+
+    let args = ["-qvn5", "--output", "file", "--dry-run", "-x"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(Cli::next_opt(&mut opts), Ok(Some((Cli::Quiet, None))));
+    assert_eq!(Cli::next_opt(&mut opts), Ok(Some((Cli::Verbose, None))));
+    assert_eq!(Cli::next_opt(&mut opts), Ok(Some((Cli::Number, Some("5")))));
+    assert_eq!(
+        Cli::next_opt(&mut opts),
+        Ok(Some((Cli::Output, Some("file"))))
+    );
+    assert_eq!(Cli::next_opt(&mut opts), Ok(Some((Cli::DryRun, None))));
+    assert_eq!(
+        Cli::next_opt(&mut opts),
+        Err(Error::UnknownOption(Opt::Short('x')))
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn help() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new()
+            .short('h')
+            .long("help")
+            .help("show this help"),
+        OptSpec::new().short('n').takes_value(true).help("count"),
+        OptSpec::new().long("level").takes_value(true),
+        OptSpec::new().short('q'),
+    ];
+
+    let mut out = String::new();
+    Help::new(SPECS).write_to(&mut out).unwrap();
+    assert_eq!(
+        out,
+        "  -h, --help           show this help
+  -n VALUE             count
+      --level VALUE
+  -q
+"
+    );
+
+    let specs: &[OptSpec<char, &str>] = &[];
+    assert_eq!(Help::new(specs).to_string(), "");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn usage() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('x').long("extract"),
+        OptSpec::new().long("level").takes_value(true),
+        OptSpec::new().short('f').long("file").takes_value(true),
+        OptSpec::new().short('v'),
+        OptSpec::new().long("pos").arity(2).value_name("N"),
+    ];
+
+    assert_eq!(
+        Usage::new("tar", SPECS).to_string(),
+        "usage: tar [-xv] [--level VALUE] [-f VALUE] [--pos N N]"
+    );
+    assert_eq!(
+        Usage::new("cat", &[] as &[OptSpec])
+            .operands("FILE...")
+            .to_string(),
+        "usage: cat [--] FILE..."
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn help_markdown() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('n').takes_value(true).value_name("N"),
+        OptSpec::new().long("a|b").help("x|y|z"),
+    ];
+
+    let mut out = String::new();
+    Help::new(SPECS).to_markdown(&mut out).unwrap();
+    assert_eq!(
+        out,
+        "| Option | Description |
+| --- | --- |
+| `-n N` |  |
+| `--a\\|b` | x\\|y\\|z |
+"
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn completions() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new()
+            .short('v')
+            .long("verbose")
+            .help("be [very] loud"),
+        OptSpec::new()
+            .long("output")
+            .takes_value(true)
+            .value_name("FILE")
+            .help("don't: overwrite"),
+        OptSpec::new().short('n').takes_value(true),
+    ];
+
+    let completions = Completions::new("my-prog", SPECS);
+    let render = |shell| {
+        let mut out = Vec::new();
+        completions.write_to(shell, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+
+    assert_eq!(
+        render(Shell::Bash),
+        r#"_my_prog() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "$prev" in
+        --output|-n)
+            COMPREPLY=($(compgen -f -- "$cur"))
+            return
+            ;;
+    esac
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "-v --verbose --output -n" -- "$cur"))
+    else
+        COMPREPLY=($(compgen -f -- "$cur"))
+    fi
+}
+complete -F _my_prog my-prog
+"#
+    );
+    assert_eq!(
+        render(Shell::Zsh),
+        r#"#compdef my-prog
+
+_arguments \
+    '(-v --verbose)'{-v,--verbose}'[be \[very\] loud]' \
+    '--output[don'\''t\: overwrite]:FILE:_files' \
+    '-n[]:VALUE:_files' \
+    '*:file:_files'
+"#
+    );
+    assert_eq!(
+        render(Shell::Fish),
+        r#"complete -c my-prog -s v -l verbose -d 'be [very] loud'
+complete -c my-prog -l output -r -d 'don'\''t: overwrite'
+complete -c my-prog -s n -r
+"#
+    );
+    assert_eq!(
+        render(Shell::Elvish),
+        r#"use str
+
+set edit:completion:arg-completer['my-prog'] = {|@words|
+    var cur = $words[-1]
+    if (and (> (count $words) 2) (has-value ['--output' '-n'] $words[-2])) {
+        edit:complete-filename $cur
+    } elif (str:has-prefix $cur -) {
+        edit:complex-candidate '-v' &display='-v (be [very] loud)'
+        edit:complex-candidate '--verbose' &display='--verbose (be [very] loud)'
+        edit:complex-candidate '--output' &display='--output (don''t: overwrite)'
+        edit:complex-candidate '-n'
+    } else {
+        edit:complete-filename $cur
+    }
+}
+"#
+    );
+    assert_eq!(
+        render(Shell::Nushell),
+        r#"export extern "my-prog" [
+    --verbose(-v) # be [very] loud
+    --output: path # don't: overwrite
+    -n: path
+    ...args: path
+]
+"#
+    );
+
+    let completions = Completions::new("a\"b", &SPECS[..1]);
+    let render = |shell| {
+        let mut out = Vec::new();
+        completions.write_to(shell, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+
+    assert!(render(Shell::Elvish).contains("    if (str:has-prefix $cur -) {\n"));
+    assert!(render(Shell::Nushell).starts_with("export extern \"a\\\"b\" [\n"));
+}
+
+#[test]
+fn suggest() {
+    use crate::suggest::{closest, edit_distance, MAX_LEN};
+
+    assert_eq!(edit_distance("", ""), Some(0));
+    assert_eq!(edit_distance("", "abc"), Some(3));
+    assert_eq!(edit_distance("abc", ""), Some(3));
+    assert_eq!(edit_distance("flaw", "lawn"), Some(2));
+    assert_eq!(edit_distance("ünï", "uni"), Some(2));
+
+    let long = "a".repeat(MAX_LEN + 1);
+    assert_eq!(edit_distance("a", &long), None);
+    assert_eq!(edit_distance(&long, "a"), Some(MAX_LEN));
+
+    let known = ["verbose", "version", "color", "colour", &long];
+    assert_eq!(closest("vrebose", &known), Some("verbose"));
+    assert_eq!(closest("versoin", &known), Some("version"));
+    assert_eq!(closest("colou", &known), Some("color"));
+    assert_eq!(closest("v", &known), None);
+    assert_eq!(closest("quiet", &known), None);
+    assert_eq!(closest("x", &[]), None);
+}
+
+#[test]
+fn validate() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('v').long("verbose"),
+        OptSpec::new().long("level").takes_value(true),
+    ];
+
+    let args = ["-vx", "--level=3", "pos", "--bogus", "--verbose"];
+    let mut opts = Options::new(args.into_iter()).validate(SPECS);
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('v'))));
+    assert_eq!(opts.next_arg(), Err(Error::UnknownOption(Opt::Short('x'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("level"))));
+    assert_eq!(opts.value(), Ok("3"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("pos"))));
+    assert_eq!(
+        opts.next_opt(),
+        Err(Error::UnknownOption(Opt::Long("bogus")))
+    );
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("verbose"))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.is_empty());
+    assert_eq!(opts.specs().len(), 2);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn required() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('a').required(true),
+        OptSpec::new().short('b'),
+        OptSpec::new().short('c').long("cee").required(true),
+    ];
+
+    let mut tracker = Tracker::new(SPECS);
+    let missing = tracker.check_required().unwrap_err();
+    assert_eq!(missing.iter().count(), 2);
+    assert_eq!(missing.to_string(), "missing required options: -a, --cee");
+    assert_eq!(missing.exit_code(), sysexits::EX_USAGE);
+
+    tracker.record(&Opt::<&str>::Long("cee")).unwrap();
+    tracker.record(&Opt::<&str>::Short('x')).unwrap();
+    assert!(tracker.is_seen(2));
+    assert!(!tracker.is_seen(1));
+    assert!(!tracker.is_seen(99));
+    let missing = tracker.check_required().unwrap_err();
+    assert_eq!(missing.iter().collect::<Vec<_>>(), [&SPECS[0]]);
+
+    tracker.record(&Opt::<&str>::Short('a')).unwrap();
+    assert_eq!(tracker.check_required(), Ok(()));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn groups() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().long("json").group(0),
+        OptSpec::new().short('y').long("yaml").group(0),
+        OptSpec::new().short('q').group(1),
+        OptSpec::new().short('v').group(1),
+        OptSpec::new().short('x'),
+    ];
+
+    let mut tracker = Tracker::new(SPECS);
+    assert_eq!(tracker.record(&Opt::<&str>::Short('y')), Ok(()));
+    assert_eq!(tracker.record(&Opt::<&str>::Short('q')), Ok(()));
+    assert_eq!(tracker.record(&Opt::<&str>::Short('x')), Ok(()));
+    assert_eq!(tracker.record(&Opt::<&str>::Long("yaml")), Ok(()));
+
+    let conflict = tracker.record(&Opt::<&str>::Long("json")).unwrap_err();
+    assert_eq!(conflict.earlier, &SPECS[1]);
+    assert_eq!(conflict.later, &SPECS[0]);
+    assert_eq!(conflict.to_string(), "--json cannot be used with --yaml");
+    assert_eq!(conflict.exit_code(), sysexits::EX_USAGE);
+    assert!(!tracker.is_seen(0));
+
+    let conflict = tracker.record(&Opt::<&str>::Short('v')).unwrap_err();
+    assert_eq!(conflict.to_string(), "-v cannot be used with -q");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn default_value() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new()
+            .short('o')
+            .takes_value(true)
+            .default_value("-"),
+        OptSpec::new().short('n').takes_value(true),
+    ];
+
+    let args = ["-n", "3"];
+    let mut opts = Options::new(args.into_iter());
+    let mut output = None;
+    let mut count = None;
+
+    while let Some((opt, values)) = opts.next_spec(SPECS).unwrap() {
+        match opt {
+            Opt::Short('o') => output = values.into_first(),
+            Opt::Short('n') => count = values.into_first(),
+            _ => unreachable!(),
+        }
+    }
+
+    let output = SPECS[0].value_or_default(output).unwrap();
+    let count = SPECS[1].value_or_default(count).unwrap();
+    assert_eq!(output, OptValue::Default("-"));
+    assert!(output.is_default());
+    assert_eq!(output.given(), None);
+    assert_eq!(output.to_string(), "-");
+    assert_eq!(count, OptValue::Given("3"));
+    assert!(!count.is_default());
+    assert_eq!(count.given(), Some("3"));
+    assert_eq!(AsRef::<str>::as_ref(&count), "3");
+    assert_eq!(SPECS[1].value_or_default(None::<&str>), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn env_value() {
+    use std::ffi::{OsStr, OsString};
+
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new()
+            .long("cache-dir")
+            .takes_value(true)
+            .env("GETARGS_TEST_CACHE_DIR"),
+        OptSpec::new()
+            .long("jobs")
+            .takes_value(true)
+            .env("GETARGS_TEST_JOBS")
+            .default_value("1"),
+    ];
+
+    std::env::set_var("GETARGS_TEST_CACHE_DIR", "/var/cache");
+    std::env::remove_var("GETARGS_TEST_JOBS");
+
+    let cache_dir = SPECS[0].value_or_env(None::<&str>).unwrap();
+    assert!(cache_dir.is_env());
+    assert_eq!(cache_dir, OptValue::Env(OsString::from("/var/cache")));
+    assert_eq!(AsRef::<OsStr>::as_ref(&cache_dir), "/var/cache");
+    assert_eq!(
+        SPECS[0].value_or_env(Some("dir")),
+        Some(OptValue::Given("dir"))
+    );
+
+    let jobs = SPECS[1].value_or_env(None::<&str>).unwrap();
+    assert!(jobs.is_default());
+    assert!(!jobs.is_env());
+
+    std::env::remove_var("GETARGS_TEST_CACHE_DIR");
+    assert_eq!(SPECS[0].value_or_env(None::<&str>), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn response_files() {
+    let dir = std::env::temp_dir().join(format!("getargs-response-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let inner = dir.join("inner");
+    let outer = dir.join("outer");
+    let cycle = dir.join("cycle");
+    std::fs::write(&inner, "-v\n'two words' \"a \\\"b\\\"\" c\\ d ''").unwrap();
+    std::fs::write(&outer, format!("--out=x @{}\n  last\n", inner.display())).unwrap();
+    std::fs::write(&cycle, format!("@{}", cycle.display())).unwrap();
+
+    let args = ["-a", "@", &format!("@{}", outer.display()), "end"].map(String::from);
+    let expanded = ResponseFiles::new(args.into_iter())
+        .collect::<std::io::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        expanded,
+        [
+            "-a",
+            "@",
+            "--out=x",
+            "-v",
+            "two words",
+            "a \"b\"",
+            "c d",
+            "",
+            "last",
+            "end"
+        ]
+    );
+
+    let args = [format!("@{}", cycle.display())];
+    let err = ResponseFiles::new(args.into_iter()).max_depth(3).next();
+    let err = err.unwrap().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(sysexits::for_io_error(&err), sysexits::EX_DATAERR);
+
+    let args = [format!("@{}", dir.join("missing").display())];
+    let err = ResponseFiles::new(args.into_iter()).next();
+    let err = err.unwrap().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert_eq!(sysexits::for_io_error(&err), sysexits::EX_NOINPUT);
+
+    let args = [format!("@{}", inner.display())];
+    let mut unexpanded = ResponseFiles::new(args.clone().into_iter()).max_depth(0);
+    assert_eq!(unexpanded.next().unwrap().unwrap(), args[0]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn inject() {
+    let args = ["-aA", "x", "--", "y"];
+    let mut opts = Options::new(Injectable::new(args.into_iter()));
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    opts.inject(["-b"]);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('A'))));
+    opts.inject(["--long=1", "-c"]);
+    opts.inject(["-d"]);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('d'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("long"))));
+    assert_eq!(opts.value(), Ok("1"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('c'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("x"));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some("y"));
+    assert_eq!(opts.next_positional(), None);
+    assert!(opts.is_empty());
+
+    opts.inject(["z"]);
+    assert!(!opts.is_empty());
+    assert!(opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some("z"));
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn aliases() {
+    let aliases = Aliases::new()
+        .alias("co", ["checkout", "--progress"])
+        .alias("mv", ["rename", "$2", "$1", "$0", "$x"])
+        .alias("co", ["checkout", "-q"]);
+
+    assert_eq!(aliases.get("co"), Some(&["checkout", "-q"][..]));
+    assert_eq!(aliases.get("checkout"), None);
+
+    let args = ["co", "-f", "main"];
+    let mut opts = Options::new(Injectable::new(args.into_iter()));
+    let command = opts.next_positional().unwrap();
+    assert!(aliases.expand(&mut opts, command));
+    let rest = opts.positionals().collect::<Vec<_>>();
+    assert_eq!(rest, ["checkout", "-q", "-f", "main"]);
+
+    let args = ["mv", "a", "b", "c"];
+    let mut opts = Options::new(Injectable::new(args.into_iter()));
+    let command = opts.next_positional().unwrap();
+    assert!(aliases.expand(&mut opts, command));
+    let rest = opts.positionals().collect::<Vec<_>>();
+    assert_eq!(rest, ["rename", "b", "a", "$0", "$x", "c"]);
+
+    let args = ["mv", "a"];
+    let mut opts = Options::new(Injectable::new(args.into_iter()));
+    let command = opts.next_positional().unwrap();
+    assert!(aliases.expand(&mut opts, command));
+    let rest = opts.positionals().collect::<Vec<_>>();
+    assert_eq!(rest, ["rename", "a", "$0", "$x"]);
+
+    let args = ["status", "-s"];
+    let mut opts = Options::new(Injectable::new(args.into_iter()));
+    let command = opts.next_positional().unwrap();
+    assert!(!aliases.expand(&mut opts, command));
+    assert_eq!(opts.next_positional(), Some("-s"));
+}
+
+#[test]
+fn subcommand_helper() {
+    let args = ["-a", "--", "cmd", "-b", "arg"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+
+    let (name, sub) = opts.subcommand().unwrap();
+    assert_eq!(name, "cmd");
+    assert!(!sub.opts_ended());
+    assert_eq!(sub.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(sub.next_opt(), Ok(None));
+    assert_eq!(sub.subcommand().map(|(name, _)| name), Some("arg"));
+    assert!(opts.subcommand().is_none());
+    assert!(opts.is_empty());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn dispatch() {
+    type Args = core::array::IntoIter<&'static str, 3>;
+
+    fn add(opts: &mut Options<&'static str, Args>) -> Result<&'static str, usize> {
+        Ok(opts.positionals().count())
+    }
+
+    const DISPATCH: Dispatch<&str, Args, usize> =
+        Dispatch::new(&[("add", add), ("remove", |_| Ok(0))]);
+
+    let mut opts = Options::new(["add", "a", "b"].into_iter());
+    assert_eq!(DISPATCH.dispatch(&mut opts), Ok(Some(2)));
+
+    let mut opts = Options::new(["-v", "remove", "x"].into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(DISPATCH.dispatch(&mut opts), Ok(Some(0)));
+    assert_eq!(opts.next_positional(), Some("x"));
+    assert_eq!(DISPATCH.dispatch(&mut opts), Ok(None));
+
+    let mut opts = Options::new(["ad", "a", "b"].into_iter());
+    let err = DISPATCH.dispatch(&mut opts).unwrap_err();
+    assert_eq!(err, Error::UnknownSubcommand("ad"));
+    assert_eq!(err.to_string(), "unknown subcommand: ad");
+    assert_eq!(err.exit_code(), sysexits::EX_USAGE);
+    assert_eq!(DISPATCH.suggest("ad"), Some("add"));
+    assert_eq!(DISPATCH.suggest("list"), None);
+    assert_eq!(DISPATCH.names().collect::<Vec<_>>(), ["add", "remove"]);
+
+    const ALIASED: Dispatch<&str, Args, usize> =
+        DISPATCH.aliases(&[("rm", "remove"), ("plus", "add"), ("gone", "missing")]);
+
+    let mut opts = Options::new(["plus", "a", "b"].into_iter());
+    assert_eq!(ALIASED.dispatch(&mut opts), Ok(Some(2)));
+    let mut opts = Options::new(["rm", "x", "y"].into_iter());
+    assert_eq!(ALIASED.dispatch(&mut opts), Ok(Some(0)));
+    let mut opts = Options::new(["gone", "x", "y"].into_iter());
+    assert_eq!(
+        ALIASED.dispatch(&mut opts),
+        Err(Error::UnknownSubcommand("gone"))
+    );
+    let mut opts = Options::new(["rm", "x", "y"].into_iter());
+    assert_eq!(
+        DISPATCH.dispatch(&mut opts),
+        Err(Error::UnknownSubcommand("rm"))
+    );
+    assert_eq!(ALIASED.suggest("plu"), Some("plus"));
+    assert_eq!(DISPATCH.suggest("plu"), None);
+    assert_eq!(ALIASED.names().collect::<Vec<_>>(), ["add", "remove"]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn iter_args() {
+    let args = ["-a", "x", "--bee=1", "--", "-c"];
+    let mut opts = Options::new(args.into_iter());
+    let mut iter = opts.iter_args();
+    assert_eq!(iter.next(), Some(Ok(Arg::Short('a'))));
+    assert_eq!(iter.next(), Some(Ok(Arg::Positional("x"))));
+    assert_eq!(iter.next(), Some(Ok(Arg::Long("bee"))));
+    assert_eq!(
+        iter.next(),
+        Some(Err(Error::DoesNotRequireValue(Opt::Long("bee"))))
+    );
+    assert_eq!(iter.next(), Some(Ok(Arg::Positional("-c"))));
+    assert_eq!(iter.next(), None);
+    assert!(opts.is_empty());
+
+    let args = ["-a", "x", "-b"];
+    let mut opts = Options::new(args.into_iter());
+    let collected: core::result::Result<Vec<_>, _> = opts.iter_args().collect();
+    assert_eq!(
+        collected,
+        Ok(vec![Arg::Short('a'), Arg::Positional("x"), Arg::Short('b')])
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn opts_iter() {
+    let args = ["-ab", "--cee=1", "x", "-d"];
+    let mut opts = Options::new(args.into_iter());
+    let mut iter = opts.opts();
+    assert_eq!(iter.next(), Some(Ok(Opt::Short('a'))));
+    assert_eq!(iter.next(), Some(Ok(Opt::Short('b'))));
+    assert_eq!(iter.next(), Some(Ok(Opt::Long("cee"))));
+    assert_eq!(
+        iter.next(),
+        Some(Err(Error::DoesNotRequireValue(Opt::Long("cee"))))
+    );
+    assert_eq!(iter.next(), None);
+    assert_eq!(opts.next_positional(), Some("x"));
+    assert_eq!(opts.opts().collect::<Vec<_>>(), [Ok(Opt::Short('d'))]);
+    assert!(opts.opts().next().is_none());
+    assert!(opts.is_empty());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn positionals_size_hint() {
+    let args = ["-a", "one", "-b", "two"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+
+    let mut positionals = opts.positionals();
+    assert_eq!(positionals.size_hint(), (3, Some(3)));
+    assert_eq!(positionals.next(), Some("one"));
+    assert_eq!(positionals.size_hint(), (2, Some(2)));
+    assert_eq!(positionals.by_ref().collect::<Vec<_>>(), ["-b", "two"]);
+    assert_eq!(positionals.size_hint(), (0, Some(0)));
+    assert_eq!(opts.operands().size_hint(), (0, Some(0)));
+
+    let args = ["-a", "one", "two"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.operands().size_hint(), (2, Some(2)));
+
+    let positionals = opts.into_positionals();
+    assert_eq!(positionals.size_hint(), (2, Some(2)));
+    assert_eq!(positionals.len(), 2);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn peek() {
+    let args = ["-ab", "--cee=1", "x", "--", "-d", "y"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.peek_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.peek_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Long("cee"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("cee"))));
+    assert_eq!(
+        opts.peek_opt(),
+        Err(Error::DoesNotRequireValue(Opt::Long("cee")))
+    );
+    assert_eq!(opts.value(), Ok("1"));
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Positional("x"))));
+    assert_eq!(opts.peek_opt(), Ok(None));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("x"))));
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Positional("-d"))));
+    assert!(!opts.opts_ended());
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Positional("-d"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("-d"))));
+    assert!(opts.opts_ended());
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Positional("y"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("y"))));
+    assert_eq!(opts.peek_arg(), Ok(None));
+    assert_eq!(opts.next_arg(), Ok(None));
+    assert!(opts.is_empty());
+
+    let args = ["-a", "--", "b", "c"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Positional("b"))));
+    assert_eq!(opts.positionals().size_hint(), (3, Some(3)));
+    assert_eq!(
+        opts.into_positionals().collect::<Vec<_>>(),
+        ["--", "b", "c"]
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn checkpoint() {
+    let args = ["-ab", "--cee", "x", "y"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+
+    let checkpoint = opts.checkpoint();
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("cee"))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("x"));
+
+    opts.restore(checkpoint);
+    assert_eq!(opts.value(), Ok("b"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("cee"))));
+
+    let checkpoint = opts.checkpoint();
+    assert_eq!(opts.value(), Ok("x"));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.positionals().collect::<Vec<_>>(), ["y"]);
+    assert!(opts.is_empty());
+
+    opts.restore(checkpoint);
+    assert!(!opts.is_empty());
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.positionals().collect::<Vec<_>>(), ["x", "y"]);
+}
+
+#[test]
+fn rewind() {
+    let args = ["-ab", "x", "--", "-c"];
+    let config = ParseConfig::new().bundled_first(true);
+    let mut opts = Options::with_config(Rewindable::new(args.into_iter()), config);
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+    opts.set_raw(true);
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Short('b'))));
+
+    opts.rewind();
+    assert!(!opts.is_raw());
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('b'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("x"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("-c"))));
+    assert_eq!(opts.next_arg(), Ok(None));
+    assert!(opts.opts_ended());
+    assert!(opts.is_empty());
+
+    opts.rewind();
+    assert!(!opts.opts_ended());
+    assert!(!opts.is_empty());
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+
+    let args = ["ab"];
+    let mut opts = Options::with_config(Rewindable::new(args.into_iter()), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    opts.rewind();
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+}
+
+#[test]
+fn arg_index() {
+    let args = ["-a", "--", "x", "y"];
+    let mut opts = Options::new(Rewindable::new(args.into_iter()));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.arg_index(), 1);
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Positional("x"))));
+    assert_eq!(opts.arg_index(), 1);
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("x"))));
+    assert_eq!(opts.arg_index(), 3);
+    assert_eq!(opts.next_positional(), Some("y"));
+    assert_eq!(opts.arg_index(), 4);
+    assert_eq!(opts.next_positional(), None);
+    assert_eq!(opts.arg_index(), 4);
+
+    opts.rewind();
+    assert_eq!(opts.arg_index(), 0);
+
+    let args = ["-o"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    assert_eq!(opts.value(), Err(Error::RequiresValue(Opt::Short('o'))));
+    assert_eq!(opts.arg_index(), 1);
+}
+
+#[test]
+fn opts_ended_forwarding() {
+    let args = ["--", "-literal"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.opts_ended());
+    assert_eq!(opts.next_positional(), Some("-literal"));
+
+    let args = ["-literal"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('l'))));
+    assert!(!opts.opts_ended());
+}
+
+#[test]
+fn current_arg() {
+    let args = ["-ab", "--cee=1", "--dee", "x", "y"];
+    let mut opts = Options::new(Rewindable::new(args.into_iter()));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.current_arg(), None);
+
+    let mut opts = Options::new(Rewindable::new(args.into_iter()));
+    opts.set_track_current_arg(true);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.current_arg(), Some("-ab"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.peek_opt(), Ok(Some(Opt::Long("cee"))));
+    assert_eq!(opts.current_arg(), Some("-ab"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("cee"))));
+    assert_eq!(opts.current_arg(), Some("--cee=1"));
+    assert_eq!(opts.value(), Ok("1"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("dee"))));
+    assert_eq!(opts.value(), Ok("x"));
+    assert_eq!(opts.current_arg(), Some("x"));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.current_arg(), Some("y"));
+
+    opts.rewind();
+    assert_eq!(opts.current_arg(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn spans() {
+    let args = [
+        "-abc", "--dee=1", "+x", "-5", "pos", "-ovalue", "--eff", "v",
+    ];
+    let config = ParseConfig::new().plus_opts(true).numeric_opts(true);
+    let mut opts = Options::with_config(args.into_iter(), config).spanned();
+    let span = |index, range| Span { index, range };
+
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('a'), span(1, 1..2)))));
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('b'), span(1, 2..3)))));
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('c'), span(1, 3..4)))));
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Long("dee"), span(2, 2..5)))));
+    assert_eq!(opts.value(), Ok(("1", span(2, 6..7))));
+    assert_eq!(
+        opts.next_arg(),
+        Ok(Some((Arg::ShortPlus('x'), span(3, 1..2))))
+    );
+    assert_eq!(
+        opts.next_opt(),
+        Ok(Some((Opt::Numeric("5"), span(4, 1..2))))
+    );
+    assert_eq!(
+        opts.next_arg(),
+        Ok(Some((Arg::Positional("pos"), span(5, 0..3))))
+    );
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('o'), span(6, 1..2)))));
+    assert_eq!(opts.value(), Ok(("value", span(6, 2..7))));
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Long("eff"), span(7, 2..5)))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some(("v", span(8, 0..1))));
+    assert_eq!(opts.next_positional(), None);
+    assert!(opts.is_empty());
+
+    let args = ["-xy", "--zed=2"].map(String::from);
+    let mut opts = Options::new(args.into_iter()).spanned();
+    assert_eq!(opts.next_opt().unwrap().unwrap().1, span(1, 1..2));
+    assert_eq!(opts.next_opt().unwrap().unwrap().1, span(1, 2..3));
+    assert_eq!(opts.next_opt().unwrap().unwrap().1, span(2, 2..5));
+    assert_eq!(opts.value(), Ok((String::from("2"), span(2, 6..7))));
+
+    let args = ["--abc=abc", "--x=--x", "-aa", "a"].map(String::from);
+    let mut opts = Options::new(args.into_iter()).spanned();
+    assert_eq!(opts.next_opt().unwrap().unwrap().1, span(1, 2..5));
+    assert_eq!(opts.value(), Ok((String::from("abc"), span(1, 6..9))));
+    assert_eq!(opts.next_opt().unwrap().unwrap().1, span(2, 2..3));
+    assert_eq!(opts.value(), Ok((String::from("--x"), span(2, 4..7))));
+    assert_eq!(opts.next_opt().unwrap().unwrap().1, span(3, 1..2));
+    assert_eq!(opts.value(), Ok((String::from("a"), span(3, 2..3))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(
+        opts.next_positional(),
+        Some((String::from("a"), span(4, 0..1)))
+    );
+
+    let args = ["/c:c", "-dee=d"].map(String::from);
+    let config = ParseConfig::new()
+        .slash_opts(true)
+        .single_dash_names(&["dee"]);
+    let mut opts = Options::with_config(args.into_iter(), config).spanned();
+    assert_eq!(opts.next_opt().unwrap().unwrap().1, span(1, 1..2));
+    assert_eq!(opts.value(), Ok((String::from("c"), span(1, 3..4))));
+    assert_eq!(opts.next_opt().unwrap().unwrap().1, span(2, 1..4));
+    assert_eq!(opts.value(), Ok((String::from("d"), span(2, 5..6))));
+
+    let args = ["@@b=@@b"].map(String::from);
+    let config = ParseConfig::new().opt_prefix(b'@');
+    let mut opts = Options::with_config(args.into_iter(), config).spanned();
+    assert_eq!(opts.next_opt().unwrap().unwrap().1, span(1, 2..3));
+    assert_eq!(opts.value(), Ok((String::from("@@b"), span(1, 4..7))));
+
+    let args = ["@ab", "+c", "de"];
+    let config = ParseConfig::new()
+        .opt_prefix(b'@')
+        .plus_opts(true)
+        .bundled_first(true);
+    let mut opts = Options::with_config(args.into_iter(), config).spanned();
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('a'), span(1, 1..2)))));
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('b'), span(1, 2..3)))));
+    assert_eq!(
+        opts.next_opt(),
+        Ok(Some((Opt::ShortPlus('c'), span(2, 1..2))))
+    );
+    assert_eq!(opts.next_opt(), Ok(None));
+
+    let args = ["de", "-f"];
+    let config = ParseConfig::new().bundled_first(true);
+    let mut opts = Options::with_config(args.into_iter(), config).spanned();
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('d'), span(1, 0..1)))));
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('e'), span(1, 1..2)))));
+    assert_eq!(opts.next_opt(), Ok(Some((Opt::Short('f'), span(2, 1..2)))));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn error_owned() {
+    fn assert_static<E: std::error::Error + Send + Sync + 'static>(_: &E) {}
+
+    let error = Error::RequiresValues {
+        opt: Opt::Long("pair"),
+        expected: 2,
+        found: 1,
+    }
+    .into_owned();
+    assert_static(&error);
+    assert_eq!(
+        error,
+        ErrorOwned(Error::RequiresValues {
+            opt: Opt::Long(String::from("pair")),
+            expected: 2,
+            found: 1,
+        })
+    );
+    assert_eq!(error.exit_code(), sysexits::EX_USAGE);
+
+    let error: ErrorOwned<Vec<u8>> = Error::UnknownOption(Opt::<&[u8]>::Short(b'x')).into();
+    assert_static(&error);
+    assert_eq!(error.to_string(), "unknown option: -x");
+
+    let error = Error::UnknownSubcommand(&b"r\xffm"[..]).into_owned();
+    assert_eq!(error.to_string(), "unknown subcommand: r\u{fffd}m");
+
+    let error = Error::<std::borrow::Cow<str>>::DoesNotRequireValue(Opt::Numeric("5".into()));
+    let error = error.into_owned();
+    assert_eq!(error.to_string(), "option does not require a value: -5");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn into_owned() {
+    let args = ["-a", "--bee", "+c", "-5", "pos"].map(String::from);
+    let config = ParseConfig::new().plus_opts(true).numeric_opts(true);
+    let mut opts = Options::with_config(args.iter().map(String::as_str), config);
+    let mut owned = Vec::new();
+
+    while let Some(arg) = opts.next_arg().unwrap() {
+        owned.push(arg.into_owned());
+    }
+
+    drop(args);
+    assert_eq!(
+        owned,
+        [
+            Arg::Short('a'),
+            Arg::Long(String::from("bee")),
+            Arg::ShortPlus('c'),
+            Arg::Numeric(String::from("5")),
+            Arg::Positional(String::from("pos")),
+        ]
+    );
+
+    assert_eq!(Opt::<&[u8]>::Short(b'x').into_owned(), Opt::Short(b'x'));
+    assert_eq!(
+        Opt::Numeric(&b"10"[..]).into_owned(),
+        Opt::Numeric(b"10".to_vec())
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn core_error() {
+    fn source<E: core::error::Error>(error: &E) -> Option<&dyn core::error::Error> {
+        error.source()
+    }
+
+    let error = Error::UnknownOption(Opt::Long("colour"));
+    assert!(source(&error).is_none());
+
+    let error: &dyn core::error::Error = &error;
+    assert_eq!(error.to_string(), "unknown option: --colour");
+}
+
+#[test]
+#[cfg(all(feature = "serde", feature = "alloc"))]
+fn serde() {
+    use serde::de::value::{self, MapAccessDeserializer, MapDeserializer};
+    use serde::Deserialize;
+
+    fn assert_serialize<T: serde::Serialize>(_: &T) {}
+
+    assert_serialize(&Opt::<&str>::Short('v'));
+    assert_serialize(&Arg::Positional(&b"file"[..]));
+    assert_serialize(&Error::RequiresValue(Opt::Long("out")));
+
+    let map = MapDeserializer::<_, value::Error>::new([("Long", "colour")].into_iter());
+    assert_eq!(
+        Opt::<String>::deserialize(MapAccessDeserializer::new(map)),
+        Ok(Opt::Long(String::from("colour")))
+    );
+
+    let map = MapDeserializer::<_, value::Error>::new([("Positional", "file")].into_iter());
+    assert_eq!(
+        Arg::<String>::deserialize(MapAccessDeserializer::new(map)),
+        Ok(Arg::Positional(String::from("file")))
+    );
+}
+
+#[test]
+#[cfg(all(feature = "serde", feature = "alloc"))]
+fn serde_from_iter() {
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Color {
+        Auto,
+        Never,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "kebab-case")]
+    struct Config<'a> {
+        #[serde(default, alias = "v")]
+        verbose: bool,
+        #[serde(default, alias = "q")]
+        quiet: bool,
+        jobs: Option<u32>,
+        #[serde(alias = "o")]
+        output: &'a str,
+        color: Option<Color>,
+    }
+
+    let config: Config = crate::from_iter(["-vqo", "out", "--color=never"]).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            verbose: true,
+            quiet: true,
+            jobs: None,
+            output: "out",
+            color: Some(Color::Never),
+        }
+    );
+
+    let config: Config =
+        crate::from_iter(["--verbose=false", "--output", "x", "--jobs", "2"]).unwrap();
+    assert!(!config.verbose);
+    assert_eq!(config.jobs, Some(2));
+    assert_eq!(config.color, None);
+
+    assert_eq!(
+        crate::from_iter::<Config, _>(["-o", "x", "--jobs=many"]),
+        Err(de::Error::InvalidValue {
+            opt: Opt::Long(String::from("jobs")),
+            value: String::from("many"),
+            reason: String::from("invalid digit found in string"),
+        })
+    );
+    assert_eq!(
+        crate::from_iter::<Config, _>(["-v"]),
+        Err(de::Error::Custom(String::from("missing field `output`")))
+    );
+    assert_eq!(
+        crate::from_iter::<Config, _>(["--output"]),
+        Err(de::Error::Parse(ErrorOwned(Error::RequiresValue(
+            Opt::Long(String::from("output"))
+        ))))
+    );
+    assert_eq!(
+        crate::from_iter::<Config, _>(["-o", "x", "extra"]),
+        Err(de::Error::UnexpectedPositional(String::from("extra")))
+    );
+    assert_eq!(
+        crate::from_iter::<Config, _>(["-o", "x", "--color=always"])
+            .unwrap_err()
+            .exit_code(),
+        sysexits::EX_USAGE
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn opt_hash_ord() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let args = ["-b", "--alpha", "-a", "-b", "pos"];
+
+    let mut counts = HashMap::new();
+    let mut opts = Options::new(args.into_iter());
+    while let Some(opt) = opts.next_opt().unwrap() {
+        *counts.entry(opt).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&Opt::Short('b')], 2);
+    assert_eq!(counts[&Opt::Long("alpha")], 1);
+
+    let mut sorted = BTreeMap::new();
+    let mut opts = Options::new(args.into_iter());
+    while let Some(arg) = opts.next_arg().unwrap() {
+        sorted.insert(arg, ());
+    }
+    assert_eq!(
+        sorted.into_keys().collect::<Vec<_>>(),
+        [
+            Arg::Short('a'),
+            Arg::Short('b'),
+            Arg::Long("alpha"),
+            Arg::Positional("pos"),
+        ]
+    );
+    assert!(Opt::Short('z') < Opt::Long("a"));
+}
+
+#[test]
+fn opt_is() {
+    let mut opts = Options::new(["-o", "a", "--output=b", "-x", "--out"].into_iter());
+    let mut outputs = 0;
+    while let Some(opt) = opts.next_opt().unwrap() {
+        if opt.is('o', "output") {
+            opts.value().unwrap();
+            outputs += 1;
+        }
+    }
+    assert_eq!(outputs, 2);
+
+    assert!(Opt::Long(&b"output"[..]).is(b'o', &b"output"[..]));
+    assert!(!Opt::<&[u8]>::Short(b'x').is(b'o', &b"output"[..]));
+}
+
+#[test]
+fn opt_match() {
+    fn parse<'a>(args: &[&'a [u8]]) -> Result<&'a [u8], (bool, Option<&'a [u8]>)> {
+        let mut opts = Options::new(args.iter().copied());
+        let mut verbose = false;
+        let mut output = None;
+
+        while let Some(opt) = opts.next_opt()? {
+            crate::opt_match! { opt,
+                (b'v' | b"verbose" | b"loud") => verbose = true,
+                (b'o' | b"output") => output = Some(opts.value()?),
+            }?;
+        }
+
+        Ok((verbose, output))
+    }
+
+    assert_eq!(
+        parse(&[b"--loud", b"-o", b"x"]),
+        Ok((true, Some(&b"x"[..])))
+    );
+    assert_eq!(parse(&[b"--output=y"]), Ok((false, Some(&b"y"[..]))));
+    assert_eq!(
+        parse(&[b"-vq"]),
+        Err(Error::UnknownOption(Opt::Short(b'q')))
+    );
+
+    let opt = Opt::Long("help");
+    assert_eq!(
+        crate::opt_match! { opt, ('h' | "help") => 1, ("version") => 2 },
+        Ok(1)
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn value_parsed() {
+    let args = ["-n", "-5", "--ratio=0.5", "--count", "x", "-n"];
+    let mut opts = Options::new(args.into_iter());
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('n'))));
+    assert_eq!(opts.value_parsed::<i32>(), Ok(-5));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("ratio"))));
+    assert_eq!(opts.value_parsed::<f64>(), Ok(0.5));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("count"))));
+    assert_eq!(
+        opts.value_parsed::<u8>(),
+        Err(ValueError::InvalidValue {
+            opt: Opt::Long("count"),
+            value: "x",
+            error: "x".parse::<u8>().unwrap_err(),
+        })
+    );
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('n'))));
+    assert_eq!(
+        opts.value_parsed::<i32>(),
+        Err(ValueError::RequiresValue(Opt::Short('n')))
+    );
+    assert_eq!(
+        ValueError::<&str, core::num::ParseIntError>::RequiresValue(Opt::Short('n')).to_string(),
+        "option requires a value: -n"
+    );
+
+    type IntError<'a> = ValueError<&'a str, core::num::ParseIntError>;
+    assert_eq!(
+        IntError::try_from(Error::RequiresValue(Opt::Short('n'))),
+        Ok(ValueError::RequiresValue(Opt::Short('n')))
+    );
+    assert_eq!(
+        IntError::try_from(Error::UnknownOption(Opt::Short('x'))),
+        Err(Error::UnknownOption(Opt::Short('x')))
+    );
+}
+
+#[test]
+fn value_or() {
+    let args = ["-c", "-cnever", "--color", "auto"];
+    let mut opts = Options::new(args.into_iter());
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('c'))));
+    assert_eq!(opts.value_or("always"), "always");
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('c'))));
+    assert_eq!(opts.value_or_else(|| unreachable!()), "never");
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("color"))));
+    assert_eq!(opts.value_or_else(|| "always"), "always");
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("auto"));
+
+    let mut opts = Options::new(["--color="].into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("color"))));
+    assert_eq!(opts.value_or("always"), "");
+}
+
+#[test]
+fn values() {
+    let args = ["-Ia", "b", "-", "-vI", "--include", "c", "--x"];
+    let mut opts = Options::new(args.into_iter());
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('I'))));
+    assert!(opts.values().eq(["a", "b", "-"]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('I'))));
+    assert_eq!(opts.values().next(), None);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("include"))));
+    assert!(opts.values().eq(["c"]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("x"))));
+    assert_eq!(opts.values().next(), None);
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert!(opts.is_empty());
+
+    let args = ["--pair", "1", "2", "3"];
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("pair"))));
+    assert!(opts.values().take(2).eq(["1", "2"]));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("3"));
+
+    let args = ["--point", "-5", "-1.5", "x", "-v"];
+    let config = ParseConfig::new().negative_numbers(true);
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("point"))));
+    assert!(opts.values().eq(["-5", "-1.5", "x"]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+
+    let args = ["@I", "a", "-b", "@@x", "+y"];
+    let config = ParseConfig::new().opt_prefix(b'@').plus_opts(true);
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('I'))));
+    assert!(opts.values().eq(["a", "-b"]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("x"))));
+    assert_eq!(opts.values().next(), None);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::ShortPlus('y'))));
+}
+
+#[test]
+#[should_panic]
+fn values_without_opt() {
+    let mut opts = Options::new(["a"].into_iter());
+    opts.values();
+}
+
+#[test]
+fn values_delimited() {
+    let args = ["--list=a,,b,", "-l", "", "--list"];
+    let mut opts = Options::new(args.into_iter());
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("list"))));
+    assert!(opts.values_delimited(b',').unwrap().eq(["a", "", "b", ""]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('l'))));
+    assert!(opts.values_delimited(b',').unwrap().eq([""]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("list"))));
+    assert_eq!(
+        opts.values_delimited(b',').map(Iterator::count),
+        Err(Error::RequiresValue(Opt::Long("list")))
+    );
+
+    assert!(Delimited::new("é;ü", b';').eq(["é", "ü"]));
+}
+
+#[test]
+fn value_pair() {
+    let args: [&[u8]; 3] = [b"-Dk=v", b"--define", b"=x"];
+    let mut opts = Options::new(args.into_iter());
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'D'))));
+    assert_eq!(opts.value_pair(), Ok((&b"k"[..], Some(&b"v"[..]))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(&b"define"[..]))));
+    assert_eq!(opts.value_pair(), Ok((&b""[..], Some(&b"x"[..]))));
+    assert_eq!(opts.next_opt(), Ok(None));
+}
+
+#[test]
+fn occurrences() {
+    const VERBOSE: OptSpec = OptSpec::new().short('v').long("verbose");
+
+    let args = ["-vxv", "--verbose", "--verbose-ish", "-v", "pos", "-v"];
+    let mut verbosity = Occurrences::new(&VERBOSE);
+    let mut opts = Options::new(args.into_iter()).observe(&mut verbosity);
+    while opts.next_opt().unwrap().is_some() {}
+    drop(opts);
+
+    assert_eq!(verbosity.count(), 4);
+    assert_eq!(verbosity.spec().long, Some("verbose"));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn utf8_options() {
+    let args: [&[u8]; 5] = [b"-ab", b"\xe2\x82\xac", b"--", b"x\x80", b"y"];
+    let mut opts = Utf8Options::new(args.into_iter());
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('a'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('b'))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Ok(Some("€")));
+    assert_eq!(opts.next_positional(), Ok(Some("--")));
+    assert_eq!(
+        opts.next_positional(),
+        Err(Error::InvalidUtf8 {
+            arg_index: 4,
+            valid_up_to: 1,
+        })
+    );
+    assert_eq!(opts.next_positional(), Ok(None));
+
+    let mut opts = Utf8Options::new([&b"-o"[..], b"\xff"].into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    let error = opts.value().unwrap_err();
+    assert_eq!(error.to_string(), "argument 2 is not valid UTF-8");
+    assert_eq!(error.exit_code(), sysexits::EX_DATAERR);
+}
+
+#[test]
+fn map_to_str() {
+    let args: [&[u8]; 4] = [b"-ab\xff", b"--", b"x", b"\x80"];
+
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'a'))));
+    assert_eq!(
+        opts.map_to_str().unwrap_err(),
+        Error::InvalidUtf8 {
+            arg_index: 1,
+            valid_up_to: 1,
+        }
+    );
+
+    let mut opts = Options::new(args.into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'a'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'b'))));
+    assert_eq!(opts.value(), Ok(&b"\xff"[..]));
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Positional(&b"x"[..]))));
+
+    let mut opts = opts.map_to_str().unwrap();
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("x"))));
+    assert!(opts.opts_ended());
+    assert_eq!(
+        opts.next_arg(),
+        Err(Error::InvalidUtf8 {
+            arg_index: 4,
+            valid_up_to: 0,
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "unicode")]
+fn graphemes() {
+    let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+    let thumbs = "\u{1f44d}\u{1f3fd}";
+    let cluster = format!("-{}{}a\u{308}\u{301}", family, thumbs);
+
+    let mut opts = Options::new([cluster.as_str(), "-\u{301}"].into_iter().map(Graphemes));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(family))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(thumbs))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short("a\u{308}\u{301}"))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short("\u{301}"))));
+    assert_eq!(opts.next_opt(), Ok(None));
+
+    let mut opts = Options::new(["-oé"].into_iter().map(Graphemes));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short("o"))));
+    assert_eq!(opts.value(), Ok(Graphemes("é")));
+    assert_eq!(
+        Error::UnknownOption(Opt::<Graphemes>::Short("e\u{301}")).to_string(),
+        "unknown option: -e\u{301}"
+    );
+}
+
+#[test]
+#[cfg(feature = "unicode")]
+fn graphemes_indic() {
+    // Bengali ki, Devanagari ksha, Tamil ko, Telugu kaa and a Devanagari
+    // stress mark, which are all one cluster each.
+    let args = [
+        "-\u{995}\u{9bf}\u{995}\u{9bc}",
+        "-\u{915}\u{94d}\u{937}\u{915}\u{951}\u{915}\u{962}",
+        "-\u{b95}\u{bca}\u{c15}\u{c3e}\u{c02}",
+    ];
+
+    let mut opts = Options::new(args.into_iter().map(Graphemes));
+    for short in [
+        "\u{995}\u{9bf}",
+        "\u{995}\u{9bc}",
+        "\u{915}\u{94d}\u{937}",
+        "\u{915}\u{951}",
+        "\u{915}\u{962}",
+        "\u{b95}\u{bca}",
+        "\u{c15}\u{c3e}\u{c02}",
+    ] {
+        assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(short))));
+    }
+
+    assert_eq!(opts.next_opt(), Ok(None));
+
+    let mut opts = Options::new(["-\u{9cd}x", "-\u{93f}"].into_iter().map(Graphemes));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short("\u{9cd}x"))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short("\u{93f}"))));
+    assert_eq!(opts.next_opt(), Ok(None));
+}
+
+#[test]
+fn str_short_cluster_mixed() {
+    let mut opts = Options::new(["-xé€v😀", "-"].into_iter());
+
+    for short in ['x', 'é', '€', 'v', '😀'] {
+        assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(short))));
+    }
+
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("-"));
+    assert_eq!("é".consume_short_opt(), ('é', None));
+    assert_eq!("ab".consume_short_opt(), ('a', Some("b")));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn try_fold_args() {
+    #[derive(Debug, PartialEq)]
+    enum CliError {
+        Parse(Error<&'static str>),
+        TooMany,
+    }
+
+    impl From<Error<&'static str>> for CliError {
+        fn from(error: Error<&'static str>) -> Self {
+            CliError::Parse(error)
+        }
+    }
+
+    let count = |args: &[&'static str]| {
+        let mut opts = Options::new(args.iter().copied());
+        opts.try_fold_args(0, |n, _, _| {
+            if n < 2 {
+                Ok(n + 1)
+            } else {
+                Err(CliError::TooMany)
+            }
+        })
+    };
+
+    assert_eq!(count(&[]), Ok(0));
+    assert_eq!(count(&["-ab"]), Ok(2));
+    assert_eq!(count(&["-abc"]), Err(CliError::TooMany));
+    assert_eq!(
+        count(&["--a=1"]),
+        Err(CliError::Parse(Error::DoesNotRequireValue(Opt::Long("a"))))
+    );
+
+    let mut opts = Options::new(["-x", "y"].into_iter());
+    let mut seen = Vec::new();
+    opts.try_for_each_arg(|_, arg| {
+        seen.push(arg);
+        Ok::<_, Error<_>>(())
+    })
+    .unwrap();
+    assert_eq!(seen, [Arg::Short('x'), Arg::Positional("y")]);
+    assert!(opts.is_empty());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn windows_args() {
+    fn split(line: &str) -> Vec<String> {
+        let mut line = line.as_bytes().to_vec();
+        WindowsArgs::new(&mut line)
+            .map(|arg| String::from_utf8(arg.to_vec()).unwrap())
+            .collect()
+    }
+
+    assert_eq!(split(""), Vec::<String>::new());
+    assert_eq!(split("  prog  a\tb  "), ["prog", "a", "b"]);
+    assert_eq!(
+        split(r#""C:\a b\prog.exe"x y"#),
+        [r"C:\a b\prog.exe", "x", "y"]
+    );
+    assert_eq!(split(r#""C:\unterminated"#), [r"C:\unterminated"]);
+    assert_eq!(split(r#"p a"b c"d "" x"#), ["p", "ab cd", "", "x"]);
+    assert_eq!(
+        split(r#"p a\\\"b a\\"b c" a\\b\"#),
+        ["p", r#"a\"b"#, r"a\b c", r"a\\b\"]
+    );
+    assert_eq!(
+        split(r#"p "a""b" "unterminated"#),
+        ["p", r#"a"b"#, "unterminated"]
+    );
+
+    let mut line = r#"-x "ü ß""#.encode_utf16().collect::<Vec<u16>>();
+    let args = WindowsArgs::new(&mut line).program_name(false);
+    let mut opts = Options::new(args);
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'x' as u16))));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(
+        opts.next_positional(),
+        Some(&"ü ß".encode_utf16().collect::<Vec<u16>>()[..])
+    );
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn shell_words() {
+    fn split(line: &str) -> Vec<String> {
+        let mut line = line.as_bytes().to_vec();
+        ShellWords::new(&mut line)
+            .map(|word| String::from_utf8(word.to_vec()).unwrap())
+            .collect()
+    }
+
+    assert_eq!(split(""), Vec::<String>::new());
+    assert_eq!(split(" \t\n "), Vec::<String>::new());
+    assert_eq!(split(" a  b\tc\nd "), ["a", "b", "c", "d"]);
+    assert_eq!(split("'' a'b c'd \"\""), ["", "ab cd", ""]);
+    assert_eq!(split(r#"'a\b' "a\b" a\b"#), [r"a\b", r"a\b", "ab"]);
+    assert_eq!(split(r#""\$\`\"\\\'""#), [r#"$`"\\'"#]);
+    assert_eq!(split("a\\\nb \"c\\\nd\" 'e\\\nf'"), ["ab", "cd", "e\\\nf"]);
+    assert_eq!(split("a \\\n b\\\n \\\n"), ["a", "b"]);
+    assert_eq!(split(r"a\ b trailing\"), ["a b", r"trailing\"]);
+    assert_eq!(split("# comment\na #b c\nc#d\n# end"), ["a", "c#d"]);
+    assert_eq!(split("a 'unterminated b"), ["a", "unterminated b"]);
+    assert_eq!(split("x\"é\" 日本"), ["xé", "日本"]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn from_cmdline_str() {
+    use std::borrow::Cow;
+
+    let words = CmdlineWords::new(" plain 'quo ted' back\\ slash x\"é\" 日本 # c")
+        .map(|word| match word {
+            Cow::Borrowed(word) => (word.to_owned(), true),
+            Cow::Owned(word) => (word, false),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        words,
+        [
+            ("plain".to_owned(), true),
+            ("quo ted".to_owned(), false),
+            ("back slash".to_owned(), false),
+            ("xé".to_owned(), false),
+            ("日本".to_owned(), true),
+        ]
+    );
+
+    let words = CmdlineWords::new("a \\\n b").collect::<Vec<_>>();
+    assert_eq!(words, ["a", "b"]);
+
+    let mut opts = Options::from_cmdline_str("-vo 'out file' -- -x");
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    assert_eq!(opts.value().as_deref(), Ok("out file"));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional().as_deref(), Some("-x"));
+    assert_eq!(opts.next_positional(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn getopt() {
+    let args = ["-ab", "x", "-cval", "-c", "-d", "--long", "pos", "-a"];
+    let mut opts = Options::new(args.into_iter()).getopt("ab:c::");
+
+    assert_eq!(opts.next(), Some(('a', None)));
+    assert_eq!(opts.next(), Some(('b', Some("x"))));
+    assert_eq!(opts.next(), Some(('c', Some("val"))));
+    assert_eq!(opts.next(), Some(('c', None)));
+    assert_eq!(opts.optopt(), None);
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(opts.optopt(), Some('d'));
+    assert_eq!(opts.error(), Some(&Error::UnknownOption(Opt::Short('d'))));
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(opts.optopt(), None);
+    assert_eq!(opts.error(), Some(&Error::UnknownOption(Opt::Long("long"))));
+    assert_eq!(opts.next(), None);
+    assert_eq!(opts.error(), None);
+    assert_eq!(opts.next_positional(), Some("pos"));
+    assert_eq!(opts.next_positional(), Some("-a"));
+
+    let mut opts = Options::new(["-b"].into_iter()).getopt("b:");
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(opts.error(), Some(&Error::RequiresValue(Opt::Short('b'))));
+
+    let mut opts = Options::new(["-b"].into_iter()).getopt("+:b:");
+    assert_eq!(opts.next(), Some((':', None)));
+    assert_eq!(opts.optopt(), Some('b'));
+
+    let mut opts = Options::new(["-:", "-:"].into_iter()).getopt("::");
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(opts.optopt(), Some(':'));
+
+    let args = ["a", "-x", "b", "--", "-x"];
+    let opts = Options::new(args.into_iter()).getopt("-x");
+    assert_eq!(
+        opts.collect::<Vec<_>>(),
+        [
+            ('\u{1}', Some("a")),
+            ('x', None),
+            ('\u{1}', Some("b")),
+            ('\u{1}', Some("-x"))
+        ]
+    );
+
+    let args: [&[u8]; 2] = [b"-ob", b"c"];
+    let opts = Options::new(args.into_iter()).getopt("bo:");
+    assert_eq!(opts.collect::<Vec<_>>(), [('o', Some(&b"b"[..]))]);
+}
+
+#[test]
+fn getopt_long() {
+    const LONGOPTS: &[LongOptSpec] = &[
+        LongOptSpec::new("verbose", HasArg::No, 'v'),
+        LongOptSpec::new("version", HasArg::No, 'V'),
+        LongOptSpec::new("output", HasArg::Required, 'o'),
+        LongOptSpec::new("out", HasArg::Required, 'O'),
+        LongOptSpec::new("color", HasArg::Optional, 'c'),
+        LongOptSpec::new("colour", HasArg::Optional, 'c'),
+    ];
+
+    let args = [
+        "--verbose=1",
+        "--ver",
+        "--out",
+        "a",
+        "--outp=b",
+        "--col",
+        "x",
+        "--colo=auto",
+        "--output",
+    ];
+    let mut opts = Options::new(args.into_iter()).getopt_long(":", LONGOPTS);
+
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(opts.optopt(), Some('v'));
+    assert_eq!(opts.longindex(), Some(0));
+    assert_eq!(
+        opts.error(),
+        Some(&Error::DoesNotRequireValue(Opt::Long("verbose")))
+    );
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(opts.optopt(), None);
+    assert_eq!(opts.error(), Some(&Error::UnknownOption(Opt::Long("ver"))));
+    assert_eq!(opts.next(), Some(('O', Some("a"))));
+    assert_eq!(opts.longindex(), Some(3));
+    assert_eq!(opts.next(), Some(('o', Some("b"))));
+    assert_eq!(opts.next(), Some(('c', None)));
+    assert_eq!(opts.longindex(), Some(4));
+    assert_eq!(opts.next(), None);
+    assert_eq!(opts.next_positional(), Some("x"));
+
+    let mut opts = Options::new(["--colo=auto", "--output"].into_iter()).getopt_long(":", LONGOPTS);
+    assert_eq!(opts.next(), Some(('c', Some("auto"))));
+    assert_eq!(opts.next(), Some((':', None)));
+    assert_eq!(opts.optopt(), Some('o'));
+    assert_eq!(opts.longindex(), Some(2));
+    assert_eq!(opts.next(), None);
+
+    let args: [&[u8]; 3] = [b"-x", b"--verb", b"--bogus"];
+    let mut opts = Options::new(args.into_iter()).getopt_long("x", LONGOPTS);
+    assert_eq!(opts.next(), Some(('x', None)));
+    assert_eq!(opts.next(), Some(('v', None)));
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(opts.next(), None);
+}
+
+#[test]
+fn getopt_long_w() {
+    const LONGOPTS: &[LongOptSpec] = &[
+        LongOptSpec::new("flag", HasArg::No, 'f'),
+        LongOptSpec::new("file", HasArg::Required, 'F'),
+        LongOptSpec::new("level", HasArg::Optional, 'l'),
+    ];
+
+    let args = [
+        "-W",
+        "fl",
+        "-Wfile",
+        "a",
+        "-W",
+        "file=b",
+        "-Wlevel",
+        "-Wlevel=2",
+        "-Wflag=x",
+        "-Wnope",
+        "-W",
+    ];
+    let mut opts = Options::new(args.into_iter()).getopt_long(":aW;", LONGOPTS);
+
+    assert_eq!(opts.next(), Some(('f', None)));
+    assert_eq!(opts.longindex(), Some(0));
+    assert_eq!(opts.next(), Some(('F', Some("a"))));
+    assert_eq!(opts.next(), Some(('F', Some("b"))));
+    assert_eq!(opts.next(), Some(('l', None)));
+    assert_eq!(opts.next(), Some(('l', Some("2"))));
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(
+        opts.error(),
+        Some(&Error::DoesNotRequireValue(Opt::Long("flag")))
+    );
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(opts.error(), Some(&Error::UnknownOption(Opt::Long("nope"))));
+    assert_eq!(opts.next(), Some((':', None)));
+    assert_eq!(opts.optopt(), Some('W'));
+    assert_eq!(opts.next(), None);
+
+    let mut opts = Options::new(["-Wfile", "--file"].into_iter()).getopt_long("W;", LONGOPTS);
+    assert_eq!(opts.next(), Some(('F', Some("--file"))));
+
+    let mut opts = Options::new(["-Wfile"].into_iter()).getopt_long("W;", LONGOPTS);
+    assert_eq!(opts.next(), Some(('?', None)));
+    assert_eq!(opts.optopt(), Some('F'));
+    assert_eq!(opts.error(), Some(&Error::RequiresValue(Opt::Long("file"))));
+
+    let mut opts = Options::new(["-W", "flag"].into_iter()).getopt_long("W", LONGOPTS);
+    assert_eq!(opts.next(), Some(('W', None)));
+    assert_eq!(opts.next(), None);
+    assert_eq!(opts.next_positional(), Some("flag"));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn posixly_correct_from_env() {
+    let parse = || {
+        let config = ParseConfig::new().posixly_correct_from_env();
+        let mut opts = Options::with_config(["-a", "file", "-b"].into_iter(), config);
+        let mut args = Vec::new();
+
+        while let Some(arg) = opts.next_arg().unwrap() {
+            args.push(arg);
+        }
+
+        args
+    };
+
+    std::env::remove_var("POSIXLY_CORRECT");
+    assert_eq!(
+        parse(),
+        [Arg::Short('a'), Arg::Positional("file"), Arg::Short('b')]
+    );
+
+    std::env::set_var("POSIXLY_CORRECT", "");
+    assert_eq!(
+        parse(),
+        [
+            Arg::Short('a'),
+            Arg::Positional("file"),
+            Arg::Positional("-b")
+        ]
+    );
+
+    std::env::remove_var("POSIXLY_CORRECT");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn reject_empty_values() {
+    let config = ParseConfig::new()
+        .reject_empty_values(true)
+        .single_dash_long(true);
+    let args = ["--name=", "--name=x", "-geometry=", "--flag", "pos"];
+    let mut opts = Options::with_config(args.into_iter(), config);
+
+    assert_eq!(opts.next_arg(), Err(Error::EmptyValue(Opt::Long("name"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("name"))));
+    assert_eq!(opts.value(), Ok("x"));
+    assert_eq!(
+        opts.next_arg(),
+        Err(Error::EmptyValue(Opt::Long("geometry")))
+    );
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("flag"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("pos"))));
+    assert_eq!(opts.next_arg(), Ok(None));
+
+    assert_eq!(
+        Error::EmptyValue(Opt::Long("name")).to_string(),
+        "option requires a non-empty value: --name"
+    );
+
+    let mut opts = Options::new(["--name="].into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("name"))));
+    assert_eq!(opts.value(), Ok(""));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn reject_lone_dash() {
+    let config = ParseConfig::new().reject_lone_dash(true);
+    let args = ["-a", "-", "b", "-", "--", "-"];
+    let mut opts = Options::with_config(args.into_iter(), config);
+
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+    assert_eq!(opts.next_arg(), Err(Error::LoneDash));
+    assert_eq!(opts.arg_index(), 2);
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("b"))));
+    assert_eq!(opts.next_arg(), Err(Error::LoneDash));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("-"))));
+    assert_eq!(opts.next_arg(), Ok(None));
+    assert_eq!(
+        Error::<&str>::LoneDash.to_string(),
+        "unexpected argument: -"
+    );
+
+    let args: [&[u8]; 3] = [b"-", b"a-", b"-"];
+    let mut opts = Options::with_config(args.into_iter(), config);
+    assert_eq!(opts.next_opt(), Err(Error::LoneDash));
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some(&b"a-"[..]));
+    assert_eq!(opts.next_positional(), Some(&b"-"[..]));
+
+    let mut opts = Options::new(["-"].into_iter());
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("-"))));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn negative_numbers() {
+    let config = ParseConfig::new().negative_numbers(true);
+    let args = [
+        "-5", "-v", "-12.5", "-1.", "-1.2.3", "-.5", "-5v", "-n", "-3", "--", "-4",
+    ];
+    let mut opts = Options::with_config(args.into_iter(), config);
+    let mut parsed = Vec::new();
+
+    while let Some(arg) = opts.next_arg().unwrap() {
+        parsed.push(arg);
+
+        if arg == Arg::Short('n') {
+            parsed.push(Arg::Positional(opts.value().unwrap()));
+        }
+    }
+
+    assert_eq!(
+        parsed,
+        [
+            Arg::Positional("-5"),
+            Arg::Short('v'),
+            Arg::Positional("-12.5"),
+            Arg::Short('1'),
+            Arg::Short('.'),
+            Arg::Short('1'),
+            Arg::Short('.'),
+            Arg::Short('2'),
+            Arg::Short('.'),
+            Arg::Short('3'),
+            Arg::Short('.'),
+            Arg::Short('5'),
+            Arg::Short('5'),
+            Arg::Short('v'),
+            Arg::Short('n'),
+            Arg::Positional("-3"),
+            Arg::Positional("-4"),
+        ]
+    );
+
+    let config = config.numeric_opts(true);
+    let mut opts = Options::with_config(["-5"].into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.next_positional(), Some("-5"));
+}
+
+#[test]
+fn short_equals() {
+    let config = ParseConfig::new().short_equals(true);
+    let args = ["-o=file", "-vo=a=b", "-o==", "-o=", "-oval", "-o", "=x"];
+    let mut opts = Options::with_config(args.into_iter(), config);
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    assert_eq!(opts.value(), Ok("file"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    assert_eq!(opts.value_opt(), Some("a=b"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    assert_eq!(opts.value(), Ok("="));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    assert_eq!(opts.value(), Ok(""));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    assert_eq!(opts.value(), Ok("val"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    assert_eq!(opts.value(), Ok("=x"));
+
+    let mut opts = Options::new(["-o=file"].into_iter());
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+    assert_eq!(opts.value(), Ok("=file"));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn opt_prefix() {
+    let config = ParseConfig::new().opt_prefix(b'@');
+    let args = ["@ab", "@@long=1", "@@flag", "@o", "-x", "@", "--", "@c"];
+    let mut opts = Options::with_config(args.into_iter(), config);
+
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('a'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('b'))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("long"))));
+    assert_eq!(opts.value(), Ok("1"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Long("flag"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Short('o'))));
+    assert_eq!(opts.value(), Ok("-x"));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("@"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("@c"))));
+    assert_eq!(opts.next_arg(), Ok(None));
+
+    let config = config.negative_numbers(true);
+    let mut opts = Options::with_config(["@5", "-5"].into_iter(), config);
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.positionals().collect::<Vec<_>>(), ["@5", "-5"]);
+}
+
+#[test]
+#[should_panic(expected = "opt_prefix must be ASCII")]
+fn opt_prefix_non_ascii() {
+    let _ = ParseConfig::new().opt_prefix(0xC3);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "opt_prefix must be ASCII")]
+fn opt_prefix_non_ascii_field() {
+    let mut config = ParseConfig::new();
+    config.opt_prefix = 0xC3;
+    let mut opts = Options::with_config([String::from("\u{e9}x")].into_iter(), config);
+    let _ = opts.next_opt();
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn terminator() {
+    let parse = |terminator, args: &[&'static str]| {
+        let config = ParseConfig::new().terminator(terminator);
+        let mut opts = Options::with_config(args.iter().copied(), config);
+        let mut parsed = Vec::new();
+
+        while let Some(arg) = opts.next_arg().unwrap() {
+            parsed.push(arg);
+        }
+
+        parsed
+    };
+
+    let args = ["-a", "--", "-b", ";", "-c"];
+
+    assert_eq!(
+        parse(Terminator::DoubleDash, &args),
+        [
+            Arg::Short('a'),
+            Arg::Positional("-b"),
+            Arg::Positional(";"),
+            Arg::Positional("-c")
+        ]
+    );
+    assert_eq!(
+        parse(Terminator::Char(b';'), &args),
+        [
+            Arg::Short('a'),
+            Arg::Positional("--"),
+            Arg::Short('b'),
+            Arg::Positional("-c")
+        ]
+    );
+    assert_eq!(
+        parse(Terminator::Disabled, &args),
+        [
+            Arg::Short('a'),
+            Arg::Positional("--"),
+            Arg::Short('b'),
+            Arg::Positional(";"),
+            Arg::Short('c')
+        ]
+    );
+    assert_eq!(
+        parse(Terminator::Char(b';'), &[";;", "-x;", ";"]),
+        [Arg::Positional(";;"), Arg::Short('x'), Arg::Short(';')]
+    );
+
+    let config = ParseConfig::new().terminator(Terminator::Char(b';'));
+    let mut opts = Options::with_config([";", "-x"].into_iter(), config);
+    assert_eq!(opts.peek_arg(), Ok(Some(Arg::Positional("-x"))));
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("-x"))));
+}
+
+#[test]
+fn single_dash_names() {
+    const CONFIG: ParseConfig = ParseConfig::new().single_dash_names(&["std", "mcpu", "é"]);
+
+    let args = [
+        "-std=c++17",
+        "-mcpu",
+        "cortex-m4",
+        "-mc",
+        "-stdx",
+        "-é",
+        "-O2",
+    ];
+    let mut opts = Options::with_config(args.into_iter(), CONFIG);
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("std"))));
+    assert_eq!(opts.value(), Ok("c++17"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("mcpu"))));
+    assert_eq!(opts.value(), Ok("cortex-m4"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('m'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('c'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('s'))));
+    assert_eq!(opts.value(), Ok("tdx"));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('é'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('O'))));
+    assert_eq!(opts.value(), Ok("2"));
+    assert_eq!(opts.next_opt(), Ok(None));
+
+    let args: [&[u8]; 2] = [b"-std=c", b"-sv"];
+    let mut opts = Options::with_config(args.into_iter(), CONFIG);
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(&b"std"[..]))));
+    assert_eq!(opts.value(), Ok(&b"c"[..]));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b's'))));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'v'))));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn spec_table_abbreviations() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('v').long("verbose"),
+        OptSpec::new().long("version"),
+        OptSpec::new().long("version-info"),
+        OptSpec::new().long("output"),
+    ];
+
+    let exact = SpecTable::new(SPECS);
+    let table = exact.abbreviations(true);
+
+    assert_eq!(exact.find(&Opt::Long("out")), Ok(None));
+    assert_eq!(table.find(&Opt::Long("out")), Ok(Some(&SPECS[3])));
+    assert_eq!(table.find(&Opt::Long("output")), Ok(Some(&SPECS[3])));
+    assert_eq!(table.find(&Opt::Long("outputs")), Ok(None));
+    assert_eq!(table.find(&Opt::Long("verb")), Ok(Some(&SPECS[0])));
+    assert_eq!(table.find(&Opt::Long("version")), Ok(Some(&SPECS[1])));
+    assert_eq!(table.find(&Opt::Long("version-")), Ok(Some(&SPECS[2])));
+    assert_eq!(table.find(&Opt::Long("")), Ok(None));
+    assert_eq!(table.find(&Opt::<&str>::Short('v')), Ok(Some(&SPECS[0])));
+    assert_eq!(table.find(&Opt::<&str>::Short('x')), Ok(None));
+
+    let ambiguous = table.find(&Opt::Long("vers")).unwrap_err();
+    assert_eq!(ambiguous.iter().collect::<Vec<_>>(), [&SPECS[1], &SPECS[2]]);
+    assert_eq!(ambiguous.exit_code(), sysexits::EX_USAGE);
+    assert_eq!(
+        ambiguous.to_string(),
+        "ambiguous option, could be: --version, --version-info"
+    );
+
+    let ambiguous = table.find(&Opt::Long("v")).unwrap_err();
+    assert_eq!(ambiguous.iter().count(), 3);
+}
+
+#[test]
+fn spec_table_ignore_case() {
+    const SPECS: &[OptSpec<u8, &[u8]>] = &[
+        OptSpec::new().short(b'o').long(b"output"),
+        OptSpec::new().long(b"Verbose"),
+        OptSpec::new().long(b"verbose"),
+        OptSpec::new().long(b"version"),
+    ];
+
+    let table = SpecTable::new(SPECS).ignore_case(true);
+
+    assert_eq!(table.find(&Opt::Long(&b"OutPut"[..])), Ok(Some(&SPECS[0])));
+    assert_eq!(table.find(&Opt::Long(&b"verbose"[..])), Ok(Some(&SPECS[2])));
+    assert_eq!(table.find(&Opt::Long(&b"Verbose"[..])), Ok(Some(&SPECS[1])));
+    assert_eq!(table.find(&Opt::Long(&b"VERBOSE"[..])), Ok(Some(&SPECS[1])));
+    assert_eq!(table.find(&Opt::Long(&b"OUT"[..])), Ok(None));
+    assert_eq!(table.find(&Opt::<&[u8]>::Short(b'O')), Ok(None));
+
+    let table = table.abbreviations(true);
+
+    assert_eq!(table.find(&Opt::Long(&b"OUT"[..])), Ok(Some(&SPECS[0])));
+    assert_eq!(table.find(&Opt::Long(&b"VERSI"[..])), Ok(Some(&SPECS[3])));
+    assert_eq!(
+        table
+            .find(&Opt::Long(&b"VERB"[..]))
+            .unwrap_err()
+            .iter()
+            .count(),
+        2
+    );
+
+    let table = SpecTable::new(SPECS).abbreviations(true);
+
+    assert_eq!(table.find(&Opt::Long(&b"OUT"[..])), Ok(None));
+    assert_eq!(table.find(&Opt::Long(&b"Verb"[..])), Ok(Some(&SPECS[1])));
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn spec_table_normalize() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().long("caf\u{e9}"),
+        OptSpec::new().long("nai\u{308}ve"),
+        OptSpec::new().long("\u{1ec7}-mode"),
+    ];
+
+    let table = SpecTable::new(SPECS).normalize(true);
+
+    assert_eq!(table.find(&Opt::Long("caf\u{e9}")), Ok(Some(&SPECS[0])));
+    assert_eq!(table.find(&Opt::Long("cafe\u{301}")), Ok(Some(&SPECS[0])));
+    assert_eq!(table.find(&Opt::Long("na\u{ef}ve")), Ok(Some(&SPECS[1])));
+    assert_eq!(
+        table.find(&Opt::Long("e\u{323}\u{302}-mode")),
+        Ok(Some(&SPECS[2]))
+    );
+    assert_eq!(
+        table.find(&Opt::Long("\u{1eb9}\u{302}-mode")),
+        Ok(Some(&SPECS[2]))
+    );
+    assert_eq!(table.find(&Opt::Long("e\u{302}\u{323}-mode")), Ok(None));
+    assert_eq!(table.find(&Opt::Long("cafe")), Ok(None));
+    assert_eq!(table.find(&Opt::Long("CAFE\u{301}")), Ok(None));
+
+    let table = table.ignore_case(true).abbreviations(true);
+
+    assert_eq!(table.find(&Opt::Long("CAFE\u{301}")), Ok(Some(&SPECS[0])));
+    assert_eq!(table.find(&Opt::Long("NA\u{ef}")), Ok(Some(&SPECS[1])));
+    assert_eq!(table.find(&Opt::Long("e\u{323}")), Ok(Some(&SPECS[2])));
+
+    let table = SpecTable::new(SPECS);
+
+    assert_eq!(table.find(&Opt::Long("cafe\u{301}")), Ok(None));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn spec_table_find_flag() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('c').long("color").negatable(true),
+        OptSpec::new().long("no-cache"),
+        OptSpec::new().long("cache").negatable(true),
+        OptSpec::new().long("verbose"),
+    ];
+
+    let table = SpecTable::new(SPECS);
+
+    assert_eq!(
+        table.find_flag(&Opt::Long("color")),
+        Ok(Some((&SPECS[0], true)))
+    );
+    assert_eq!(
+        table.find_flag(&Opt::Long("no-color")),
+        Ok(Some((&SPECS[0], false)))
+    );
+    assert_eq!(
+        table.find_flag(&Opt::<&str>::Short('c')),
+        Ok(Some((&SPECS[0], true)))
+    );
+    assert_eq!(
+        table.find_flag(&Opt::Long("no-cache")),
+        Ok(Some((&SPECS[1], true)))
+    );
+    assert_eq!(table.find_flag(&Opt::Long("no-verbose")), Ok(None));
+    assert_eq!(table.find_flag(&Opt::Long("NO-color")), Ok(None));
+    assert_eq!(table.find_flag(&Opt::Long("no-")), Ok(None));
+    assert_eq!(table.find_flag(&Opt::Long("no")), Ok(None));
+    assert_eq!(table.find(&Opt::Long("no-color")), Ok(None));
+
+    let table = table.ignore_case(true).abbreviations(true);
+
+    assert_eq!(
+        table.find_flag(&Opt::Long("NO-Col")),
+        Ok(Some((&SPECS[0], false)))
+    );
+    assert_eq!(
+        table.find_flag(&Opt::Long("no-ca")),
+        Ok(Some((&SPECS[1], true)))
+    );
+    assert_eq!(table.find_flag(&Opt::Long("no-v")), Ok(None));
+    assert_eq!(
+        table.find_flag(&Opt::Long("no-c")),
+        Ok(Some((&SPECS[1], true)))
+    );
+    assert_eq!(
+        table.find_flag(&Opt::Long("c")).unwrap_err().to_string(),
+        "ambiguous option, could be: --color, --cache"
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn help_negatable() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('c').long("color").negatable(true),
+        OptSpec::new().long("cache").negatable(true),
+    ];
+
+    assert_eq!(
+        Help::new(SPECS).to_string(),
+        "  -c, --[no-]color
+      --[no-]cache
+"
+    );
+    assert_eq!(
+        Usage::new("app", SPECS).to_string(),
+        "usage: app [-c] [--[no-]cache]"
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn deprecations() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('c').long("color"),
+        OptSpec::new()
+            .short('C')
+            .long("colour")
+            .deprecated(true)
+            .replaced_by("color"),
+        OptSpec::new().long("legacy").deprecated(true),
+    ];
+
+    assert_eq!(SPECS[0].deprecation(Opt::Long("color")), None);
+
+    let warning = SPECS[1].deprecation(Opt::<&str>::Short('C')).unwrap();
+    assert_eq!(warning.opt, Opt::Short('C'));
+    assert_eq!(warning.spec, &SPECS[1]);
+    assert_eq!(warning.replaced_by(), Some("color"));
+    assert_eq!(warning.to_string(), "-C is deprecated, use --color instead");
+
+    let mut warnings = Vec::new();
+    let args = ["-cC", "--legacy", "--colour", "--unknown", "--color"];
+    let mut opts = Options::new(args.into_iter()).observe(Deprecations::new(
+        SPECS,
+        |warning: Deprecated<'static, &'static str>| warnings.push(warning),
+    ));
+
+    while opts.next_opt().unwrap().is_some() {}
+    drop(opts);
+
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|warning| (warning.opt, warning.spec.long))
+            .collect::<Vec<_>>(),
+        [
+            (Opt::Short('C'), Some("colour")),
+            (Opt::Long("legacy"), Some("legacy")),
+            (Opt::Long("colour"), Some("colour")),
+        ]
+    );
+    assert_eq!(warnings[1].to_string(), "--legacy is deprecated");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn parse_with() {
+    use core::ops::ControlFlow;
+
+    #[derive(Debug, PartialEq)]
+    enum MyError {
+        Parse(Error<&'static str>),
+        Bad(&'static str),
+    }
+
+    impl From<Error<&'static str>> for MyError {
+        fn from(error: Error<&'static str>) -> Self {
+            MyError::Parse(error)
+        }
+    }
+
+    #[derive(Default)]
+    struct Handler {
+        seen: Vec<Opt<&'static str>>,
+        values: Vec<(&'static str, ValueSource)>,
+    }
+
+    impl ParseHandler<&'static str, &'static str, MyError> for Handler {
+        fn on_opt(
+            &mut self,
+            opt: Opt<&'static str>,
+            value: &mut ValueAccess<'_, &'static str>,
+        ) -> core::result::Result<ControlFlow<&'static str>, MyError> {
+            self.seen.push(opt);
+
+            match opt {
+                Opt::Short('o') | Opt::Long("out") => {
+                    self.values.push(value.value_with_source()?);
+                }
+                Opt::Long("color") => assert_eq!(value.value_opt(), None),
+                Opt::Long("flag") => {}
+                Opt::Long("stop") => return Ok(ControlFlow::Break("stopped")),
+                Opt::Long(name) => return Err(MyError::Bad(name)),
+                _ => {}
+            }
+
+            Ok(ControlFlow::Continue(()))
+        }
+    }
+
+    let args = ["-vofile", "--out=x", "pos", "--color", "--stop", "after"];
+    let mut opts = Options::new(args.into_iter());
+    let mut handler = Handler::default();
+
+    assert_eq!(
+        opts.parse_with(&mut handler),
+        Ok(ControlFlow::Break("stopped"))
+    );
+    assert_eq!(
+        handler.seen,
+        [
+            Opt::Short('v'),
+            Opt::Short('o'),
+            Opt::Long("out"),
+            Opt::Long("color"),
+            Opt::Long("stop"),
+        ]
+    );
+    assert_eq!(
+        handler.values,
+        [("file", ValueSource::Attached), ("x", ValueSource::Inline)]
+    );
+    assert_eq!(opts.next_arg(), Ok(Some(Arg::Positional("after"))));
+
+    let mut opts = Options::new(["--nope"].into_iter());
+    let handler: &mut dyn ParseHandler<_, _, _> = &mut Handler::default();
+    assert_eq!(opts.parse_with(handler), Err(MyError::Bad("nope")));
+
+    let mut opts = Options::new(["-o"].into_iter());
+    assert_eq!(
+        opts.parse_with(&mut Handler::default()),
+        Err(MyError::Parse(Error::RequiresValue(Opt::Short('o'))))
+    );
+
+    let mut opts = Options::new(["--flag=x"].into_iter());
+    assert_eq!(
+        opts.parse_with(&mut Handler::default()),
+        Err(MyError::Parse(Error::DoesNotRequireValue(Opt::Long(
+            "flag"
+        ))))
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn parsed_args() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('v').long("verbose"),
+        OptSpec::new().short('I').long("include").takes_value(true),
+        OptSpec::new().short('o').long("output").takes_value(true),
+        OptSpec::new().short('q'),
+    ];
+
+    let args = [
+        "-vv",
+        "a",
+        "-Ix",
+        "--include=y",
+        "-o",
+        "1",
+        "b",
+        "--verbose",
+        "-o2",
+        "--",
+        "-q",
+    ];
+    let parsed = ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap();
+
+    assert!(parsed.flag("verbose"));
+    assert_eq!(parsed.count("verbose"), 3);
+    assert_eq!(parsed.count("include"), 2);
+    assert_eq!(parsed.values("include").collect::<Vec<_>>(), [&"x", &"y"]);
+    assert_eq!(parsed.value("output"), Some(OptValue::Given(&"2")));
+    assert_eq!(parsed.value('o'), Some(OptValue::Given(&"2")));
+    assert_eq!(parsed.value("verbose"), None);
+    assert!(!parsed.flag("missing"));
+    assert_eq!(parsed.value("missing"), None);
+    assert!(parsed.flag('v'));
+    assert_eq!(parsed.count('v'), 3);
+    assert!(!parsed.flag('q'));
+    assert_eq!(parsed.enabled('q'), None);
+    assert!(!parsed.flag('x'));
+    assert_eq!(parsed.positionals(), ["a", "b", "-q"]);
+    assert_eq!(parsed.specs(), SPECS);
+
+    let args = ["-v", "-x"];
+    assert_eq!(
+        ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap_err(),
+        Error::UnknownOption(Opt::Short('x'))
+    );
+
+    let args = ["--include"];
+    assert_eq!(
+        ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap_err(),
+        Error::RequiresValue(Opt::Long("include"))
+    );
+
+    let args = ["--verbose=1"];
+    assert_eq!(
+        ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap_err(),
+        Error::DoesNotRequireValue(Opt::Long("verbose"))
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn parsed_args_arity() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('p').long("point").arity(2),
+        OptSpec::new().short('v').long("verbose"),
+    ];
+
+    let args = ["-p", "1", "2", "a", "--point=3", "-4", "-v", "b"];
+    let parsed = ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap();
+
+    assert_eq!(parsed.count("point"), 2);
+    assert_eq!(parsed.group("point"), Some(&["3", "-4"][..]));
+    assert_eq!(parsed.group('p'), Some(&["3", "-4"][..]));
+    assert_eq!(parsed.value("point"), Some(OptValue::Given(&"3")));
+    assert_eq!(
+        parsed.values("point").collect::<Vec<_>>(),
+        [&"1", &"2", &"3", &"-4"]
+    );
+    assert_eq!(parsed.group("verbose"), None);
+    assert!(parsed.flag("verbose"));
+    assert_eq!(parsed.positionals(), ["a", "b"]);
+
+    let args = ["-v", "--point", "1"];
+    assert_eq!(
+        ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap_err(),
+        Error::RequiresValues {
+            opt: Opt::Long("point"),
+            expected: 2,
+            found: 1,
+        }
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn parsed_args_get() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('p').long("port").takes_value(true),
+        OptSpec::new().short('v').long("verbose"),
+        OptSpec::new().long("ratio").takes_value(true),
+    ];
+
+    let args = ["-p", "80", "-pabc", "-v", "--ratio=0.5"];
+    let parsed = ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap();
+
+    let error = parsed.get::<u16>("port").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid value 'abc' for -p: invalid digit found in string"
+    );
+    assert!(matches!(
+        error,
+        ValueError::InvalidValue {
+            opt: Opt::Short('p'),
+            value: "abc",
+            ..
+        }
+    ));
+    assert_eq!(parsed.get::<f64>("ratio"), Ok(Some(0.5)));
+    assert_eq!(parsed.get::<u8>("verbose"), Ok(None));
+    assert_eq!(parsed.get::<u8>("missing"), Ok(None));
+    assert_eq!(
+        parsed.get_with("ratio", |value| Ok::<_, ()>(value.len())),
+        Ok(Some(3))
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn parsed_args_negatable() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new().short('c').long("color").negatable(true),
+        OptSpec::new().long("no-cache"),
+        OptSpec::new().long("verbose"),
+    ];
+
+    let args = ["--color", "-c", "--no-color", "--no-cache", "x"];
+    let parsed = ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap();
+
+    assert!(!parsed.flag("color"));
+    assert_eq!(parsed.enabled("color"), Some(false));
+    assert_eq!(parsed.count("color"), 2);
+    assert!(parsed.flag("no-cache"));
+    assert_eq!(parsed.enabled("verbose"), None);
+    assert_eq!(parsed.positionals(), ["x"]);
+
+    let args = ["--no-color", "--color"];
+    let parsed = ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap();
+    assert!(parsed.flag('c'));
+
+    let args = ["--no-verbose"];
+    assert_eq!(
+        ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap_err(),
+        Error::UnknownOption(Opt::Long("no-verbose"))
+    );
+
+    let args = ["--no-color=yes"];
+    assert_eq!(
+        ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap_err(),
+        Error::DoesNotRequireValue(Opt::Long("no-color"))
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn parsed_args_default() {
+    const SPECS: &[OptSpec] = &[
+        OptSpec::new()
+            .short('j')
+            .takes_value(true)
+            .default_value("4"),
+        OptSpec::new()
+            .long("mode")
+            .takes_value(true)
+            .default_value("fast"),
+        OptSpec::new()
+            .long("bad")
+            .takes_value(true)
+            .default_value("x"),
+    ];
+
+    let args = ["--mode", "slow"];
+    let parsed = ParsedArgs::parse(Options::new(args.into_iter()), SPECS).unwrap();
+
+    assert_eq!(parsed.value('j'), Some(OptValue::Default("4")));
+    assert_eq!(parsed.get::<u8>('j'), Ok(Some(4)));
+    assert_eq!(parsed.value("mode"), Some(OptValue::Given(&"slow")));
+    assert_eq!(
+        parsed.get_with("mode", |value| Ok::<_, ()>(value.len())),
+        Ok(Some(4))
+    );
+    assert!(!parsed.flag('j'));
+    assert_eq!(parsed.values('j').count(), 0);
+    assert_eq!(parsed.value('z'), None);
+
+    let specs: &[OptSpec<u8, &[u8]>] = &[OptSpec::new()
+        .short(b'o')
+        .long(b"output".as_slice())
+        .takes_value(true)
+        .default_value(b"-".as_slice())];
+    let args: [&[u8]; 0] = [];
+    let parsed = ParsedArgs::parse(Options::new(args.into_iter()), specs).unwrap();
+    assert_eq!(
+        parsed.value(b"output"),
+        Some(OptValue::Default(b"-".as_slice()))
+    );
+    assert_eq!(parsed.value(b'o'), Some(OptValue::Default(b"-".as_slice())));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+#[should_panic(expected = "invalid default value in getargs::OptSpec")]
+fn parsed_args_invalid_default() {
+    const SPECS: &[OptSpec] = &[OptSpec::new()
+        .long("jobs")
+        .takes_value(true)
+        .default_value("many")];
+
+    let parsed = ParsedArgs::parse(Options::new(core::iter::empty::<&str>()), SPECS).unwrap();
+    let _ = parsed.get::<u8>("jobs");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn parsed_args_env() {
+    const SPECS: &[OptSpec] = &[OptSpec::new()
+        .short('d')
+        .takes_value(true)
+        .env("GETARGS_TEST_PARSED_ARGS_ENV")];
+
+    std::env::set_var("GETARGS_TEST_PARSED_ARGS_ENV", "/env");
+    let parsed = ParsedArgs::parse(Options::new(core::iter::empty::<&str>()), SPECS).unwrap();
+    assert_eq!(
+        parsed.value_or_env('d'),
+        Some(OptValue::Env(std::ffi::OsString::from("/env")))
+    );
+    assert_eq!(parsed.value('d'), None);
+
+    let parsed = ParsedArgs::parse(Options::new(["-dx"].into_iter()), SPECS).unwrap();
+    assert_eq!(parsed.value_or_env('d'), Some(OptValue::Given(&"x")));
+    std::env::remove_var("GETARGS_TEST_PARSED_ARGS_ENV");
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn heapless_collections() {
+    let args = ["-Ia", "--include=b", "-I", "c", "x", "y"];
+    let mut opts = Options::new(args.into_iter());
+    let mut includes = heapless::Vec::<_, 2>::new();
+
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('I'))));
+    assert_eq!(opts.value_into(&mut includes), Ok(()));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("include"))));
+    assert_eq!(opts.value_into(&mut includes), Ok(()));
+    assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('I'))));
+
+    let error = opts.value_into(&mut includes).unwrap_err();
+    assert_eq!(
+        error,
+        Error::TooManyValues {
+            opt: Opt::Short('I'),
+            capacity: 2
+        }
+    );
+    assert_eq!(error.to_string(), "option accepts at most 2 values: -I");
+    assert_eq!(error.exit_code(), sysexits::EX_USAGE);
+    assert_eq!(includes, ["a", "b"]);
+    assert_eq!(opts.next_opt(), Ok(None));
+
+    let mut positionals = heapless::Vec::<_, 2>::new();
+    assert_eq!(opts.positionals_into(&mut positionals), Ok(()));
+    assert_eq!(positionals, ["x", "y"]);
+    assert_eq!(opts.next_positional(), None);
+
+    let mut opts = Options::new(["--", "-a", "b", "c"].into_iter());
+    let mut positionals = heapless::Vec::<_, 2>::new();
+    assert_eq!(opts.next_opt(), Ok(None));
+
+    let error = opts.positionals_into(&mut positionals).unwrap_err();
+    assert_eq!(error, Error::TooManyPositionals { capacity: 2 });
+    assert_eq!(error.to_string(), "expected at most 2 positional arguments");
+    assert_eq!(positionals, ["-a", "b"]);
+    assert_eq!(opts.next_positional(), Some("c"));
+
+    let mut opts = Options::new(["x"].into_iter());
+    let mut positionals = heapless::Vec::<_, 0>::new();
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(
+        opts.positionals_into(&mut positionals),
+        Err(Error::TooManyPositionals { capacity: 0 })
+    );
+
+    let mut opts = Options::new([].into_iter());
+    assert_eq!(opts.next_opt(), Ok(None));
+    assert_eq!(opts.positionals_into(&mut positionals), Ok(()));
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn fuzz_args() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut seen = Vec::new();
+    let mut state = 1u32;
+
+    for _ in 0..64 {
+        let data: Vec<u8> = core::iter::repeat_with(|| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (state >> 16) as u8
+        })
+        .take(256)
+        .collect();
+
+        let mut u = Unstructured::new(&data);
+        let args = FuzzArgs::with_names(&mut u, &['v', 'o'], &["verbose", "output"]).unwrap();
+        assert!(args.args().len() <= 32);
+
+        let mut opts = Options::new(args.iter());
+        while let Ok(Some(opt)) = opts.next_opt() {
+            if opt == Opt::Short('o') || opt == Opt::Long("output") {
+                let _ = opts.value();
+            }
+        }
+        drop(opts);
+
+        seen.extend(args.into_inner());
+    }
+
+    assert!(seen.iter().any(|arg| arg == "--"));
+    assert!(seen.iter().any(|arg| arg == "-"));
+    assert!(seen.iter().any(|arg| arg.is_empty()));
+    assert!(seen.iter().any(|arg| arg == "--verbose"));
+    assert!(seen.iter().any(|arg| arg.starts_with("--output=")));
+    assert!(seen.iter().any(|arg| arg == "+v" || arg == "+o"));
+    assert!(seen
+        .iter()
+        .any(|arg| { arg.len() > 2 && arg[1..].bytes().all(|byte| byte == b'v' || byte == b'o') }));
+    assert!(seen
+        .iter()
+        .any(|arg| arg.len() > 1 && arg[1..].bytes().all(|byte| byte.is_ascii_digit())));
+
+    let data = [3; 16];
+    let mut u = Unstructured::new(&data);
+    let arbitrary = FuzzArgs::arbitrary(&mut u).unwrap();
+    assert_eq!(
+        arbitrary,
+        FuzzArgs::arbitrary(&mut Unstructured::new(&data)).unwrap()
+    );
+
+    let mut u = Unstructured::new(&data);
+    assert!(matches!(
+        Opt::<&str>::arbitrary(&mut u),
+        Ok(Opt::Short(_) | Opt::Long(_) | Opt::ShortPlus(_) | Opt::Numeric(_))
+    ));
+    assert!(Arg::<&[u8]>::arbitrary(&mut u).is_ok());
+}
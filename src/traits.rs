@@ -1,10 +1,18 @@
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String, vec::Vec};
 use core::fmt::Debug;
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
+#[cfg(all(feature = "std", unix))]
+use std::os::unix::ffi::OsStrExt;
 
 /// The argument trait for types that can be parsed by
 /// [`Options`][crate::Options].
 ///
-/// This trait is implemented for both [`&str`] and [`&[u8]`][slice],
-/// and allows them to be understood by `getargs` enough to parse them -
+/// This trait is implemented for [`&str`], [`&[u8]`][slice] and
+/// `&[u16]`, for `&OsStr` with the `std` feature, and for the owned
+/// `String`, `Vec<u8>` and `Cow<str>` with the `alloc` feature. It
+/// allows them to be understood by `getargs` enough to parse them -
 /// `getargs` is entirely generic over the type of its arguments.
 ///
 /// Adding `#[inline]` to implementations of this trait can improve
@@ -16,13 +24,37 @@ use core::fmt::Debug;
 /// This trait should not need to be implemented unless you are using
 /// arguments that cannot be coerced into `&str` or `&[u8]` for whatever
 /// reason. If they can be in any way, you should use an
-/// [`Iterator::map`] instead of implementing [`Argument`].
+/// [`Iterator::map`] instead of implementing [`Argument`]. If they
+/// can't, consider implementing the simpler [`SplitArgument`] trait
+/// instead, which provides an implementation of [`Argument`].
 ///
-/// Implementing `Argument` requires [`Copy`], [`Eq`], and [`Debug`]
-/// because it simplifies `#[derive]`s on `getargs`' side and codifies
-/// the inexpensive, zero-copy expectations of argument types. This
-/// should be a borrow like `&str`, not an owned struct like `String`.
-pub trait Argument: Copy + Eq + Debug {
+/// For example, C strings (`&CStr`, as received through `argv` from
+/// C) can't implement `Argument`, because the pieces `getargs` returns,
+/// like the name of a long option, aren't NUL-terminated. Instead, map
+/// them to `&[u8]` with [`CStr::to_bytes`][core::ffi::CStr::to_bytes],
+/// which is free:
+///
+/// ```
+/// # use getargs::{Opt, Options};
+/// use core::ffi::CStr;
+///
+/// let args = [c"-v", c"--file=foo"];
+/// let mut opts = Options::new(args.into_iter().map(CStr::to_bytes));
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'v'))));
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(b"file".as_slice()))));
+/// assert_eq!(opts.value(), Ok(b"foo".as_slice()));
+/// ```
+///
+/// Implementing `Argument` requires [`Clone`], [`Eq`], and [`Debug`]
+/// because it simplifies `#[derive]`s on `getargs`' side. Arguments are
+/// cloned freely while parsing, so they should usually be a cheap
+/// borrow like `&str`. With the `alloc` feature, `Argument` is also
+/// implemented for the owned types `String` and `Vec<u8>`, for
+/// arguments that are generated at runtime and don't live in a stable
+/// buffer, but parsing them allocates. `Cow<str>` is supported too, so
+/// borrowed and owned arguments can be mixed.
+pub trait Argument: Clone + Eq + Debug {
     /// The short-flag type. For [`&str`], this is [`char`]. For
     /// [`&[u8]`][slice], this is `u8`.
     type ShortOpt: Copy + Eq + Debug;
@@ -89,6 +121,260 @@ pub trait Argument: Copy + Eq + Debug {
     /// [`parse_short_cluster`][Self::parse_short_cluster]. Returns the
     /// value that was consumed.
     fn consume_short_val(self) -> Self;
+
+    /// Splits this argument at the first occurrence of the ASCII
+    /// character `separator`, returning the part before it and, if the
+    /// separator was found, the part after it. For example, splitting
+    /// `"name=value"` on `=` would return `("name", Some("value"))`.
+    ///
+    /// This is used by syntax that is not enabled by default, such as
+    /// [`ParseConfig::single_dash_long`][crate::ParseConfig#structfield.single_dash_long].
+    /// The default implementation never splits, which means that such
+    /// syntax will not recognize values attached to options.
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        let _ = separator;
+        (self, None)
+    }
+
+    /// Strips the ASCII character `prefix` from the start of this
+    /// argument, returning the rest, or `None` if this argument does
+    /// not start with `prefix` or nothing follows it. For example,
+    /// stripping `/` from `"/flag"` would return `Some("flag")`.
+    ///
+    /// Like [`split_on`][Self::split_on], this is only used by syntax
+    /// that is not enabled by default, such as
+    /// [`ParseConfig::slash_opts`][crate::ParseConfig#structfield.slash_opts].
+    /// The default implementation never strips anything, which means
+    /// that such syntax will never be recognized.
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        let _ = prefix;
+        None
+    }
+
+    /// Attempts to parse this argument as a short option cluster
+    /// without a leading `-`, like the `xvf` in `tar xvf`. Returns
+    /// `None` if this argument is empty.
+    ///
+    /// This is only used for the first argument when
+    /// [`ParseConfig::bundled_first`][crate::ParseConfig#structfield.bundled_first]
+    /// is enabled, and only if the argument is not already an option.
+    /// The returned cluster is subject to the same requirements as the
+    /// return value of
+    /// [`parse_short_cluster`][Self::parse_short_cluster]. The default
+    /// implementation always returns `None`, which means that such a
+    /// first argument will be treated as positional.
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        None
+    }
+
+    /// Attempts to parse this argument as the digits of a numeric
+    /// option, like the `5` in `-5`. Returns this argument if it is not
+    /// empty and consists only of ASCII digits, otherwise `None`.
+    ///
+    /// This is only used for short option clusters when
+    /// [`ParseConfig::numeric_opts`][crate::ParseConfig#structfield.numeric_opts]
+    /// is enabled. The default implementation always returns `None`,
+    /// which means that such clusters will be parsed as short options.
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        None
+    }
+
+    /// Returns `true` if this argument is empty, like the value of
+    /// `--name=`.
+    ///
+    /// This is only used when
+    /// [`ParseConfig::reject_empty_values`][crate::ParseConfig#structfield.reject_empty_values]
+    /// is enabled. The default implementation always returns `false`,
+    /// which means that empty values will still be accepted.
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        false
+    }
+
+    /// Returns the bytes of this argument, if it is expensive to clone
+    /// and can be viewed as bytes.
+    ///
+    /// [`Options::next_opt`][crate::Options::next_opt] uses these to
+    /// work out what kind of option this argument is before taking it
+    /// apart, instead of cloning it to try each kind in turn. The other
+    /// methods must split this argument the same way they would split
+    /// these bytes as a [`&[u8]`][slice], or parsing may panic. The
+    /// default implementation returns `None`, which is best for cheap
+    /// borrows like `&str`.
+    #[inline]
+    fn byte_view(&self) -> Option<&[u8]> {
+        None
+    }
+}
+
+/// A simpler alternative to implementing [`Argument`] directly.
+///
+/// [`Argument`] is implemented for every [`SplitArgument`] in terms of
+/// a handful of primitive string operations, so supporting a custom
+/// argument type (like an ASCII-only string or a rope slice) only
+/// requires implementing those operations rather than the whole parsing
+/// protocol. The blanket implementation recognizes the same syntax as
+/// the one for [`&str`].
+///
+/// Positions are measured in whatever units your type uses (bytes,
+/// code units, etc.), as long as [`find`][Self::find] and
+/// [`split_at`][Self::split_at] agree with each other.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, Options, SplitArgument};
+/// #
+/// /// An argument that only contains ASCII.
+/// #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+/// struct Ascii<'a>(&'a [u8]);
+///
+/// impl SplitArgument for Ascii<'_> {
+///     type ShortOpt = u8;
+///
+///     fn is_empty(self) -> bool {
+///         self.0.is_empty()
+///     }
+///
+///     fn strip_prefix(self, prefix: &str) -> Option<Self> {
+///         self.0.strip_prefix(prefix.as_bytes()).map(Ascii)
+///     }
+///
+///     fn find(self, ch: u8) -> Option<usize> {
+///         self.0.iter().position(|b| *b == ch)
+///     }
+///
+///     fn split_at(self, index: usize) -> (Self, Self) {
+///         let (left, right) = self.0.split_at(index);
+///         (Ascii(left), Ascii(right))
+///     }
+///
+///     fn split_short_opt(self) -> Option<(Self::ShortOpt, Self)> {
+///         self.0.split_first().map(|(b, rest)| (*b, Ascii(rest)))
+///     }
+/// }
+///
+/// let args = [Ascii(b"-ab"), Ascii(b"--see=1")];
+/// let mut opts = Options::new(args.into_iter());
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'a'))));
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'b'))));
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long(Ascii(b"see")))));
+/// assert_eq!(opts.value(), Ok(Ascii(b"1")));
+/// ```
+pub trait SplitArgument: Copy + Eq + Debug {
+    /// The short-flag type. See [`Argument::ShortOpt`].
+    type ShortOpt: Copy + Eq + Debug;
+
+    /// Returns `true` if this argument is empty.
+    fn is_empty(self) -> bool;
+
+    /// Returns this argument with `prefix` removed from the start, or
+    /// `None` if it does not start with `prefix`. `prefix` is always
+    /// ASCII.
+    fn strip_prefix(self, prefix: &str) -> Option<Self>;
+
+    /// Returns the position of the first occurrence of the ASCII
+    /// character `ch` in this argument, if any.
+    fn find(self, ch: u8) -> Option<usize>;
+
+    /// Splits this argument into two at `position`, as returned by
+    /// [`find`][Self::find]. The second half starts with the character
+    /// at `position`.
+    fn split_at(self, position: usize) -> (Self, Self);
+
+    /// Splits the first short option off of this argument, returning it
+    /// and the rest of the argument, or `None` if this argument is
+    /// empty.
+    fn split_short_opt(self) -> Option<(Self::ShortOpt, Self)>;
+}
+
+impl<T: SplitArgument> Argument for T {
+    type ShortOpt = T::ShortOpt;
+
+    #[inline]
+    fn ends_opts(self) -> bool {
+        self.strip_prefix("--").is_some_and(T::is_empty)
+    }
+
+    #[inline]
+    fn parse_long_opt(self) -> Option<(Self, Option<Self>)> {
+        let option = self.strip_prefix("--").filter(|a| !a.is_empty())?;
+
+        if let Some(position) = option.find(b'=') {
+            let (name, value) = option.split_at(position);
+            Some((name, value.strip_prefix("=")))
+        } else {
+            Some((option, None))
+        }
+    }
+
+    #[inline]
+    fn parse_short_cluster(self) -> Option<Self> {
+        self.strip_prefix("-").filter(|a| !a.is_empty())
+    }
+
+    #[inline]
+    fn consume_short_opt(self) -> (Self::ShortOpt, Option<Self>) {
+        let (opt, rest) = self
+            .split_short_opt()
+            .expect("<T as getargs::Argument>::consume_short_opt called on an empty SplitArgument");
+
+        (opt, Some(rest).filter(|a| !a.is_empty()))
+    }
+
+    #[inline]
+    fn consume_short_val(self) -> Self {
+        self
+    }
+
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        if let Some(position) = self.find(separator) {
+            let (left, right) = self.split_at(position);
+            (left, right.split_short_opt().map(|(_, right)| right))
+        } else {
+            (self, None)
+        }
+    }
+
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        let prefix = [prefix];
+        let prefix = core::str::from_utf8(&prefix).ok()?;
+        self.strip_prefix(prefix).filter(|rest| !rest.is_empty())
+    }
+
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        Some(self).filter(|a| !a.is_empty())
+    }
+
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        if self.is_empty() {
+            return None;
+        }
+
+        const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+        let mut rest = self;
+
+        while !rest.is_empty() {
+            rest = DIGITS.iter().find_map(|digit| rest.strip_prefix(digit))?;
+        }
+
+        Some(self)
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        T::is_empty(*self)
+    }
 }
 
 impl Argument for &'_ str {
@@ -120,20 +406,56 @@ impl Argument for &'_ str {
 
     #[inline]
     fn consume_short_opt(self) -> (Self::ShortOpt, Option<Self>) {
-        let ch = self
-            .chars()
-            .next()
-            .expect("<&str as getargs::Argument>::consume_short_opt called on an empty string");
+        let (ch, len) = match self.as_bytes().first() {
+            // ASCII fast path: most short options are ASCII, and don't
+            // need a whole `char` to be decoded
+            Some(&byte) if byte.is_ascii() => (byte as char, 1),
+            _ => {
+                let ch = self.chars().next().expect(
+                    "<&str as getargs::Argument>::consume_short_opt called on an empty string",
+                );
+
+                (ch, ch.len_utf8())
+            }
+        };
 
         // using `unsafe` here only improves performance by ~10% and is
         // not worth it for losing the "we don't use `unsafe`" guarantee
-        (ch, Some(&self[ch.len_utf8()..]).filter(|s| !s.is_empty()))
+        (ch, Some(&self[len..]).filter(|s| !s.is_empty()))
     }
 
     #[inline]
     fn consume_short_val(self) -> Self {
         self
     }
+
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        match self.split_once(separator as char) {
+            Some((left, right)) => (left, Some(right)),
+            None => (self, None),
+        }
+    }
+
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        self.strip_prefix(prefix as char).filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        Some(self).filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        Some(self).filter(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
 }
 
 impl Argument for &'_ [u8] {
@@ -147,9 +469,121 @@ impl Argument for &'_ [u8] {
     #[inline]
     fn parse_long_opt(self) -> Option<(Self, Option<Self>)> {
         let option = self.strip_prefix(b"--").filter(|a| !a.is_empty())?;
+        Some(option.split_on(b'='))
+    }
+
+    #[inline]
+    fn parse_short_cluster(self) -> Option<Self> {
+        self.strip_prefix(b"-").filter(|a| !a.is_empty())
+    }
+
+    #[inline]
+    fn consume_short_opt(self) -> (Self::ShortOpt, Option<Self>) {
+        let (byte, rest) = self
+            .split_first()
+            .expect("<&[u8] as getargs::Argument>::consume_short_opt called on an empty slice");
+
+        (*byte, Some(rest).filter(|s| !s.is_empty()))
+    }
+
+    #[inline]
+    fn consume_short_val(self) -> Self {
+        self
+    }
+
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        match find_byte(self, separator) {
+            Some(i) => (&self[..i], Some(&self[i + 1..])),
+            None => (self, None),
+        }
+    }
+
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        self.strip_prefix(&[prefix]).filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        Some(self).filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        Some(self).filter(|s| !s.is_empty() && s.iter().all(u8::is_ascii_digit))
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+/// The size of the words that [`find_byte`] compares at a time.
+const WORD: usize = core::mem::size_of::<usize>();
+
+/// A word with every byte set to `0x01`.
+const LO: usize = usize::MAX / 0xFF;
 
-        // This is faster than iterators in dev
-        let name = option.split(|b| *b == b'=').next().unwrap();
+/// A word with every byte set to `0x80`.
+const HI: usize = LO << 7;
+
+/// Returns the position of the first `needle` in `haystack`. This is
+/// the only byte search used when parsing `&[u8]` arguments, for both
+/// `--option=value` and [`Argument::split_on`], so that it can be
+/// optimized in one place.
+///
+/// Long arguments are searched a word at a time without any `unsafe`
+/// code: each word is XORed with `needle` repeated in every byte, so
+/// that matching bytes become zero, and the classic "has a zero byte"
+/// test, `(x - LO) & !x & HI`, tells whether the word contains a match.
+/// Only the word that does is then searched byte by byte.
+///
+/// This is used instead of the `memchr` crate, whose SIMD search only
+/// pays off on haystacks much longer than a typical argument, so that
+/// `getargs` stays free of dependencies and `unsafe` code. See
+/// `bench/README.md` for measurements.
+#[inline]
+fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    let repeated = LO * needle as usize;
+    let mut words = haystack.chunks_exact(WORD);
+
+    for (index, word) in words.by_ref().enumerate() {
+        let x = usize::from_ne_bytes(word.try_into().unwrap()) ^ repeated;
+
+        if x.wrapping_sub(LO) & !x & HI != 0 {
+            let offset = word.iter().position(|b| *b == needle);
+            return offset.map(|offset| index * WORD + offset);
+        }
+    }
+
+    let rest = words.remainder();
+    let offset = rest.iter().position(|b| *b == needle)?;
+    Some(haystack.len() - rest.len() + offset)
+}
+
+/// Parses UTF-16 arguments, like those returned by
+/// `CommandLineToArgvW` on Windows, without converting them to UTF-8.
+///
+/// Short options are single code units, so characters outside the
+/// Basic Multilingual Plane (which are encoded as surrogate pairs) are
+/// returned as two separate short options.
+impl Argument for &'_ [u16] {
+    type ShortOpt = u16;
+
+    #[inline]
+    fn ends_opts(self) -> bool {
+        self == [b'-' as u16; 2]
+    }
+
+    #[inline]
+    fn parse_long_opt(self) -> Option<(Self, Option<Self>)> {
+        let option = self
+            .strip_prefix(&[b'-' as u16; 2][..])
+            .filter(|a| !a.is_empty())?;
+
+        let name = option.split(|u| *u == b'=' as u16).next().unwrap();
         let value = if name.len() < option.len() {
             Some(&option[name.len() + 1..])
         } else {
@@ -161,20 +595,437 @@ impl Argument for &'_ [u8] {
 
     #[inline]
     fn parse_short_cluster(self) -> Option<Self> {
-        self.strip_prefix(b"-").filter(|a| !a.is_empty())
+        self.strip_prefix(&[b'-' as u16][..])
+            .filter(|a| !a.is_empty())
     }
 
     #[inline]
     fn consume_short_opt(self) -> (Self::ShortOpt, Option<Self>) {
-        let (byte, rest) = self
+        let (unit, rest) = self
             .split_first()
-            .expect("<&[u8] as getargs::Argument>::consume_short_opt called on an empty slice");
+            .expect("<&[u16] as getargs::Argument>::consume_short_opt called on an empty slice");
 
-        (*byte, Some(rest).filter(|s| !s.is_empty()))
+        (*unit, Some(rest).filter(|s| !s.is_empty()))
     }
 
     #[inline]
     fn consume_short_val(self) -> Self {
         self
     }
+
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        match self.iter().position(|u| *u == separator as u16) {
+            Some(i) => (&self[..i], Some(&self[i + 1..])),
+            None => (self, None),
+        }
+    }
+
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        self.strip_prefix(&[prefix as u16])
+            .filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        Some(self).filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        Some(self).filter(|s| {
+            !s.is_empty() && s.iter().all(|&u| (b'0' as u16..=b'9' as u16).contains(&u))
+        })
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+/// Parses [`OsStr`] arguments without any lossy UTF-8 conversion, so
+/// that the results of [`std::env::args_os`] can be used directly.
+///
+/// This is zero-copy: parsing is done on the underlying bytes, using
+/// [`OsStrExt`]. Other platforms, like Windows, don't allow borrowing
+/// parts of an [`OsStr`] without `unsafe` code, so there arguments are
+/// parsed with [`OsStr::to_str`] instead. That is still zero-copy, but
+/// short options are [`char`]s rather than bytes, and arguments that
+/// are not valid Unicode, like unpaired surrogates on Windows, are
+/// never options: they are returned unchanged as positional arguments
+/// or values.
+#[cfg(all(feature = "std", unix))]
+impl Argument for &'_ OsStr {
+    type ShortOpt = u8;
+
+    #[inline]
+    fn ends_opts(self) -> bool {
+        self.as_bytes().ends_opts()
+    }
+
+    #[inline]
+    fn parse_long_opt(self) -> Option<(Self, Option<Self>)> {
+        let (name, value) = self.as_bytes().parse_long_opt()?;
+        Some((OsStr::from_bytes(name), value.map(OsStr::from_bytes)))
+    }
+
+    #[inline]
+    fn parse_short_cluster(self) -> Option<Self> {
+        self.as_bytes().parse_short_cluster().map(OsStr::from_bytes)
+    }
+
+    #[inline]
+    fn consume_short_opt(self) -> (Self::ShortOpt, Option<Self>) {
+        let (opt, rest) = self.as_bytes().consume_short_opt();
+        (opt, rest.map(OsStr::from_bytes))
+    }
+
+    #[inline]
+    fn consume_short_val(self) -> Self {
+        self
+    }
+
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        let (left, right) = self.as_bytes().split_on(separator);
+        (OsStr::from_bytes(left), right.map(OsStr::from_bytes))
+    }
+
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        self.as_bytes()
+            .strip_ascii_prefix(prefix)
+            .map(OsStr::from_bytes)
+    }
+
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        Some(self).filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        self.as_bytes().parse_numeric().map(OsStr::from_bytes)
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+/// Parses [`OsStr`] arguments that are valid Unicode like [`&str`],
+/// on platforms other than Unix. See the Unix implementation.
+#[cfg(all(feature = "std", not(unix)))]
+impl Argument for &'_ OsStr {
+    type ShortOpt = char;
+
+    #[inline]
+    fn ends_opts(self) -> bool {
+        self == "--"
+    }
+
+    #[inline]
+    fn parse_long_opt(self) -> Option<(Self, Option<Self>)> {
+        let (name, value) = self.to_str()?.parse_long_opt()?;
+        Some((OsStr::new(name), value.map(OsStr::new)))
+    }
+
+    #[inline]
+    fn parse_short_cluster(self) -> Option<Self> {
+        self.to_str()?.parse_short_cluster().map(OsStr::new)
+    }
+
+    #[inline]
+    fn consume_short_opt(self) -> (Self::ShortOpt, Option<Self>) {
+        // Short option clusters only come from arguments that are valid
+        // Unicode, but fall back to U+FFFD like a lossy conversion.
+        match self.to_str() {
+            Some(cluster) => {
+                let (opt, rest) = cluster.consume_short_opt();
+                (opt, rest.map(OsStr::new))
+            }
+            None => (char::REPLACEMENT_CHARACTER, None),
+        }
+    }
+
+    #[inline]
+    fn consume_short_val(self) -> Self {
+        self
+    }
+
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        match self.to_str() {
+            Some(arg) => {
+                let (left, right) = arg.split_on(separator);
+                (OsStr::new(left), right.map(OsStr::new))
+            }
+            None => (self, None),
+        }
+    }
+
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        self.to_str()?.strip_ascii_prefix(prefix).map(OsStr::new)
+    }
+
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        self.to_str()?.parse_bundled_cluster().map(OsStr::new)
+    }
+
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        self.to_str()?.parse_numeric().map(OsStr::new)
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+/// Parses owned [`String`] arguments.
+///
+/// This works the same as the implementation for [`&str`], except that
+/// every piece split off of an argument (option names, values and the
+/// rest of short option clusters) is copied into a new [`String`].
+#[cfg(feature = "alloc")]
+impl Argument for String {
+    type ShortOpt = char;
+
+    #[inline]
+    fn ends_opts(self) -> bool {
+        self == "--"
+    }
+
+    #[inline]
+    fn parse_long_opt(self) -> Option<(Self, Option<Self>)> {
+        let (name, value) = self.as_str().parse_long_opt()?;
+        Some((name.into(), value.map(Into::into)))
+    }
+
+    #[inline]
+    fn parse_short_cluster(self) -> Option<Self> {
+        self.as_str().parse_short_cluster().map(Into::into)
+    }
+
+    #[inline]
+    fn consume_short_opt(self) -> (Self::ShortOpt, Option<Self>) {
+        let (opt, rest) = self.as_str().consume_short_opt();
+        (opt, rest.map(Into::into))
+    }
+
+    #[inline]
+    fn consume_short_val(self) -> Self {
+        self
+    }
+
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        match self.as_str().split_on(separator) {
+            (_, None) => (self, None),
+            (left, Some(right)) => (left.into(), Some(right.into())),
+        }
+    }
+
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        self.as_str().strip_ascii_prefix(prefix).map(Into::into)
+    }
+
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        Some(self).filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        Some(self).filter(|s| s.as_str().parse_numeric().is_some())
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+
+    #[inline]
+    fn byte_view(&self) -> Option<&[u8]> {
+        Some(self.as_bytes())
+    }
+}
+
+/// Parses owned [`Vec<u8>`] arguments.
+///
+/// This works the same as the implementation for [`&[u8]`][slice],
+/// except that every piece split off of an argument (option names,
+/// values and the rest of short option clusters) is copied into a new
+/// [`Vec`].
+#[cfg(feature = "alloc")]
+impl Argument for Vec<u8> {
+    type ShortOpt = u8;
+
+    #[inline]
+    fn ends_opts(self) -> bool {
+        self == b"--"
+    }
+
+    #[inline]
+    fn parse_long_opt(self) -> Option<(Self, Option<Self>)> {
+        let (name, value) = self.as_slice().parse_long_opt()?;
+        Some((name.into(), value.map(Into::into)))
+    }
+
+    #[inline]
+    fn parse_short_cluster(self) -> Option<Self> {
+        self.as_slice().parse_short_cluster().map(Into::into)
+    }
+
+    #[inline]
+    fn consume_short_opt(self) -> (Self::ShortOpt, Option<Self>) {
+        let (opt, rest) = self.as_slice().consume_short_opt();
+        (opt, rest.map(Into::into))
+    }
+
+    #[inline]
+    fn consume_short_val(self) -> Self {
+        self
+    }
+
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        match self.as_slice().split_on(separator) {
+            (_, None) => (self, None),
+            (left, Some(right)) => (left.into(), Some(right.into())),
+        }
+    }
+
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        self.as_slice().strip_ascii_prefix(prefix).map(Into::into)
+    }
+
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        Some(self).filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        Some(self).filter(|s| s.as_slice().parse_numeric().is_some())
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+
+    #[inline]
+    fn byte_view(&self) -> Option<&[u8]> {
+        Some(self.as_slice())
+    }
+}
+
+/// Parses a mix of borrowed and owned string arguments, like arguments
+/// from `argv` combined with ones synthesized at runtime.
+///
+/// Borrowed arguments are parsed like [`&str`] without copying, and
+/// owned arguments are parsed like [`String`].
+#[cfg(feature = "alloc")]
+impl Argument for Cow<'_, str> {
+    type ShortOpt = char;
+
+    #[inline]
+    fn ends_opts(self) -> bool {
+        self == "--"
+    }
+
+    #[inline]
+    fn parse_long_opt(self) -> Option<(Self, Option<Self>)> {
+        match self {
+            Cow::Borrowed(arg) => {
+                let (name, value) = arg.parse_long_opt()?;
+                Some((Cow::Borrowed(name), value.map(Cow::Borrowed)))
+            }
+            Cow::Owned(arg) => {
+                let (name, value) = arg.parse_long_opt()?;
+                Some((Cow::Owned(name), value.map(Cow::Owned)))
+            }
+        }
+    }
+
+    #[inline]
+    fn parse_short_cluster(self) -> Option<Self> {
+        match self {
+            Cow::Borrowed(arg) => arg.parse_short_cluster().map(Cow::Borrowed),
+            Cow::Owned(arg) => arg.parse_short_cluster().map(Cow::Owned),
+        }
+    }
+
+    #[inline]
+    fn consume_short_opt(self) -> (Self::ShortOpt, Option<Self>) {
+        match self {
+            Cow::Borrowed(arg) => {
+                let (opt, rest) = arg.consume_short_opt();
+                (opt, rest.map(Cow::Borrowed))
+            }
+            Cow::Owned(arg) => {
+                let (opt, rest) = arg.consume_short_opt();
+                (opt, rest.map(Cow::Owned))
+            }
+        }
+    }
+
+    #[inline]
+    fn consume_short_val(self) -> Self {
+        self
+    }
+
+    #[inline]
+    fn split_on(self, separator: u8) -> (Self, Option<Self>) {
+        match self {
+            Cow::Borrowed(arg) => {
+                let (left, right) = arg.split_on(separator);
+                (Cow::Borrowed(left), right.map(Cow::Borrowed))
+            }
+            Cow::Owned(arg) => {
+                let (left, right) = arg.split_on(separator);
+                (Cow::Owned(left), right.map(Cow::Owned))
+            }
+        }
+    }
+
+    #[inline]
+    fn strip_ascii_prefix(self, prefix: u8) -> Option<Self> {
+        match self {
+            Cow::Borrowed(arg) => arg.strip_ascii_prefix(prefix).map(Cow::Borrowed),
+            Cow::Owned(arg) => arg.strip_ascii_prefix(prefix).map(Cow::Owned),
+        }
+    }
+
+    #[inline]
+    fn parse_bundled_cluster(self) -> Option<Self> {
+        Some(self).filter(|s| !s.is_empty())
+    }
+
+    #[inline]
+    fn parse_numeric(self) -> Option<Self> {
+        Some(self).filter(|s| s.as_ref().parse_numeric().is_some())
+    }
+
+    #[inline]
+    fn is_empty_value(&self) -> bool {
+        self.is_empty()
+    }
+
+    #[inline]
+    fn byte_view(&self) -> Option<&[u8]> {
+        match self {
+            Cow::Borrowed(_) => None,
+            Cow::Owned(arg) => arg.byte_view(),
+        }
+    }
 }
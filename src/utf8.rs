@@ -0,0 +1,182 @@
+use crate::{Arg, Error, Opt, Options, ParseConfig, Result};
+
+/// An iterator that converts byte arguments into `&str`, stopping at
+/// the first argument that is not valid UTF-8.
+///
+/// This is the iterator of the [`Options`] wrapped by a
+/// [`Utf8Options`], which reports the invalid argument as
+/// [`Error::InvalidUtf8`].
+#[derive(Copy, Clone, Debug)]
+pub struct Utf8Args<I> {
+    iter: I,
+    index: usize,
+    error: Option<(usize, usize)>,
+    failed: bool,
+}
+
+impl<I> Utf8Args<I> {
+    /// Creates a new [`Utf8Args`] over `iter`.
+    pub fn new(iter: I) -> Self {
+        Self {
+            iter,
+            index: 0,
+            error: None,
+            failed: false,
+        }
+    }
+
+    /// Creates a new [`Utf8Args`] over `iter`, whose first argument is
+    /// counted as argument `index + 1`.
+    pub(crate) fn with_index(iter: I, index: usize) -> Self {
+        Self {
+            index,
+            ..Self::new(iter)
+        }
+    }
+
+    /// Takes the error for the argument that was not valid UTF-8, if
+    /// it was just reached.
+    fn take_error<'a>(&mut self) -> Option<Error<&'a str>> {
+        self.error
+            .take()
+            .map(|(arg_index, valid_up_to)| Error::InvalidUtf8 {
+                arg_index,
+                valid_up_to,
+            })
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a [u8]>> Iterator for Utf8Args<I> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let arg = self.iter.next()?;
+        self.index += 1;
+
+        match core::str::from_utf8(arg) {
+            Ok(arg) => Some(arg),
+            Err(error) => {
+                self.error = Some((self.index, error.valid_up_to()));
+                self.failed = true;
+                None
+            }
+        }
+    }
+}
+
+/// An [`Options`] that parses `&[u8]` arguments, but returns options
+/// and values as `&str`.
+///
+/// Each argument is converted when it is parsed, so parsing still does
+/// not allocate. If an argument is not valid UTF-8, the method that
+/// reached it returns [`Error::InvalidUtf8`] with its position, instead
+/// of converting it lossily. Parsing ends after that error.
+///
+/// This type can be created with [`Utf8Options::new`], or from an
+/// [`Options`] that has already started parsing with
+/// [`Options::map_to_str`].
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Error, Opt, Utf8Options};
+/// #
+/// let args: [&[u8]; 3] = [b"--name", b"caf\xc3\xa9", b"--path=\xff"];
+/// let mut opts = Utf8Options::new(args.into_iter());
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Long("name"))));
+/// assert_eq!(opts.value(), Ok("café"));
+/// assert_eq!(
+///     opts.next_opt(),
+///     Err(Error::InvalidUtf8 {
+///         arg_index: 3,
+///         valid_up_to: 7,
+///     })
+/// );
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Utf8Options<'a, I: Iterator<Item = &'a [u8]>> {
+    opts: Options<&'a str, Utf8Args<I>>,
+}
+
+impl<'a, I: Iterator<Item = &'a [u8]>> Utf8Options<'a, I> {
+    /// Creates a new [`Utf8Options`] given an iterator over byte
+    /// arguments. See [`Options::new`].
+    pub fn new(iter: I) -> Self {
+        Self::with_config(iter, ParseConfig::new())
+    }
+
+    /// Creates a new [`Utf8Options`] with the behavior switches in
+    /// `config`. See [`Options::with_config`].
+    pub fn with_config(iter: I, config: ParseConfig) -> Self {
+        Self {
+            opts: Options::with_config(Utf8Args::new(iter), config),
+        }
+    }
+
+    pub(crate) fn from_options(opts: Options<&'a str, Utf8Args<I>>) -> Self {
+        Self { opts }
+    }
+
+    fn check<T>(&mut self, result: Result<&'a str, T>) -> Result<&'a str, T> {
+        match self.opts.iter_mut().take_error() {
+            Some(error) => Err(error),
+            None => result,
+        }
+    }
+
+    /// Like [`Options::next_opt`], but returns [`Error::InvalidUtf8`]
+    /// if the next argument is not valid UTF-8.
+    pub fn next_opt(&mut self) -> Result<&'a str, Option<Opt<&'a str>>> {
+        let result = self.opts.next_opt();
+        self.check(result)
+    }
+
+    /// Like [`Options::next_arg`], but returns [`Error::InvalidUtf8`]
+    /// if the next argument is not valid UTF-8.
+    pub fn next_arg(&mut self) -> Result<&'a str, Option<Arg<&'a str>>> {
+        let result = self.opts.next_arg();
+        self.check(result)
+    }
+
+    /// Like [`Options::value`], but returns [`Error::InvalidUtf8`] if
+    /// the value is not valid UTF-8.
+    pub fn value(&mut self) -> Result<&'a str, &'a str> {
+        let result = self.opts.value();
+        self.check(result)
+    }
+
+    /// Returns [`Options::value_opt`] for the wrapped [`Options`].
+    /// Explicit values are part of an argument that was already
+    /// converted, so this can't fail.
+    pub fn value_opt(&mut self) -> Option<&'a str> {
+        self.opts.value_opt()
+    }
+
+    /// Like [`Options::next_positional`], but returns
+    /// [`Error::InvalidUtf8`] if the next argument is not valid UTF-8.
+    pub fn next_positional(&mut self) -> Result<&'a str, Option<&'a str>> {
+        let result = self.opts.next_positional();
+        self.check(Ok(result))
+    }
+
+    /// Returns [`Options::opts_ended`] for the wrapped [`Options`].
+    pub fn opts_ended(&self) -> bool {
+        self.opts.opts_ended()
+    }
+
+    /// Returns [`Options::is_empty`] for the wrapped [`Options`].
+    pub fn is_empty(&self) -> bool {
+        self.opts.is_empty()
+    }
+
+    /// Consumes this [`Utf8Options`], returning the wrapped
+    /// [`Options`].
+    pub fn into_inner(self) -> Options<&'a str, Utf8Args<I>> {
+        self.opts
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{Arg, Argument, Error, Opt, OptSpec, Options, Result};
+
+/// An [`Options`] that only accepts options from a table of
+/// [`OptSpec`]s.
+///
+/// This type can be obtained by calling [`Options::validate`]. It
+/// offers the same parsing methods as [`Options`], which behave
+/// identically except that [`Validated::next_opt`] and
+/// [`Validated::next_arg`] return [`Error::UnknownOption`] for options
+/// that are not in the table. Unlike [`Options::next_spec`], values
+/// are still retrieved by calling [`Validated::value`] or
+/// [`Validated::value_opt`], so parsing loops don't need to change.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Error, Opt, OptSpec, Options};
+/// #
+/// const SPECS: &[OptSpec] = &[
+///     OptSpec::new().short('v').long("verbose"),
+///     OptSpec::new().short('o').takes_value(true),
+/// ];
+///
+/// let args = ["-v", "-o", "out.txt", "--colour"];
+/// let mut opts = Options::new(args.into_iter()).validate(SPECS);
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('v'))));
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short('o'))));
+/// assert_eq!(opts.value(), Ok("out.txt"));
+/// assert_eq!(opts.next_opt(), Err(Error::UnknownOption(Opt::Long("colour"))));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Validated<'s, A: Argument, I: Iterator<Item = A>, L> {
+    opts: Options<A, I>,
+    specs: &'s [OptSpec<A::ShortOpt, L>],
+}
+
+impl<'s, A: Argument + PartialEq<L>, I: Iterator<Item = A>, L: Copy> Validated<'s, A, I, L> {
+    pub(crate) fn new(opts: Options<A, I>, specs: &'s [OptSpec<A::ShortOpt, L>]) -> Self {
+        Self { opts, specs }
+    }
+
+    fn check(&self, opt: Opt<A>) -> Result<A, Opt<A>> {
+        if self.specs.iter().any(|spec| spec.matches(&opt)) {
+            Ok(opt)
+        } else {
+            Err(Error::UnknownOption(opt))
+        }
+    }
+
+    /// Like [`Options::next_opt`], but returns
+    /// [`Error::UnknownOption`] for unknown options.
+    pub fn next_opt(&mut self) -> Result<A, Option<Opt<A>>> {
+        self.opts.next_opt()?.map(|opt| self.check(opt)).transpose()
+    }
+
+    /// Like [`Options::next_arg`], but returns
+    /// [`Error::UnknownOption`] for unknown options.
+    pub fn next_arg(&mut self) -> Result<A, Option<Arg<A>>> {
+        match self.opts.next_arg()? {
+            Some(Arg::Positional(positional)) => Ok(Some(Arg::Positional(positional))),
+            Some(arg) => Ok(Some(self.check(arg.opt().unwrap())?.into())),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns [`Options::value`] for the wrapped [`Options`].
+    pub fn value(&mut self) -> Result<A, A> {
+        self.opts.value()
+    }
+
+    /// Returns [`Options::value_opt`] for the wrapped [`Options`].
+    pub fn value_opt(&mut self) -> Option<A> {
+        self.opts.value_opt()
+    }
+
+    /// Returns [`Options::next_positional`] for the wrapped
+    /// [`Options`].
+    pub fn next_positional(&mut self) -> Option<A> {
+        self.opts.next_positional()
+    }
+
+    /// Returns [`Options::opts_ended`] for the wrapped [`Options`].
+    pub fn opts_ended(&self) -> bool {
+        self.opts.opts_ended()
+    }
+
+    /// Returns [`Options::is_empty`] for the wrapped [`Options`].
+    pub fn is_empty(&self) -> bool {
+        self.opts.is_empty()
+    }
+
+    /// Returns the table of [`OptSpec`]s that options are checked
+    /// against.
+    pub fn specs(&self) -> &'s [OptSpec<A::ShortOpt, L>] {
+        self.specs
+    }
+
+    /// Consumes this [`Validated`], returning the wrapped [`Options`].
+    pub fn into_inner(self) -> Options<A, I> {
+        self.opts
+    }
+}
@@ -0,0 +1,86 @@
+use core::fmt::{self, Display, Formatter};
+
+/// How the value of an option was supplied on the command line.
+///
+/// This is returned by
+/// [`Options::value_with_source`][crate::Options::value_with_source]
+/// and
+/// [`Options::value_opt_with_source`][crate::Options::value_opt_with_source]
+/// for tools that need to validate or quote values differently
+/// depending on how they were written.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum ValueSource {
+    /// The value was attached to a long option with `=`, like
+    /// `--file=foo.txt`.
+    Inline,
+    /// The value was the rest of a short option cluster, like
+    /// `-ffoo.txt`.
+    Attached,
+    /// The value was taken from the following argument, like
+    /// `--file foo.txt` or `-f foo.txt`.
+    Next,
+}
+
+/// The value of an option, which is either the value given on the
+/// command line, the value of the option's
+/// [environment variable][crate::OptSpec#structfield.env], or the
+/// option's [default value][crate::OptSpec#structfield.default_value].
+///
+/// This is returned by
+/// [`OptSpec::value_or_default`][crate::OptSpec::value_or_default] and
+/// [`OptSpec::value_or_env`][crate::OptSpec::value_or_env]. The default
+/// value has the type of long option names, `L`, since it is declared
+/// in the same table, and values from the environment have type `E`,
+/// so the variants can have different types; [`OptValue`] implements
+/// [`AsRef`] and [`Display`] so that it can be used without matching on
+/// it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum OptValue<A, L = &'static str, E = A> {
+    /// The value was given on the command line.
+    Given(A),
+    /// The option was not given, so the value of its environment
+    /// variable was used.
+    Env(E),
+    /// The option was not given, so its default value was used.
+    Default(L),
+}
+
+impl<A, L, E> OptValue<A, L, E> {
+    /// Returns `true` if this is the value of an environment variable.
+    pub fn is_env(&self) -> bool {
+        matches!(self, Self::Env(_))
+    }
+
+    /// Returns `true` if this is the default value.
+    pub fn is_default(&self) -> bool {
+        matches!(self, Self::Default(_))
+    }
+
+    /// Returns the value given on the command line, if any.
+    pub fn given(self) -> Option<A> {
+        match self {
+            Self::Given(value) => Some(value),
+            Self::Env(_) | Self::Default(_) => None,
+        }
+    }
+}
+
+impl<T: ?Sized, A: AsRef<T>, L: AsRef<T>, E: AsRef<T>> AsRef<T> for OptValue<A, L, E> {
+    fn as_ref(&self) -> &T {
+        match self {
+            Self::Given(value) => value.as_ref(),
+            Self::Env(value) => value.as_ref(),
+            Self::Default(value) => value.as_ref(),
+        }
+    }
+}
+
+impl<A: Display, L: Display, E: Display> Display for OptValue<A, L, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Given(value) => value.fmt(f),
+            Self::Env(value) => value.fmt(f),
+            Self::Default(value) => value.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+/// An iterator that splits a Windows command line into arguments, using
+/// the same rules as `CommandLineToArgvW` and the MSVC runtime.
+///
+/// Arguments are separated by spaces and tabs, and can be quoted with
+/// `"` to include them. Inside quotes, `""` is a literal `"`.
+/// Backslashes are only special before a `"`: `2n` backslashes followed
+/// by a `"` become `n` backslashes and a quote that opens or closes a
+/// quoted section, while `2n + 1` backslashes followed by a `"` become
+/// `n` backslashes and a literal `"`. The first argument is the program
+/// name, which ends at the next space or, if it starts with a quote, at
+/// the closing quote, and never has backslashes removed.
+///
+/// The command line can be UTF-8 (`[u8]`) or UTF-16 (`[u16]`), and is
+/// unescaped in place, so each argument is borrowed from it and can be
+/// passed straight to [`Options::new`][crate::Options::new] without
+/// allocating.
+///
+/// # Example
+///
+/// ```
+/// # use getargs::{Opt, Options, WindowsArgs};
+/// #
+/// let mut line = *br#"prog.exe -o "C:\Program Files\out.txt" "say \"hi\"" a\\b"#;
+/// let mut args = WindowsArgs::new(&mut line);
+///
+/// assert_eq!(args.next(), Some(&b"prog.exe"[..]));
+///
+/// let mut opts = Options::new(args);
+///
+/// assert_eq!(opts.next_opt(), Ok(Some(Opt::Short(b'o'))));
+/// assert_eq!(opts.value(), Ok(&br"C:\Program Files\out.txt"[..]));
+/// assert_eq!(opts.next_positional(), Some(&br#"say "hi""#[..]));
+/// assert_eq!(opts.next_positional(), Some(&br"a\\b"[..]));
+/// assert_eq!(opts.next_positional(), None);
+/// ```
+#[derive(Debug)]
+pub struct WindowsArgs<'a, T> {
+    rest: &'a mut [T],
+    program_name: bool,
+}
+
+impl<'a, T> WindowsArgs<'a, T> {
+    /// Creates a new [`WindowsArgs`] over the command line `line`,
+    /// whose first argument is the program name.
+    pub fn new(line: &'a mut [T]) -> Self {
+        Self {
+            rest: line,
+            program_name: true,
+        }
+    }
+
+    /// Sets whether the first argument is parsed as a program name.
+    /// This is `true` by default, and should be set to `false` for
+    /// command lines that only contain the arguments.
+    pub fn program_name(mut self, program_name: bool) -> Self {
+        self.program_name = program_name;
+        self
+    }
+}
+
+impl<'a, T: Copy + PartialEq + From<u8>> Iterator for WindowsArgs<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let is_space = |unit: T| unit == T::from(b' ') || unit == T::from(b'\t');
+        let quote = T::from(b'"');
+        let backslash = T::from(b'\\');
+
+        let start = self.rest.iter().position(|&unit| !is_space(unit))?;
+        let rest = &mut core::mem::take(&mut self.rest)[start..];
+
+        if core::mem::replace(&mut self.program_name, false) {
+            // Program names can't contain quotes, so there is nothing to
+            // unescape.
+            if rest[0] == quote {
+                let len = rest[1..].iter().position(|&unit| unit == quote);
+                let (arg, tail) = rest.split_at_mut(len.map_or(rest.len(), |len| len + 1));
+                self.rest = tail.get_mut(1..).unwrap_or_default();
+                return Some(&arg[1..]);
+            }
+
+            let len = rest.iter().position(|&unit| is_space(unit));
+            let (arg, tail) = rest.split_at_mut(len.unwrap_or(rest.len()));
+            self.rest = tail;
+            return Some(arg);
+        }
+
+        let mut read = 0;
+        let mut write = 0;
+        let mut quoted = false;
+
+        while read < rest.len() {
+            let unit = rest[read];
+
+            if unit == backslash {
+                let count = rest[read..]
+                    .iter()
+                    .take_while(|&&unit| unit == backslash)
+                    .count();
+                let before_quote = rest.get(read + count) == Some(&quote);
+                let literal = if before_quote { count / 2 } else { count };
+
+                rest[write..write + literal].fill(backslash);
+                write += literal;
+                read += count;
+
+                if before_quote && count % 2 == 1 {
+                    rest[write] = quote;
+                    write += 1;
+                    read += 1;
+                }
+            } else if unit == quote {
+                if quoted && rest.get(read + 1) == Some(&quote) {
+                    rest[write] = quote;
+                    write += 1;
+                    read += 2;
+                } else {
+                    quoted = !quoted;
+                    read += 1;
+                }
+            } else if !quoted && is_space(unit) {
+                break;
+            } else {
+                rest[write] = unit;
+                write += 1;
+                read += 1;
+            }
+        }
+
+        let (arg, tail) = rest.split_at_mut(read);
+        self.rest = tail;
+        Some(&arg[..write])
+    }
+}